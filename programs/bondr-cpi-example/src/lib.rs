@@ -0,0 +1,316 @@
+#![allow(unexpected_cfgs, deprecated)]
+
+// Example integrator: every authority gets an agent PDA that acts in Bondr on its behalf.
+// An agent can fund and release escrows as the sender, or accept and claim them as the receiver.
+// Agents hold no data and are owned by the system program, so they can pay for Bondr's accounts.
+// The program also implements Bondr's settlement callback, recording settled escrows in a log.
+
+use anchor_lang::prelude::*;
+use bondr::{
+    cpi::accounts::{AcceptEscrow, ClaimPayment, InitializeEscrow, ReleasePayment},
+    program::Bondr,
+};
+
+declare_id!("3YViugTKS2dR4TWnKYLn1SyKicY6n85jQLnWRs3UX1Bf");
+
+pub const AGENT_SEED: &[u8] = b"agent";
+pub const SETTLEMENT_LOG_SEED: &[u8] = b"settlement_log";
+
+#[program]
+pub mod bondr_cpi_example {
+    use super::*;
+
+    // 1. The agent funds a SOL escrow, Bondr hands back its escrow id
+    pub fn fund_escrow(ctx: Context<FundEscrow>, amount: u64, reference_seed: u8) -> Result<u64> {
+        let authority = ctx.accounts.authority.key();
+        let seeds: &[&[u8]] = &[AGENT_SEED, authority.as_ref(), &[ctx.bumps.agent]];
+        let signer = &[seeds];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.bondr_program.to_account_info(),
+            InitializeEscrow {
+                sender: ctx.accounts.agent.to_account_info(),
+                receiver: ctx.accounts.receiver.to_account_info(),
+                escrow: ctx.accounts.escrow.to_account_info(),
+                vault: ctx.accounts.vault.to_account_info(),
+                sender_stats: ctx.accounts.sender_stats.to_account_info(),
+                client_multisig: None,
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: None,
+                sender_token_account: None,
+                escrow_token_account: None,
+                token_mint: None,
+                associated_token_program: None,
+            },
+            signer,
+        );
+        let escrow_id = bondr::cpi::initialize_escrow(
+            cpi_ctx,
+            amount,
+            reference_seed,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?
+        .get();
+
+        msg!("Funded Bondr escrow {}", escrow_id);
+        Ok(escrow_id)
+    }
+
+    // 2. The receiving agent accepts the terms
+    pub fn accept_escrow(
+        ctx: Context<AcceptAsAgent>,
+        reference_seed: u8,
+        terms_hash: [u8; 32],
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let seeds: &[&[u8]] = &[AGENT_SEED, authority.as_ref(), &[ctx.bumps.agent]];
+        let signer = &[seeds];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.bondr_program.to_account_info(),
+            AcceptEscrow {
+                receiver: ctx.accounts.agent.to_account_info(),
+                escrow: ctx.accounts.escrow.to_account_info(),
+            },
+            signer,
+        );
+        bondr::cpi::accept_escrow(cpi_ctx, reference_seed, terms_hash)
+    }
+
+    // 3. The funding agent releases the whole escrow
+    pub fn release_escrow(ctx: Context<ReleaseAsAgent>, reference_seed: u8) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let seeds: &[&[u8]] = &[AGENT_SEED, authority.as_ref(), &[ctx.bumps.agent]];
+        let signer = &[seeds];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.bondr_program.to_account_info(),
+            ReleasePayment {
+                client: ctx.accounts.agent.to_account_info(),
+                escrow: ctx.accounts.escrow.to_account_info(),
+                vault: ctx.accounts.vault.to_account_info(),
+            },
+            signer,
+        );
+        bondr::cpi::release_payment(cpi_ctx, reference_seed, None)
+    }
+
+    // 4. The receiving agent claims into itself, paying the rent of its Bondr records.
+    // Remaining accounts are forwarded for escrows with a settlement callback, none skips it
+    pub fn claim_escrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAsAgent<'info>>,
+        reference_seed: u8,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let seeds: &[&[u8]] = &[AGENT_SEED, authority.as_ref(), &[ctx.bumps.agent]];
+        let signer = &[seeds];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.bondr_program.to_account_info(),
+            ClaimPayment {
+                client: ctx.accounts.client.to_account_info(),
+                freelancer: ctx.accounts.agent.to_account_info(),
+                escrow: ctx.accounts.escrow.to_account_info(),
+                vault: ctx.accounts.vault.to_account_info(),
+                receiver_stats: ctx.accounts.receiver_stats.to_account_info(),
+                client_stats: ctx.accounts.client_stats.to_account_info(),
+                badge: None,
+                counterparty: Some(ctx.accounts.counterparty.to_account_info()),
+                reputation_config: ctx.accounts.reputation_config.to_account_info(),
+                receipt: ctx.accounts.receipt.to_account_info(),
+                multisig: None,
+                payout_destination: ctx.accounts.payout_destination.to_account_info(),
+                payout_approval: None,
+                payout_multisig: None,
+                receiver_sol: ctx.accounts.agent.to_account_info(),
+                escrow_token_account: None,
+                receiver_token_account: None,
+                token_mint: None,
+                token_program: None,
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            signer,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        bondr::cpi::claim_payment(cpi_ctx, reference_seed, false)
+    }
+
+    pub fn init_settlement_log(ctx: Context<InitSettlementLog>) -> Result<()> {
+        ctx.accounts.log.set_inner(SettlementLog {
+            authority: ctx.accounts.authority.key(),
+            settled: 0,
+            last_escrow_id: 0,
+            last_outcome: 0,
+            last_amount: 0,
+            bump: ctx.bumps.log,
+        });
+        Ok(())
+    }
+
+    // Bondr's settlement callback, registered by passing EscrowCallback { program, accounts: [log] }
+    // to initialize_escrow or accept_bid. Only a Bondr escrow can sign as `escrow`, but anyone
+    // can fund one that registers any log, so the log only records escrows its authority funded
+    pub fn on_bondr_settlement(
+        ctx: Context<OnBondrSettlement>,
+        escrow_id: u64,
+        outcome: u8,
+        amount: u64,
+    ) -> Result<()> {
+        require_keys_eq!(ctx.accounts.log.authority, ctx.accounts.escrow.sender);
+
+        let log = &mut ctx.accounts.log;
+        log.settled += 1;
+        log.last_escrow_id = escrow_id;
+        log.last_outcome = outcome;
+        log.last_amount = amount;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SettlementLog {
+    pub authority: Pubkey,
+    pub settled: u32,
+    pub last_escrow_id: u64,
+    pub last_outcome: u8, // bondr::SettlementOutcome as u8
+    pub last_amount: u64,
+    pub bump: u8,
+}
+
+// Bondr validates every account it receives, the example only derives the agent.
+
+#[derive(Accounts)]
+pub struct FundEscrow<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, seeds = [AGENT_SEED, authority.key().as_ref()], bump)]
+    pub agent: SystemAccount<'info>,
+
+    /// CHECK: any receiver, a wallet or another program's PDA
+    pub receiver: UncheckedAccount<'info>,
+
+    /// CHECK: created by Bondr
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: created by Bondr on the agent's first escrow
+    #[account(mut)]
+    pub sender_stats: UncheckedAccount<'info>,
+
+    pub bondr_program: Program<'info, Bondr>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAsAgent<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [AGENT_SEED, authority.key().as_ref()], bump)]
+    pub agent: SystemAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+
+    pub bondr_program: Program<'info, Bondr>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseAsAgent<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, seeds = [AGENT_SEED, authority.key().as_ref()], bump)]
+    pub agent: SystemAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    pub vault: UncheckedAccount<'info>,
+
+    pub bondr_program: Program<'info, Bondr>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAsAgent<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, seeds = [AGENT_SEED, authority.key().as_ref()], bump)]
+    pub agent: SystemAccount<'info>,
+
+    /// CHECK: the escrow sender, gets the escrow rent back
+    #[account(mut)]
+    pub client: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    #[account(mut)]
+    pub receiver_stats: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    #[account(mut)]
+    pub client_stats: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    #[account(mut)]
+    pub counterparty: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    pub reputation_config: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    #[account(mut)]
+    pub receipt: UncheckedAccount<'info>,
+
+    /// CHECK: checked by Bondr
+    pub payout_destination: UncheckedAccount<'info>,
+
+    pub bondr_program: Program<'info, Bondr>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitSettlementLog<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SettlementLog::INIT_SPACE,
+        seeds = [SETTLEMENT_LOG_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub log: Account<'info, SettlementLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OnBondrSettlement<'info> {
+    // the settling escrow, still open during the callback
+    #[account(signer)]
+    pub escrow: Box<Account<'info, bondr::Escrow>>,
+
+    #[account(mut)]
+    pub log: Account<'info, SettlementLog>,
+}
@@ -5,6 +5,67 @@ pub const PROFESSIONAL_METADATA_URI: &str =
     "https://gateway.pinata.cloud/ipfs/bafkreibjqolqul673vv3if5hgavqzegtwuxp6bi5ulfkyhktuiapqx5aqm";
 pub const ELITE_METADATA_URI: &str =
     "https://gateway.pinata.cloud/ipfs/bafkreifcjchjznq2psd64rofzxf7kewxbinbgftp67mgqazyal7ojgezlm";
+// client badges, CIDs of client/public/badges/client-*.json
+pub const VERIFIED_CLIENT_METADATA_URI: &str =
+    "https://gateway.pinata.cloud/ipfs/bafkreibtccpz6v6swf2ji5nhvtkcftp5mqtb5ijt6quq6horhdcvdjzfpe";
+pub const PROFESSIONAL_CLIENT_METADATA_URI: &str =
+    "https://gateway.pinata.cloud/ipfs/bafkreibiplozvkg2fjwhhinwvmpi33cj64h56xlo24rageobhjawzrjf5q";
+pub const ELITE_CLIENT_METADATA_URI: &str =
+    "https://gateway.pinata.cloud/ipfs/bafkreiddlso4qh6qoox4td6yrvmifme2riv47rd33d4yoatz6ycuv6ugb4";
+
+//METADATA NAME
+pub const VERIFIED_BADGE_NAME: &str = "Bondr Verified Badge";
+pub const PROFESSIONAL_BADGE_NAME: &str = "Bondr Professional Badge";
+pub const ELITE_BADGE_NAME: &str = "Bondr Elite Badge";
+pub const VERIFIED_CLIENT_BADGE_NAME: &str = "Bondr Verified Client Badge";
+pub const PROFESSIONAL_CLIENT_BADGE_NAME: &str = "Bondr Professional Client Badge";
+pub const ELITE_CLIENT_BADGE_NAME: &str = "Bondr Elite Client Badge";
+
+//DEFAULT TIER THRESHOLDS (seeded into ReputationConfig, admin can change them later)
+pub const VERIFIED_MIN_ESCROWS: u32 = 3;
+pub const PROFESSIONAL_MIN_ESCROWS: u32 = 10;
+pub const ELITE_MIN_ESCROWS: u32 = 25;
+pub const VERIFIED_MIN_VALUE: u64 = 50_000_000; // 0.05 SOL
+pub const PROFESSIONAL_MIN_VALUE: u64 = 200_000_000; // 0.2 SOL
+pub const ELITE_MIN_VALUE: u64 = 500_000_000; // 0.5 SOL
+pub const DEFAULT_MIN_ESCROW_VALUE: u64 = 10_000_000; // 0.01 SOL, smaller claims don't count towards a tier
+pub const DEFAULT_DECAY_PERIOD: i64 = 180 * 24 * 60 * 60; // 180 days per tier step
+pub const DEFAULT_FAILURE_PENALTY: u32 = 3; // each failed escrow cancels out 3 completed ones
+
+//ESCROW REVIEW
+pub const REVIEW_WINDOW: i64 = 7 * 24 * 60 * 60; // client has 7 days to release or dispute submitted work
+
+//MULTISIG TIMELOCK
+pub const MAX_MULTISIG_TIMELOCK: i64 = 30 * 24 * 60 * 60; // a vetoable release waits 30 days at most
+
+//JOB BIDDING
+pub const MAX_BID_TIMELINE: i64 = 366 * 24 * 60 * 60; // an accepted bid's escrow is due within a year
+
+//RETAINER
+pub const MAX_RETAINER_PERIOD_LENGTH: i64 = 366 * 24 * 60 * 60; // a retainer period lasts a year at most
+
+//SETTLEMENT CALLBACK
+// sha256("global:on_bondr_settlement")[..8], the Anchor discriminator of the integrator's handler
+pub const SETTLEMENT_CALLBACK_DISCRIMINATOR: [u8; 8] = [9, 72, 162, 228, 28, 156, 107, 159];
+
+//ACCOUNT VERSIONS (bump when appending fields, migrate_* upgrades older accounts)
+pub const ESCROW_VERSION: u8 = 11;
+pub const USER_STATS_VERSION: u8 = 1;
+pub const FREELANCER_BADGE_VERSION: u8 = 1;
+pub const CLIENT_MULTISIG_VERSION: u8 = 4;
+pub const RETAINER_VERSION: u8 = 1;
+pub const JOB_VERSION: u8 = 1;
+pub const PAYOUT_DESTINATION_VERSION: u8 = 1;
 
 //CONSTANTS
-pub const MAX_MULTISIG_MEMBERS: usize = 5;
\ No newline at end of file
+pub const INLINE_MULTISIG_MEMBERS: usize = 5; // members stored in the fixed ClientMultisig arrays
+pub const MAX_MULTISIG_MEMBERS: usize = 32;
+pub const MAX_TEAM_RECIPIENTS: usize = 5;
+pub const MAX_CALLBACK_ACCOUNTS: usize = 4; // accounts registered for an escrow's settlement callback
+pub const TOTAL_BPS: u16 = 10_000;
+pub const REPUTATION_TIER_COUNT: usize = 3; // Verified, Professional, Elite
+pub const MAX_TIER_NAME_LEN: usize = 32;
+pub const MAX_TIER_URI_LEN: usize = 200;
+pub const MAX_PRICED_MINTS: usize = 16;
+pub const MIN_RATING: u8 = 1;
+pub const MAX_RATING: u8 = 5;
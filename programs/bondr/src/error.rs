@@ -44,4 +44,124 @@ pub enum BondrError {
     MultisigPendingEscrowMismatch,
     #[msg("Multisig threshold is not met, can't withdraw amount")]
     MultisigThresholdNotMet,
+    #[msg("Signer is not the reputation config admin")]
+    UnauthorizedAdmin,
+    #[msg("Invalid reputation tier config")]
+    InvalidTierConfig,
+    #[msg("Mint price table is full")]
+    PriceTableFull,
+    #[msg("Multisig account does not match the escrow")]
+    MultisigMismatch,
+    #[msg("Rating must be between 1 and 5")]
+    InvalidRating,
+    #[msg("Signer is not a party of this escrow")]
+    NotEscrowParty,
+    #[msg("Decay period can't be negative")]
+    InvalidDecayPeriod,
+    #[msg("Deadline must be in the future")]
+    InvalidDeadline,
+    #[msg("Escrow has no deadline or it has not passed yet")]
+    EscrowNotExpired,
+    #[msg("Asset, badge authority and MPL Core program must be provided together")]
+    MissingBadgeAssetAccounts,
+    #[msg("Asset is not the badge's latest asset")]
+    InvalidBadgeAsset,
+    #[msg("Account is not a migratable Bondr account")]
+    InvalidMigrationAccount,
+    #[msg("Account is already on the latest version")]
+    AlreadyMigrated,
+    #[msg("Release amount exceeds the unreleased escrow balance")]
+    ReleaseExceedsEscrow,
+    #[msg(
+        "SOL escrow chunks and the locked remainder must each cover the vault rent-exempt minimum"
+    )]
+    ReleaseBelowRentMinimum,
+    #[msg("Nothing released to claim")]
+    NothingToClaim,
+    #[msg("Receiver can't change once part of the escrow is released")]
+    ReceiverChangeAfterRelease,
+    #[msg("New escrow, vault and token accounts are required to change the receiver")]
+    MissingReceiverChangeAccounts,
+    #[msg("Escrow has not been accepted by the receiver")]
+    EscrowNotAccepted,
+    #[msg("Escrow already accepted")]
+    AlreadyAccepted,
+    #[msg("Terms hash doesn't match the escrow terms")]
+    TermsMismatch,
+    #[msg("No work submitted for review")]
+    NoWorkSubmitted,
+    #[msg("Review window has ended")]
+    ReviewWindowClosed,
+    #[msg("Submission already disputed")]
+    AlreadyDisputed,
+    #[msg("Vesting must start before it ends, with the cliff in between")]
+    InvalidVestingSchedule,
+    #[msg("Retainer needs a positive period amount, a period of up to 366 days and at least one period")]
+    InvalidRetainerTerms,
+    #[msg("SOL retainer periods must cover the vault rent-exempt minimum")]
+    PeriodBelowRentMinimum,
+    #[msg("Funding exceeds the remaining retainer periods")]
+    RetainerOverfunded,
+    #[msg("No funded retainer period is due")]
+    NoPeriodDue,
+    #[msg("Retainer is cancelled or has paid all periods")]
+    RetainerEnded,
+    #[msg("Retainer is already in that state")]
+    RetainerPauseUnchanged,
+    #[msg("Team needs 2-5 distinct recipients led by the receiver, with splits adding up to 10000 bps")]
+    InvalidTeamSplits,
+    #[msg("Team escrows can't change receiver")]
+    TeamReceiverChange,
+    #[msg("Escrow already has a receiver")]
+    NotABounty,
+    #[msg("Receiver can't be the default key")]
+    InvalidReceiver,
+    #[msg("Job budget must cover the vault's rent minimum and stay within the amount limit")]
+    InvalidJobBudget,
+    #[msg("Bid must be within the job budget, cover the vault's rent minimum and take up to 366 days")]
+    InvalidBid,
+    #[msg("Token mint doesn't match the job")]
+    JobMintMismatch,
+    #[msg("This member has not approved.")]
+    NotApproved,
+    #[msg("This member has already rejected.")]
+    AlreadyRejected,
+    #[msg("The multisig rejected this escrow, it can only be refunded")]
+    EscrowRejected,
+    #[msg("Release is timelocked, it can be claimed once the delay has passed")]
+    ReleaseTimelocked,
+    #[msg("No timelocked release to veto")]
+    NoTimelockedRelease,
+    #[msg("Multisig threshold exceeds the members' total weight")]
+    MultisigThresholdUnachievable,
+    #[msg("Invalid payout destination")]
+    InvalidPayoutDestination,
+    #[msg("Payout destination has no receiver multisig")]
+    NoReceiverMultisig,
+    #[msg("The receiver group has not approved this payout")]
+    PayoutNotApproved,
+    #[msg("Payout must go to the receiver's registered treasury")]
+    PayoutDestinationMismatch,
+    #[msg("Invalid settlement callback")]
+    InvalidCallback,
+    #[msg("Settlement callback accounts don't match the escrow's")]
+    CallbackAccountsMismatch,
+    #[msg("Counterparty record is required once the reputation config exists")]
+    MissingCounterpartyRecord,
+    #[msg("Badges can only be updated once the reputation config exists")]
+    MissingReputationConfig,
+    #[msg("Escrow is not settled yet")]
+    EscrowNotSettled,
+    #[msg("Transfer doesn't match the escrow's asset")]
+    EscrowAssetMismatch,
+    #[msg("Submitted work can only be refunded while disputed")]
+    WorkNotDisputed,
+    #[msg("Multisig escrows are released through member approvals")]
+    MultisigReleaseRequired,
+    #[msg("Not enough member signatures to change the multisig")]
+    MultisigChangeNotApproved,
+    #[msg("Streaming escrows release on their vesting schedule, not on submitted work")]
+    StreamingReviewUnsupported,
+    #[msg("Timestamp out of range")]
+    TimestampOverflow,
 }
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::ReputationTier;
+use crate::{ReputationTier, SettlementOutcome};
 
 #[event]
 pub struct EscrowCreateEvent {
@@ -23,6 +23,15 @@ pub struct ClientMultisigCreated {
     pub multisig: Pubkey,
     pub member_count: u8,
     pub threshold: u8,
+    pub timelock: i64,
+    pub weights: Vec<u8>,
+}
+
+#[event]
+pub struct MultisigMembersAdded {
+    pub multisig: Pubkey,
+    pub added: u8,
+    pub member_count: u8,
 }
 
 #[event]
@@ -30,4 +39,267 @@ pub struct MultisigApprovalAdded {
     pub multisig: Pubkey,
     pub member: Pubkey,
     pub escrow: Pubkey,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct MultisigApprovalRevoked {
+    pub multisig: Pubkey,
+    pub member: Pubkey,
+    pub escrow: Pubkey,
+    pub unreleased: bool, // the revocation took back the escrow's release
+}
+
+#[event]
+pub struct MultisigRejectionAdded {
+    pub multisig: Pubkey,
+    pub member: Pubkey,
+    pub escrow: Pubkey,
+    pub rejected: bool, // the threshold became unreachable, the escrow is refund-only now
+}
+
+#[event]
+pub struct MultisigReleaseVetoed {
+    pub multisig: Pubkey,
+    pub member: Pubkey,
+    pub escrow: Pubkey,
+}
+
+#[event]
+pub struct ReputationConfigUpdated {
+    pub admin: Pubkey,
+    pub config: Pubkey,
+}
+
+#[event]
+pub struct EscrowCancelled {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EscrowReclaimed {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReviewSubmitted {
+    pub receipt: Pubkey,
+    pub reviewer: Pubkey,
+    pub reviewee: Pubkey,
+    pub rating: u8,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct ReputationRefreshed {
+    pub freelancer: Pubkey,
+    pub tier: ReputationTier,
+    pub effective_tier: ReputationTier,
+    pub is_stale: bool,
+}
+
+#[event]
+pub struct PaymentReleased {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+    pub released_amount: u64,
+}
+
+#[event]
+pub struct EscrowToppedUp {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+    pub new_amount: u64,
+}
+
+#[event]
+pub struct EscrowAmended {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+    pub deadline: Option<i64>,
+}
+
+#[event]
+pub struct EscrowAccepted {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow: Pubkey,
+    pub terms_hash: [u8; 32],
+}
+
+#[event]
+pub struct EscrowDeclined {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WorkSubmitted {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow: Pubkey,
+    pub deliverable_hash: [u8; 32],
+    pub review_ends_at: i64,
+}
+
+#[event]
+pub struct WorkDisputed {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow: Pubkey,
+    pub deliverable_hash: [u8; 32],
+}
+
+#[event]
+pub struct RetainerCreated {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub retainer: Pubkey,
+    pub period_amount: u64,
+    pub period_length: i64,
+    pub max_periods: u32,
+    pub next_period_at: i64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct RetainerFunded {
+    pub retainer: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct RetainerPeriodsReleased {
+    pub retainer: Pubkey,
+    pub periods: u32,
+    pub amount: u64,
+    pub periods_released: u32,
+}
+
+#[event]
+pub struct RetainerPauseChanged {
+    pub retainer: Pubkey,
+    pub is_paused: bool,
+    pub next_period_at: i64,
+}
+
+#[event]
+pub struct RetainerCancelled {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub retainer: Pubkey,
+    pub refund: u64,
+}
+
+#[event]
+pub struct RetainerClaimed {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub retainer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReceiverAssigned {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub bounty: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct JobPosted {
+    pub client: Pubkey,
+    pub job: Pubkey,
+    pub budget: u64,
+    pub token_mint: Option<Pubkey>,
+    pub description_hash: [u8; 32],
+}
+
+#[event]
+pub struct BidSubmitted {
+    pub job: Pubkey,
+    pub bid: Pubkey,
+    pub freelancer: Pubkey,
+    pub price: u64,
+    pub timeline: i64,
+}
+
+#[event]
+pub struct BidAccepted {
+    pub job: Pubkey,
+    pub bid: Pubkey,
+    pub freelancer: Pubkey,
+    pub escrow: Pubkey,
+    pub price: u64,
+    pub refund: u64,
+}
+
+#[event]
+pub struct JobCancelled {
+    pub client: Pubkey,
+    pub job: Pubkey,
+    pub refund: u64,
+}
+
+#[event]
+pub struct PayoutDestinationSet {
+    pub owner: Pubkey,
+    pub destination: Pubkey,
+    pub treasury: Pubkey,
+    pub receiver_multisig: Option<Pubkey>,
+}
+
+#[event]
+pub struct PayoutApproved {
+    pub destination: Pubkey,
+    pub target: Pubkey,
+    pub member: Pubkey,
+    pub approved_weight: u16,
+}
+
+#[event]
+pub struct EscrowCallbackSet {
+    pub escrow: Pubkey,
+    pub program: Pubkey,
+    pub accounts: Vec<Pubkey>,
+}
+
+#[event]
+pub struct SettlementCallbackInvoked {
+    pub escrow: Pubkey,
+    pub program: Pubkey,
+    pub escrow_id: u64,
+    pub outcome: SettlementOutcome,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SettlementCallbackSkipped {
+    pub escrow: Pubkey,
+    pub program: Pubkey,
+    pub escrow_id: u64,
+    pub outcome: SettlementOutcome,
+    pub amount: u64,
+}
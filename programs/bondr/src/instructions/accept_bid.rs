@@ -0,0 +1,255 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
+    Bid, BidAccepted, Escrow, EscrowAsset, EscrowCallback, EscrowCallbackSet, EscrowCreateEvent,
+    Job, UserStats, ESCROW_VERSION, MAX_CALLBACK_ACCOUNTS, MAX_TEAM_RECIPIENTS, USER_STATS_VERSION,
+};
+
+#[derive(Accounts)]
+#[instruction(job_reference_seed: u8, reference_seed: u8)]
+pub struct AcceptBid<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    // closed in instruction logic once the budget is moved out
+    #[account(
+        mut,
+        seeds = [b"job", client.key().as_ref(), &[job_reference_seed]],
+        bump = job.bump
+    )]
+    pub job: Box<Account<'info, Job>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", job.key().as_ref()],
+        bump = job.vault_bump
+    )]
+    pub job_vault: SystemAccount<'info>,
+
+    // the winning bid, its rent goes back to the freelancer
+    #[account(
+        mut,
+        close = freelancer,
+        seeds = [b"bid", job.key().as_ref(), freelancer.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Box<Account<'info, Bid>>,
+
+    #[account(mut)]
+    pub freelancer: SystemAccount<'info>,
+
+    // same PDAs as an escrow created through initialize_escrow
+    #[account(
+        init,
+        payer = client,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", client.key().as_ref(), freelancer.key().as_ref(), &[reference_seed]],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", client.key().as_ref(), freelancer.key().as_ref(), &[reference_seed]],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = client,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", client.key().as_ref()],
+        bump
+    )]
+    pub client_stats: Box<Account<'info, UserStats>>,
+
+    // SPL jobs
+    #[account(mut)]
+    pub job_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub client_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = client,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+    )]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AcceptBid<'info> {
+    pub fn accept_bid(
+        &mut self,
+        job_reference_seed: u8,
+        reference_seed: u8,
+        callback: Option<EscrowCallback>,
+        bump: u8,
+        vault_bump: u8,
+        stats_bump: u8,
+    ) -> Result<()> {
+        // 1. the bid price funds the escrow, the rest of the budget is refunded
+        let price = self.bid.price;
+        let refund = self.job.budget - price;
+        let is_token_transfer = self.job.token_mint.is_some();
+        let client_key = self.client.key();
+        let job_key = self.job.key();
+        let job_vault_seeds = &[b"vault", job_key.as_ref(), &[self.job.vault_bump]];
+        let job_seeds = &[
+            b"job",
+            client_key.as_ref(),
+            &[job_reference_seed],
+            &[self.job.bump],
+        ];
+        if let Some(callback) = &callback {
+            require!(callback.is_valid(), BondrError::InvalidCallback);
+        }
+
+        // 2. moving the budget out of the job
+        if let Some(mint) = self.job.token_mint {
+            validate_token_accounts(
+                &self.token_program,
+                &self.job_token_account,
+                &self.escrow_token_account,
+                &self.token_mint,
+            )?;
+            require_keys_eq!(
+                self.token_mint.as_ref().unwrap().key(),
+                mint,
+                BondrError::JobMintMismatch
+            );
+
+            transfer_spl_tokens(
+                self.job_token_account.as_ref().unwrap(),
+                self.escrow_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.job.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                price,
+                Some(&[&job_seeds[..]]),
+            )?;
+            if refund > 0 {
+                transfer_spl_tokens(
+                    self.job_token_account.as_ref().unwrap(),
+                    self.client_token_account
+                        .as_ref()
+                        .ok_or(BondrError::MissingTokenAccounts)?,
+                    self.token_mint.as_ref().unwrap(),
+                    &self.job.to_account_info(),
+                    self.token_program.as_ref().unwrap(),
+                    refund,
+                    Some(&[&job_seeds[..]]),
+                )?;
+            }
+        } else {
+            transfer_sol(
+                &self.job_vault.to_account_info(),
+                &self.vault.to_account_info(),
+                &self.system_program,
+                price,
+                Some(&[&job_vault_seeds[..]]),
+            )?;
+            if refund > 0 {
+                transfer_sol(
+                    &self.job_vault.to_account_info(),
+                    &self.client.to_account_info(),
+                    &self.system_program,
+                    refund,
+                    Some(&[&job_vault_seeds[..]]),
+                )?;
+            }
+        }
+
+        // 3. the bid is the freelancer's signed offer, so the escrow starts accepted on its terms
+        let now = Clock::get()?.unix_timestamp;
+        let deadline = now
+            .checked_add(self.bid.timeline)
+            .ok_or(BondrError::TimestampOverflow)?;
+        self.escrow.set_inner(Escrow {
+            version: ESCROW_VERSION,
+            sender: client_key,
+            receiver: self.freelancer.key(),
+            amount: price,
+            is_released: false,
+            bump,
+            vault_bump,
+            client_multisig: None,
+            escrow_id: self.client_stats.escrows_funded as u64, // zero on a freshly created stats account
+            deadline: Some(deadline),
+            released_amount: 0,
+            claimed_amount: 0,
+            terms_hash: self.bid.proposal_hash,
+            is_accepted: true,
+            deliverable_hash: [0; 32],
+            review_ends_at: None,
+            is_disputed: false,
+            vesting: None,
+            team_size: 0,
+            team_recipients: [Pubkey::default(); MAX_TEAM_RECIPIENTS],
+            team_bps: [0; MAX_TEAM_RECIPIENTS],
+            team_claimed: [0; MAX_TEAM_RECIPIENTS],
+            is_rejected: false,
+            releasable_at: None,
+            callback_program: None, // see set_callback below
+            callback_account_count: 0,
+            callback_accounts: [Pubkey::default(); MAX_CALLBACK_ACCOUNTS],
+            asset: EscrowAsset::new(self.job.token_mint),
+            multisig_released: 0,
+        });
+
+        if let Some(callback) = callback {
+            self.escrow.set_callback(&callback);
+            emit!(EscrowCallbackSet {
+                escrow: self.escrow.key(),
+                program: callback.program,
+                accounts: callback.accounts,
+            });
+        }
+
+        // 4. init user stats
+        if self.client_stats.user == Pubkey::default() {
+            self.client_stats.set_inner(UserStats {
+                version: USER_STATS_VERSION,
+                user: client_key,
+                completed_escrows: 0,
+                bump: stats_bump,
+                ..Default::default()
+            });
+        }
+        self.client_stats.escrows_funded += 1;
+
+        // 5. the job is filled, other bidders withdraw their bids
+        self.job.close(self.client.to_account_info())?;
+
+        emit!(EscrowCreateEvent {
+            sender: client_key,
+            receiver: self.freelancer.key(),
+            amount: price,
+            reference_seed,
+            is_token_transfer
+        });
+        emit!(BidAccepted {
+            job: job_key,
+            bid: self.bid.key(),
+            freelancer: self.freelancer.key(),
+            escrow: self.escrow.key(),
+            price,
+            refund,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, Escrow, EscrowAccepted};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct AcceptEscrow<'info> {
+    pub receiver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.sender.as_ref(), receiver.key().as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> AcceptEscrow<'info> {
+    pub fn accept_escrow(&mut self, terms_hash: [u8; 32]) -> Result<()> {
+        // 1. the receiver agrees once, to exactly the terms the sender funded
+        require!(!self.escrow.is_accepted, BondrError::AlreadyAccepted);
+        require!(
+            self.escrow.terms_hash == terms_hash,
+            BondrError::TermsMismatch
+        );
+
+        // 2. from now on the escrow can be released
+        self.escrow.is_accepted = true;
+
+        emit!(EscrowAccepted {
+            sender: self.escrow.sender,
+            receiver: self.receiver.key(),
+            escrow: self.escrow.key(),
+            terms_hash,
+        });
+
+        Ok(())
+    }
+}
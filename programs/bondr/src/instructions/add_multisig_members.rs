@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, ClientMultisig, MultisigMembersAdded, MAX_MULTISIG_MEMBERS};
+
+#[derive(Accounts)]
+#[instruction(members: Vec<Pubkey>)]
+pub struct AddMultisigMembers<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    // grows by one MultisigMember per member past the inline ones
+    #[account(
+        mut,
+        seeds = [b"client_multisig", client.key().as_ref()],
+        bump = multisig.bump,
+        realloc = ClientMultisig::space(multisig.member_count as usize + members.len()),
+        realloc::payer = client,
+        realloc::zero = false
+    )]
+    pub multisig: Account<'info, ClientMultisig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddMultisigMembers<'info> {
+    // `signers` are existing members co-signing the change, the client counts if it's a member
+    pub fn add_members(
+        &mut self,
+        members: Vec<Pubkey>,
+        weights: Option<Vec<u8>>,
+        signers: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let client = self.client.to_account_info();
+        let multisig = &mut self.multisig;
+
+        // 1. membership only changes between escrows, votes in flight stay as they were cast
+        require!(
+            multisig.pending_escrow == Pubkey::default(),
+            BondrError::MultisigBusy
+        );
+
+        // 2. bounds checks, weights default to one vote per member
+        require!(
+            !members.is_empty()
+                && multisig.member_count as usize + members.len() <= MAX_MULTISIG_MEMBERS,
+            BondrError::InvalidMultisigConfig
+        );
+        let weights = weights.unwrap_or_else(|| vec![1u8; members.len()]);
+        require!(
+            weights.len() == members.len() && weights.iter().all(|&w| w > 0),
+            BondrError::InvalidMultisigConfig
+        );
+
+        // 3. new members add weight towards the same threshold, so the existing members must
+        //    approve them with as much weight as a release needs
+        let signed = std::iter::once(&client)
+            .chain(signers)
+            .filter(|info| info.is_signer)
+            .filter_map(|info| multisig.member_index(info.key))
+            .fold(0u32, |bitmap, index| bitmap | 1 << index);
+        require!(
+            multisig.bitmap_weight(signed) >= multisig.threshold as u16,
+            BondrError::MultisigChangeNotApproved
+        );
+
+        // 4. appending the members
+        for (&member, &weight) in members.iter().zip(weights.iter()) {
+            require!(
+                multisig.member_index(&member).is_none(),
+                BondrError::DuplicateMember
+            );
+            multisig.push_member(member, weight);
+        }
+
+        emit!(MultisigMembersAdded {
+            multisig: multisig.key(),
+            added: members.len() as u8,
+            member_count: multisig.member_count,
+        });
+
+        Ok(())
+    }
+}
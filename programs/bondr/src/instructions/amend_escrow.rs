@@ -0,0 +1,257 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
+    ClientMultisig, Escrow, EscrowAmended, EscrowAmendment,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8, is_token_transfer: bool, amendment: EscrowAmendment)]
+pub struct AmendEscrow<'info> {
+    // both parties co-sign every amendment
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub receiver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", sender.key().as_ref(), receiver.key().as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", sender.key().as_ref(), receiver.key().as_ref(), &[reference_seed]],
+        bump = escrow.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // Required for multisig escrows moved to a new receiver
+    #[account(mut)]
+    pub multisig: Option<Box<Account<'info, ClientMultisig>>>,
+
+    // Receiver change: the escrow PDAs are derived from the receiver, so funds and state move
+    // to a new escrow/vault pair and the old escrow is closed
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", sender.key().as_ref(), amendment.receiver.unwrap_or_default().as_ref(), &[amendment.new_reference_seed]],
+        bump,
+    )]
+    pub new_escrow: Option<Box<Account<'info, Escrow>>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", sender.key().as_ref(), amendment.receiver.unwrap_or_default().as_ref(), &[amendment.new_reference_seed]],
+        bump,
+    )]
+    pub new_vault: Option<SystemAccount<'info>>,
+
+    // SPL refund / move
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub sender_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = token_mint,
+        associated_token::authority = new_escrow,
+    )]
+    pub new_escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AmendEscrow<'info> {
+    pub fn amend_escrow(
+        &mut self,
+        reference_seed: u8,
+        is_token_transfer: bool,
+        amendment: EscrowAmendment,
+        new_escrow_bump: Option<u8>,
+        new_vault_bump: Option<u8>,
+    ) -> Result<()> {
+        // 1. terms only change while some of the escrow is still locked
+        require!(!self.escrow.is_released, BondrError::AlreadyReleased);
+        self.escrow
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+
+        let sender_key = self.sender.key();
+        let receiver_key = self.receiver.key();
+        let vault_seeds = &[
+            b"vault",
+            sender_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.vault_bump],
+        ];
+        let escrow_seeds = &[
+            b"escrow",
+            sender_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.bump],
+        ];
+
+        // 2. lower amount, the difference goes back to the sender
+        if let Some(new_amount) = amendment.amount {
+            require!(
+                new_amount < self.escrow.amount && new_amount > self.escrow.released_amount,
+                BondrError::InvalidAmount
+            );
+
+            let locked = new_amount - self.escrow.released_amount;
+            if !is_token_transfer {
+                require!(
+                    locked >= Rent::get()?.minimum_balance(0),
+                    BondrError::ReleaseBelowRentMinimum
+                );
+            }
+
+            let refund = self.escrow.amount - new_amount;
+            if is_token_transfer {
+                validate_token_accounts(
+                    &self.token_program,
+                    &self.escrow_token_account,
+                    &self.sender_token_account,
+                    &self.token_mint,
+                )?;
+
+                transfer_spl_tokens(
+                    self.escrow_token_account.as_ref().unwrap(),
+                    self.sender_token_account.as_ref().unwrap(),
+                    self.token_mint.as_ref().unwrap(),
+                    &self.escrow.to_account_info(),
+                    self.token_program.as_ref().unwrap(),
+                    refund,
+                    Some(&[&escrow_seeds[..]]),
+                )?;
+            } else {
+                transfer_sol(
+                    &self.vault.to_account_info(),
+                    &self.sender.to_account_info(),
+                    &self.system_program,
+                    refund,
+                    Some(&[&vault_seeds[..]]),
+                )?;
+            }
+            self.escrow.amount = new_amount;
+        }
+
+        // 3. new or no deadline
+        if amendment.remove_deadline {
+            self.escrow.deadline = None;
+        } else if let Some(deadline) = amendment.deadline {
+            require!(
+                deadline > Clock::get()?.unix_timestamp,
+                BondrError::InvalidDeadline
+            );
+            self.escrow.deadline = Some(deadline);
+        }
+
+        // 4. new terms, both parties sign the amendment
+        if let Some(terms_hash) = amendment.terms_hash {
+            self.escrow.terms_hash = terms_hash;
+        }
+
+        let amount = self.escrow.amount;
+        let deadline = self.escrow.deadline;
+
+        // 5. new receiver, moving everything to the new escrow
+        let mut escrow_key = self.escrow.key();
+        let mut current_receiver = receiver_key;
+        if let Some(new_receiver) = amendment.receiver {
+            require!(
+                self.escrow.released_amount == 0,
+                BondrError::ReceiverChangeAfterRelease
+            );
+            require!(new_receiver != sender_key, BondrError::SelfTransfer);
+            require!(!self.escrow.is_team(), BondrError::TeamReceiverChange);
+            let (Some(new_escrow), Some(new_vault)) = (&mut self.new_escrow, &self.new_vault)
+            else {
+                return err!(BondrError::MissingReceiverChangeAccounts);
+            };
+
+            if is_token_transfer {
+                validate_token_accounts(
+                    &self.token_program,
+                    &self.escrow_token_account,
+                    &self.new_escrow_token_account,
+                    &self.token_mint,
+                )?;
+
+                transfer_spl_tokens(
+                    self.escrow_token_account.as_ref().unwrap(),
+                    self.new_escrow_token_account.as_ref().unwrap(),
+                    self.token_mint.as_ref().unwrap(),
+                    &self.escrow.to_account_info(),
+                    self.token_program.as_ref().unwrap(),
+                    amount,
+                    Some(&[&escrow_seeds[..]]),
+                )?;
+            } else {
+                transfer_sol(
+                    &self.vault.to_account_info(),
+                    &new_vault.to_account_info(),
+                    &self.system_program,
+                    amount,
+                    Some(&[&vault_seeds[..]]),
+                )?;
+            }
+
+            // same escrow_id, so the receipt and reviews still line up with the sender's history
+            new_escrow.set_inner(Escrow {
+                receiver: new_receiver,
+                bump: new_escrow_bump.unwrap(),
+                vault_bump: new_vault_bump.unwrap(),
+                is_accepted: false, // the new receiver accepts on their own
+                deliverable_hash: [0; 32],
+                review_ends_at: None,
+                is_disputed: false,
+                releasable_at: None,
+                ..Escrow::clone(&self.escrow)
+            });
+
+            if let Some(expected) = self.escrow.client_multisig {
+                let multisig = self.multisig.as_mut().ok_or(BondrError::MultisigMismatch)?;
+                require_keys_eq!(multisig.key(), expected, BondrError::MultisigMismatch);
+                require_keys_eq!(
+                    multisig.pending_escrow,
+                    escrow_key,
+                    BondrError::MultisigPendingEscrowMismatch
+                );
+                // approvals were given for the old receiver
+                multisig.pending_escrow = new_escrow.key();
+                multisig.reset_votes();
+            }
+
+            escrow_key = new_escrow.key();
+            current_receiver = new_receiver;
+            self.escrow.close(self.sender.to_account_info())?;
+        }
+
+        emit!(EscrowAmended {
+            sender: sender_key,
+            receiver: current_receiver,
+            escrow: escrow_key,
+            amount,
+            deadline,
+        });
+
+        Ok(())
+    }
+}
@@ -35,39 +35,43 @@ impl<'info> ApproveMultisigRelease<'info> {
             crate::error::BondrError::MultisigPendingEscrowMismatch
         );
 
-        // 2. locating the member's index in the members array
-        let mut found = false;
-        let mut member_index = 0;
-        for i in 0..(multisig.member_count as usize) {
-            if multisig.members[i] == self.member.key() {
-                found = true;
-                member_index = i;
-                break;
-            }
-        }
+        // only accepted escrows can be approved for release
+        require!(
+            self.escrow.is_accepted,
+            crate::error::BondrError::EscrowNotAccepted
+        );
 
-        require!(found, crate::error::BondrError::NotMultisigMember);
+        // 2. locating the member's index in the members array
+        let member_index = multisig
+            .member_index(&self.member.key())
+            .ok_or(crate::error::BondrError::NotMultisigMember)?;
 
-        // 3. preventing double approvals
+        // 3. preventing double approvals, a rejection is final
+        require!(
+            multisig.vote(member_index) != 2,
+            crate::error::BondrError::AlreadyRejected
+        );
         require!(
-            multisig.approvals[member_index] == 0,
+            multisig.vote(member_index) == 0,
             crate::error::BondrError::AlreadyApproved
         );
 
         // 4. approving this member -> 1 for approve on that index for that member
-        multisig.approvals[member_index] = 1;
+        multisig.set_vote(member_index, 1);
 
-        // 5. If threshold is now met, mark the escrow as released.
+        // 5. If the approved weight now meets the threshold, release whatever is still locked.
         //    Transfer still only happens in claim_payment.
-        let approvals_met = multisig
-            .approvals
-            .iter()
-            .take(multisig.member_count as usize)
-            .filter(|&&a| a == 1)
-            .count() as u8;
+        if multisig.threshold_met() {
+            if !self.escrow.is_released {
+                let unreleased = self.escrow.unreleased();
+                self.escrow.release(unreleased);
+                self.escrow.multisig_released = unreleased;
+            }
 
-        if approvals_met >= multisig.threshold {
-            self.escrow.is_released = true;
+            // the cooling-off period starts once, members can veto until it ends
+            if multisig.timelock > 0 && self.escrow.releasable_at.is_none() {
+                self.escrow.releasable_at = Some(Clock::get()?.unix_timestamp + multisig.timelock);
+            }
         }
 
         // 6. Emit event
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, ClientMultisig, PayoutApproval, PayoutApproved, PayoutDestination};
+
+#[derive(Accounts)]
+#[instruction(target: Pubkey)]
+pub struct ApprovePayout<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    #[account(
+        seeds = [b"payout_destination", destination.owner.as_ref()],
+        bump = destination.bump
+    )]
+    pub destination: Account<'info, PayoutDestination>,
+
+    #[account(
+        seeds = [b"client_multisig", destination.owner.as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, ClientMultisig>,
+
+    // one per payout, the first approving member pays the rent
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + PayoutApproval::INIT_SPACE,
+        seeds = [b"payout_approval", destination.key().as_ref(), target.as_ref()],
+        bump
+    )]
+    pub approval: Account<'info, PayoutApproval>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ApprovePayout<'info> {
+    pub fn approve_payout(&mut self, target: Pubkey, bump: u8) -> Result<()> {
+        // 1. only the destination's receiver group votes
+        require!(
+            self.destination.receiver_multisig == Some(self.multisig.key()),
+            BondrError::NoReceiverMultisig
+        );
+
+        // 2. locating the member, one vote each
+        let member_index = self
+            .multisig
+            .member_index(&self.member.key())
+            .ok_or(BondrError::NotMultisigMember)?;
+        let bit = 1u32 << member_index;
+
+        // votes cast before the destination changed are void, the round starts over
+        if self.approval.destination == Pubkey::default()
+            || self.approval.treasury != self.destination.treasury
+        {
+            self.approval.set_inner(PayoutApproval {
+                destination: self.destination.key(),
+                target,
+                approved: 0,
+                bump,
+                treasury: self.destination.treasury,
+            });
+        }
+        require!(
+            self.approval.approved & bit == 0,
+            BondrError::AlreadyApproved
+        );
+
+        // 3. approving, claim_payment or set_payout_destination check the threshold
+        self.approval.approved |= bit;
+
+        emit!(PayoutApproved {
+            destination: self.destination.key(),
+            target,
+            member: self.member.key(),
+            approved_weight: self.multisig.bitmap_weight(self.approval.approved),
+        });
+
+        Ok(())
+    }
+}
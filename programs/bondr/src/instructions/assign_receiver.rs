@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
+    ClientMultisig, Escrow, ReceiverAssigned,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8, new_reference_seed: u8)]
+pub struct AssignReceiver<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    // bounty escrows are seeded with the default key until a receiver is assigned
+    #[account(
+        mut,
+        seeds = [b"escrow", sender.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", sender.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    ///CHECK: Receiver's public key - validated in instruction logic
+    pub receiver: AccountInfo<'info>,
+
+    // Required for multisig bounties, the group's pending escrow moves along
+    #[account(mut)]
+    pub multisig: Option<Box<Account<'info, ClientMultisig>>>,
+
+    // the escrow PDAs are derived from the receiver, so the funds and state move to the
+    // receiver's escrow/vault pair and the bounty escrow is closed
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", sender.key().as_ref(), receiver.key().as_ref(), &[new_reference_seed]],
+        bump,
+    )]
+    pub new_escrow: Box<Account<'info, Escrow>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", sender.key().as_ref(), receiver.key().as_ref(), &[new_reference_seed]],
+        bump,
+    )]
+    pub new_vault: SystemAccount<'info>,
+
+    // SPL move
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = token_mint,
+        associated_token::authority = new_escrow,
+    )]
+    pub new_escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AssignReceiver<'info> {
+    pub fn assign_receiver(
+        &mut self,
+        reference_seed: u8,
+        is_token_transfer: bool,
+        new_escrow_bump: u8,
+        new_vault_bump: u8,
+    ) -> Result<()> {
+        // 1. Validate the assignment
+        require_keys_eq!(
+            self.sender.key(),
+            self.escrow.sender,
+            BondrError::UnauthorizedSender
+        );
+        require!(self.escrow.is_bounty(), BondrError::NotABounty);
+        require!(!self.escrow.is_released, BondrError::AlreadyReleased);
+        self.escrow
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+        let receiver_key = self.receiver.key();
+        require!(
+            receiver_key != Pubkey::default(),
+            BondrError::InvalidReceiver
+        );
+        require!(receiver_key != self.sender.key(), BondrError::SelfTransfer);
+
+        // 2. moving the funds to the receiver's vault/escrow ATA
+        let sender_key = self.sender.key();
+        let bounty_key = Pubkey::default();
+        let vault_seeds = &[
+            b"vault",
+            sender_key.as_ref(),
+            bounty_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.vault_bump],
+        ];
+        let escrow_seeds = &[
+            b"escrow",
+            sender_key.as_ref(),
+            bounty_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.bump],
+        ];
+
+        let amount = self.escrow.amount;
+        if is_token_transfer {
+            validate_token_accounts(
+                &self.token_program,
+                &self.escrow_token_account,
+                &self.new_escrow_token_account,
+                &self.token_mint,
+            )?;
+
+            transfer_spl_tokens(
+                self.escrow_token_account.as_ref().unwrap(),
+                self.new_escrow_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.escrow.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                amount,
+                Some(&[&escrow_seeds[..]]),
+            )?;
+        } else {
+            transfer_sol(
+                &self.vault.to_account_info(),
+                &self.new_vault.to_account_info(),
+                &self.system_program,
+                amount,
+                Some(&[&vault_seeds[..]]),
+            )?;
+        }
+
+        // 3. same escrow_id, deadline and terms, the receiver still has to accept them
+        self.new_escrow.set_inner(Escrow {
+            receiver: receiver_key,
+            bump: new_escrow_bump,
+            vault_bump: new_vault_bump,
+            is_accepted: false,
+            ..Escrow::clone(&self.escrow)
+        });
+
+        // 4. the multisig follows its escrow
+        if let Some(expected) = self.escrow.client_multisig {
+            let multisig = self.multisig.as_mut().ok_or(BondrError::MultisigMismatch)?;
+            require_keys_eq!(multisig.key(), expected, BondrError::MultisigMismatch);
+            require_keys_eq!(
+                multisig.pending_escrow,
+                self.escrow.key(),
+                BondrError::MultisigPendingEscrowMismatch
+            );
+            multisig.pending_escrow = self.new_escrow.key();
+            multisig.reset_votes();
+        }
+
+        self.escrow.close(self.sender.to_account_info())?;
+
+        emit!(ReceiverAssigned {
+            sender: sender_key,
+            receiver: receiver_key,
+            bounty: self.escrow.key(),
+            escrow: self.new_escrow.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
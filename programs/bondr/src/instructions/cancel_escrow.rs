@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::BondrError,
+    utils::{
+        invoke_settlement_callback, transfer_sol, transfer_spl_tokens, validate_token_accounts,
+        vault_minimum,
+    },
+    ClientMultisig, Escrow, EscrowCancelled, SettlementOutcome, UserStats,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed:u8)]
+pub struct CancelEscrow<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    // closed in instruction logic unless released funds are still waiting to be claimed
+    #[account(
+        mut,
+        seeds = [b"escrow", client.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", client.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", client.key().as_ref()],
+        bump = client_stats.bump
+    )]
+    pub client_stats: Account<'info, UserStats>,
+
+    // Required for multisig escrows so the group can take on a new escrow
+    #[account(mut)]
+    pub multisig: Option<Account<'info, ClientMultisig>>,
+
+    // SPL refund
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub client_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelEscrow<'info> {
+    pub fn cancel_escrow(
+        &mut self,
+        reference_seed: u8,
+        is_token_transfer: bool,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // 1. Only the unreleased part of an escrow can be refunded. Once the receiver accepted,
+        // only a multisig rejection makes it refundable before the deadline
+        require_keys_eq!(
+            self.client.key(),
+            self.escrow.sender,
+            BondrError::UnauthorizedSender
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !self.escrow.is_released && !self.escrow.review_lapsed(now),
+            BondrError::AlreadyReleased
+        );
+        require!(
+            !self.escrow.is_accepted || self.escrow.is_rejected,
+            BondrError::AlreadyAccepted
+        );
+        require!(!self.escrow.awaits_review(), BondrError::WorkNotDisputed);
+        self.escrow
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+
+        // 2. the multisig linked to this escrow is freed once the escrow closes
+        if let Some(expected) = self.escrow.client_multisig {
+            let multisig = self.multisig.as_ref().ok_or(BondrError::MultisigMismatch)?;
+            require_keys_eq!(multisig.key(), expected, BondrError::MultisigMismatch);
+            require_keys_eq!(
+                multisig.pending_escrow,
+                self.escrow.key(),
+                BondrError::MultisigPendingEscrowMismatch
+            );
+        }
+
+        // 3. Signer seeds for moving the unreleased part back to the client,
+        // a streaming escrow keeps what vested so far for the freelancer
+        self.escrow
+            .release_vested(now, vault_minimum(is_token_transfer)?);
+        let amount = self.escrow.refund_unreleased();
+        let client_key = self.client.key();
+        let receiver_key = self.escrow.receiver;
+        let vault_seeds = &[
+            b"vault",
+            client_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.vault_bump],
+        ];
+        let escrow_seeds = &[
+            b"escrow",
+            client_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.bump],
+        ];
+
+        // 4. refund
+        if is_token_transfer {
+            validate_token_accounts(
+                &self.token_program,
+                &self.escrow_token_account,
+                &self.client_token_account,
+                &self.token_mint,
+            )?;
+
+            transfer_spl_tokens(
+                self.escrow_token_account.as_ref().unwrap(),
+                self.client_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.escrow.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                amount,
+                Some(&[&escrow_seeds[..]]),
+            )?;
+        } else {
+            transfer_sol(
+                &self.vault.to_account_info(),
+                &self.client.to_account_info(),
+                &self.system_program,
+                amount,
+                Some(&[&vault_seeds[..]]),
+            )?;
+        }
+
+        // 5. Record the cancellation on the client's reputation
+        self.client_stats.cancellations += 1;
+
+        // 6. close unless the freelancer still has released funds to claim, the integrator's
+        // callback hears about it when the escrow closes
+        if self.escrow.is_settled() {
+            invoke_settlement_callback(
+                &self.escrow,
+                remaining_accounts,
+                &escrow_seeds[..],
+                SettlementOutcome::Cancelled,
+                amount,
+                false,
+            )?;
+        }
+        self.close_if_settled(&vault_seeds[..])?;
+
+        emit!(EscrowCancelled {
+            sender: client_key,
+            receiver: receiver_key,
+            escrow: self.escrow.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    fn close_if_settled(&mut self, vault_seeds: &[&[u8]]) -> Result<()> {
+        if !self.escrow.is_settled() {
+            return Ok(());
+        }
+
+        // the rent reserve of a team escrow goes back with the escrow rent
+        let reserve = self.vault.lamports();
+        if reserve > 0 {
+            transfer_sol(
+                &self.vault.to_account_info(),
+                &self.client.to_account_info(),
+                &self.system_program,
+                reserve,
+                Some(&[vault_seeds]),
+            )?;
+        }
+
+        // checked against escrow.client_multisig before the refund
+        if let (Some(_), Some(multisig)) = (self.escrow.client_multisig, self.multisig.as_mut()) {
+            multisig.pending_escrow = Pubkey::default();
+            multisig.reset_votes();
+        }
+        self.escrow.close(self.client.to_account_info())
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
+    Job, JobCancelled,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct CancelJob<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    // closed in instruction logic, open bids are withdrawn by their freelancers
+    #[account(
+        mut,
+        seeds = [b"job", client.key().as_ref(), &[reference_seed]],
+        bump = job.bump
+    )]
+    pub job: Account<'info, Job>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", job.key().as_ref()],
+        bump = job.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // SPL refund
+    #[account(mut)]
+    pub job_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub client_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelJob<'info> {
+    pub fn cancel_job(&mut self, reference_seed: u8) -> Result<()> {
+        // 1. the whole budget goes back to the client
+        let refund = self.job.budget;
+        let client_key = self.client.key();
+        let job_key = self.job.key();
+        let vault_seeds = &[b"vault", job_key.as_ref(), &[self.job.vault_bump]];
+        let job_seeds = &[
+            b"job",
+            client_key.as_ref(),
+            &[reference_seed],
+            &[self.job.bump],
+        ];
+
+        if let Some(mint) = self.job.token_mint {
+            validate_token_accounts(
+                &self.token_program,
+                &self.job_token_account,
+                &self.client_token_account,
+                &self.token_mint,
+            )?;
+            require_keys_eq!(
+                self.token_mint.as_ref().unwrap().key(),
+                mint,
+                BondrError::JobMintMismatch
+            );
+
+            transfer_spl_tokens(
+                self.job_token_account.as_ref().unwrap(),
+                self.client_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.job.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                refund,
+                Some(&[&job_seeds[..]]),
+            )?;
+        } else {
+            transfer_sol(
+                &self.vault.to_account_info(),
+                &self.client.to_account_info(),
+                &self.system_program,
+                refund,
+                Some(&[&vault_seeds[..]]),
+            )?;
+        }
+
+        // 2. close the job
+        self.job.close(self.client.to_account_info())?;
+
+        emit!(JobCancelled {
+            client: client_key,
+            job: job_key,
+            refund,
+        });
+
+        Ok(())
+    }
+}
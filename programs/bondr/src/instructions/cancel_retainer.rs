@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
+    Retainer, RetainerCancelled,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct CancelRetainer<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    // closed in instruction logic unless released periods are still waiting to be claimed
+    #[account(
+        mut,
+        seeds = [b"retainer", sender.key().as_ref(), retainer.receiver.as_ref(), &[reference_seed]],
+        bump = retainer.bump
+    )]
+    pub retainer: Account<'info, Retainer>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", retainer.key().as_ref()],
+        bump = retainer.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // SPL refund, from the retainer's own token account
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = retainer,
+    )]
+    pub retainer_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub sender_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelRetainer<'info> {
+    pub fn cancel_retainer(&mut self, reference_seed: u8, is_token_transfer: bool) -> Result<()> {
+        // 1. periods already due stay with the freelancer
+        require!(!self.retainer.is_finished(), BondrError::RetainerEnded);
+        self.retainer
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+        let retainer_key = self.retainer.key();
+        self.retainer
+            .release_due(retainer_key, Clock::get()?.unix_timestamp);
+
+        // 2. the unreleased balance goes back to the sender
+        let refund = self.retainer.balance;
+        let sender_key = self.sender.key();
+        let receiver_key = self.retainer.receiver;
+        let vault_seeds = &[b"vault", retainer_key.as_ref(), &[self.retainer.vault_bump]];
+        let retainer_seeds = &[
+            b"retainer",
+            sender_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.retainer.bump],
+        ];
+
+        if refund > 0 {
+            if is_token_transfer {
+                validate_token_accounts(
+                    &self.token_program,
+                    &self.retainer_token_account,
+                    &self.sender_token_account,
+                    &self.token_mint,
+                )?;
+
+                transfer_spl_tokens(
+                    self.retainer_token_account.as_ref().unwrap(),
+                    self.sender_token_account.as_ref().unwrap(),
+                    self.token_mint.as_ref().unwrap(),
+                    &self.retainer.to_account_info(),
+                    self.token_program.as_ref().unwrap(),
+                    refund,
+                    Some(&[&retainer_seeds[..]]),
+                )?;
+            } else {
+                transfer_sol(
+                    &self.vault.to_account_info(),
+                    &self.sender.to_account_info(),
+                    &self.system_program,
+                    refund,
+                    Some(&[&vault_seeds[..]]),
+                )?;
+            }
+        }
+        self.retainer.balance = 0;
+        self.retainer.is_cancelled = true;
+
+        // 3. close unless the freelancer still has released periods to claim
+        if self.retainer.is_settled() {
+            self.retainer.close(self.sender.to_account_info())?;
+        }
+
+        emit!(RetainerCancelled {
+            sender: sender_key,
+            receiver: receiver_key,
+            retainer: retainer_key,
+            refund,
+        });
+
+        Ok(())
+    }
+}
@@ -3,8 +3,13 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::{
     error::BondrError,
-    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
-    ClientMultisig, Escrow, UserStats, MAX_MULTISIG_MEMBERS,
+    utils::{
+        invoke_settlement_callback, transfer_sol, transfer_spl_tokens, validate_token_accounts,
+        vault_minimum,
+    },
+    ClientMultisig, CounterpartyRecord, Escrow, EscrowReceipt, FreelancerBadge, PaymentReleased,
+    PayoutApproval, PayoutDestination, ReputationConfig, SettlementOutcome, UserStats,
+    USER_STATS_VERSION,
 };
 
 #[derive(Accounts)]
@@ -17,10 +22,11 @@ pub struct ClaimPayment<'info> {
     #[account(mut)]
     pub freelancer: Signer<'info>,
 
+    // closed in instruction logic once everything is claimed.
+    // Derived from escrow.receiver, team recipients claim from the lead's escrow
     #[account(
         mut,
-        close = client,
-        seeds = [b"escrow", client.key().as_ref(), freelancer.key().as_ref(), &[reference_seed]],
+        seeds = [b"escrow", client.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
         bump = escrow.bump
     )]
     pub escrow: Account<'info, Escrow>,
@@ -28,7 +34,7 @@ pub struct ClaimPayment<'info> {
     // Vault PDA that holds the actual SOL funds
     #[account(
         mut,
-        seeds = [b"vault", client.key().as_ref(), freelancer.key().as_ref(), &[reference_seed]],
+        seeds = [b"vault", client.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
         bump = escrow.vault_bump
     )]
     pub vault: SystemAccount<'info>,
@@ -42,11 +48,76 @@ pub struct ClaimPayment<'info> {
     )]
     pub receiver_stats: Account<'info, UserStats>,
 
-    // Optional client multisig. When present, constraints are enforced in instruction logic.
+    // client stats exist since initialize_escrow
+    #[account(
+        mut,
+        seeds = [b"user_stats", client.key().as_ref()],
+        bump = client_stats.bump
+    )]
+    pub client_stats: Box<Account<'info, UserStats>>,
+
+    // Optional freelancer badge. When present, the claim is recorded on it.
+    #[account(
+        mut,
+        seeds = [b"badge", freelancer.key().as_ref()],
+        bump = badge.bump
+    )]
+    pub badge: Option<Box<Account<'info, FreelancerBadge>>>,
+
+    // Required once the reputation config exists, counts distinct counterparties
+    #[account(
+        init_if_needed,
+        payer = freelancer,
+        space = 8 + CounterpartyRecord::INIT_SPACE,
+        seeds = [b"counterparty", freelancer.key().as_ref(), client.key().as_ref()],
+        bump
+    )]
+    pub counterparty: Option<Box<Account<'info, CounterpartyRecord>>>,
+
+    // Always the config PDA, so tier bookkeeping can't be left out where the admin set it up.
+    /// CHECK: read in instruction logic, claims settle without reputation until it exists
+    #[account(seeds = [b"reputation_config"], bump)]
+    pub reputation_config: UncheckedAccount<'info>,
+
+    // settlement record that survives the escrow close, reviews hang off it.
+    // Created by the first claim, partial claims add to its amount.
+    #[account(
+        init_if_needed,
+        payer = freelancer,
+        space = 8 + EscrowReceipt::INIT_SPACE,
+        seeds = [b"receipt", client.key().as_ref(), &escrow.escrow_id.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Box<Account<'info, EscrowReceipt>>,
+
+    // Required for multisig escrows, checked against escrow.client_multisig in instruction logic
     #[account(mut)]
     pub multisig: Option<Account<'info, ClientMultisig>>,
 
-    // SOL claim
+    // Always the claimer's PDA, so a registered payout destination can't be left out.
+    /// CHECK: read in instruction logic when the claimer registered one
+    #[account(
+        seeds = [b"payout_destination", freelancer.key().as_ref()],
+        bump
+    )]
+    pub payout_destination: UncheckedAccount<'info>,
+
+    // receiver group votes on this payout, needed when the destination has a receiver multisig
+    #[account(
+        mut,
+        close = freelancer,
+        seeds = [b"payout_approval", payout_destination.key().as_ref(), escrow.key().as_ref()],
+        bump = payout_approval.bump
+    )]
+    pub payout_approval: Option<Box<Account<'info, PayoutApproval>>>,
+
+    #[account(
+        seeds = [b"client_multisig", freelancer.key().as_ref()],
+        bump = payout_multisig.bump
+    )]
+    pub payout_multisig: Option<Box<Account<'info, ClientMultisig>>>,
+
+    // SOL claim, the treasury when a payout destination is registered
     #[account(mut)]
     pub receiver_sol: SystemAccount<'info>,
 
@@ -68,9 +139,47 @@ impl<'info> ClaimPayment<'info> {
         reference_seed: u8,
         is_token_transfer: bool,
         receiver_stats_bump: u8,
+        counterparty_bump: Option<u8>,
+        receipt_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
-        // 1. multisig checks
-        if let Some(multisig) = &mut self.multisig {
+        // 1. paid out in the escrow's asset, streaming escrows release what vested so far
+        self.escrow
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+        let now = Clock::get()?.unix_timestamp;
+        let is_streaming = self.escrow.vesting.is_some();
+        self.escrow
+            .release_vested(now, vault_minimum(is_token_transfer)?);
+
+        // auto-release: the client let the review window of submitted work lapse. Multisig
+        // members get their timelock on top to reject it, as with a release through approvals
+        let lapse_delay = match (self.escrow.client_multisig, &self.multisig) {
+            (Some(_), Some(multisig)) => multisig.timelock,
+            _ => 0,
+        };
+        let auto_release = self.escrow.review_lapsed(now.saturating_sub(lapse_delay));
+        if auto_release {
+            let rest = self.escrow.unreleased();
+            self.escrow.release(rest);
+
+            emit!(PaymentReleased {
+                sender: self.escrow.sender,
+                receiver: self.escrow.receiver,
+                escrow: self.escrow.key(),
+                amount: rest,
+                released_amount: self.escrow.released_amount,
+            });
+        }
+
+        // a timelocked multisig release waits out its veto window, whatever released it
+        if let Some(releasable_at) = self.escrow.releasable_at {
+            require!(now >= releasable_at, BondrError::ReleaseTimelocked);
+        }
+
+        // 2. multisig checks, a multisig escrow can't be claimed without its group
+        if let Some(expected) = self.escrow.client_multisig {
+            let multisig = self.multisig.as_ref().ok_or(BondrError::MultisigMismatch)?;
+            require_keys_eq!(multisig.key(), expected, BondrError::MultisigMismatch);
             // pending escrow match check
             require_keys_eq!(
                 multisig.pending_escrow,
@@ -78,38 +187,56 @@ impl<'info> ClaimPayment<'info> {
                 BondrError::MultisigPendingEscrowMismatch
             );
 
-            // counting approvals
-            let approvals_met = multisig
-                .approvals
-                .iter()
-                .take(multisig.member_count as usize)
-                .filter(|&&a| a == 1)
-                .count() as u8;
-
+            // the members had the review window to approve or dispute, streams need no approval
             require!(
-                approvals_met >= multisig.threshold,
+                auto_release || is_streaming || multisig.threshold_met(),
                 BondrError::MultisigThresholdNotMet
             );
-
-            // resetting pending escrow after release so multisig can be reused
-            multisig.pending_escrow = Pubkey::default();
-            multisig.approvals = [0u8; MAX_MULTISIG_MEMBERS];
         }
 
-        // 2. Input validation
-        require!(self.escrow.is_released, BondrError::NotReleased);
-        require_keys_eq!(self.freelancer.key(), self.escrow.receiver);
+        // 3. Input validation
+        require!(self.escrow.released_amount > 0, BondrError::NotReleased);
+        let member = if self.escrow.is_team() {
+            Some(
+                self.escrow
+                    .team_index(&self.freelancer.key())
+                    .ok_or(BondrError::NotEscrowParty)?,
+            )
+        } else {
+            require_keys_eq!(self.freelancer.key(), self.escrow.receiver);
+            None
+        };
 
-        // 3. using amount from escrow state
-        let amount = self.escrow.amount;
+        // 4. paying out everything released since the last claim, per recipient on team escrows
+        let released = self.escrow.released_amount;
+        let amount = match member {
+            Some(i) => self
+                .escrow
+                .team_share(i, released)
+                .saturating_sub(self.escrow.team_claimed[i]),
+            None => self.escrow.claimable(),
+        };
+        require!(amount > 0, BondrError::NothingToClaim);
+        let treasury = self.payout_treasury()?;
+
+        // reputation accounts, the config only misses on deployments that never created it
+        let config = ReputationConfig::load(&self.reputation_config)?;
+        if config.is_some() {
+            require!(
+                self.counterparty.is_some(),
+                BondrError::MissingCounterpartyRecord
+            );
+        } else {
+            require!(self.badge.is_none(), BondrError::MissingReputationConfig);
+        }
 
-        // 4. Creating signer seeds as movement from escrow -> freelancer
+        // 5. Creating signer seeds as movement from escrow -> freelancer
         let client_key = self.client.key();
-        let freelancer_key = self.freelancer.key();
+        let receiver_key = self.escrow.receiver;
         let vault_seeds = &[
             b"vault",
-            client_key.as_ref(),     // sender
-            freelancer_key.as_ref(), // receiver
+            client_key.as_ref(),   // sender
+            receiver_key.as_ref(), // receiver
             &[reference_seed],
             &[self.escrow.vault_bump],
         ];
@@ -119,13 +246,13 @@ impl<'info> ClaimPayment<'info> {
         let escrow_seeds = &[
             b"escrow",
             client_key.as_ref(),
-            freelancer_key.as_ref(),
+            receiver_key.as_ref(),
             &[reference_seed],
             &[self.escrow.bump],
         ];
         let escrow_signer_seeds = &[&escrow_seeds[..]];
 
-        // 5. transfer based on flag
+        // 6. transfer based on flag
         if is_token_transfer {
             validate_token_accounts(
                 &self.token_program,
@@ -133,6 +260,13 @@ impl<'info> ClaimPayment<'info> {
                 &self.receiver_token_account,
                 &self.token_mint,
             )?;
+            if let Some(treasury) = treasury {
+                require_keys_eq!(
+                    self.receiver_token_account.as_ref().unwrap().owner,
+                    treasury,
+                    BondrError::PayoutDestinationMismatch
+                );
+            }
 
             transfer_spl_tokens(
                 self.escrow_token_account.as_ref().unwrap(),
@@ -144,6 +278,13 @@ impl<'info> ClaimPayment<'info> {
                 Some(escrow_signer_seeds), // Escrow PDA signs for tokens
             )?;
         } else {
+            if let Some(treasury) = treasury {
+                require_keys_eq!(
+                    self.receiver_sol.key(),
+                    treasury,
+                    BondrError::PayoutDestinationMismatch
+                );
+            }
             transfer_sol(
                 &self.vault.to_account_info(),
                 &self.receiver_sol.to_account_info(),
@@ -153,17 +294,184 @@ impl<'info> ClaimPayment<'info> {
             )?;
         }
 
-        // 6. Update stats (create-then-update safe)
+        // 7. Receipt for what has been paid out so far
+        self.escrow.claimed_amount += amount;
+        if let Some(i) = member {
+            self.escrow.team_claimed[i] += amount;
+        }
+        if self.receipt.sender == Pubkey::default() {
+            self.receipt.set_inner(EscrowReceipt {
+                sender: self.client.key(),
+                receiver: receiver_key,
+                escrow_id: self.escrow.escrow_id,
+                amount,
+                settled_at: 0,
+                bump: receipt_bump,
+            });
+        } else {
+            self.receipt.amount += amount;
+        }
+
+        // 8. the recipient's whole share is paid out -> their completion
+        let total = self.escrow.amount;
+        let (share, paid) = match member {
+            Some(i) => (
+                self.escrow.team_share(i, total),
+                self.escrow.team_claimed[i],
+            ),
+            None => (total, self.escrow.claimed_amount),
+        };
+        if self.escrow.is_released && paid == share {
+            self.record_completion(
+                config.as_ref(),
+                share,
+                is_token_transfer,
+                receiver_stats_bump,
+                counterparty_bump,
+                now,
+            );
+        }
+
+        // partial claim, the rest stays locked or claimable
+        if !self.escrow.is_settled() {
+            return Ok(());
+        }
+
+        // 9. Record the whole escrow on the client's reputation, its receipt can now be reviewed
+        self.receipt.settled_at = now;
+        let normalized_value = config
+            .as_ref()
+            .and_then(|config| self.normalized_value(config, total, is_token_transfer));
+        let client_stats = &mut self.client_stats;
+        client_stats.completed_escrows += 1;
+        client_stats.escrows_paid_out += 1;
+        client_stats.total_volume += normalized_value.unwrap_or(0);
+        // the client disputed the work and paid it out anyway
+        if self.escrow.is_disputed {
+            client_stats.disputes_lost += 1;
+        }
+
+        // 10. the integrator's callback, if the sender registered one. The receiver can skip it
+        invoke_settlement_callback(
+            &self.escrow,
+            remaining_accounts,
+            &escrow_seeds[..],
+            SettlementOutcome::Paid,
+            total,
+            true,
+        )?;
+
+        // 11. resetting pending escrow so multisig can be reused, then closing the escrow.
+        // What's left in the vault is the rent reserve of a team escrow
+        if let (Some(_), Some(multisig)) = (self.escrow.client_multisig, &mut self.multisig) {
+            multisig.pending_escrow = Pubkey::default();
+            multisig.reset_votes();
+        }
+        let reserve = self.vault.lamports();
+        if reserve > 0 {
+            transfer_sol(
+                &self.vault.to_account_info(),
+                &self.client.to_account_info(),
+                &self.system_program,
+                reserve,
+                Some(vault_signer_seeds),
+            )?;
+        }
+        self.escrow.close(self.client.to_account_info())?;
+
+        Ok(())
+    }
+
+    // treasury of the claimer's payout destination, if they registered one. With a receiver
+    // group every payout needs its approval, which the claim uses up
+    fn payout_treasury(&self) -> Result<Option<Pubkey>> {
+        let info = self.payout_destination.to_account_info();
+        let Some(destination) = PayoutDestination::load(&info)? else {
+            return Ok(None);
+        };
+        destination.check_approval(
+            info.key(),
+            self.escrow.key(),
+            self.payout_approval.as_deref(),
+            self.payout_multisig.as_deref(),
+        )?;
+        Ok(Some(destination.treasury))
+    }
+
+    // freelancer side of a completion, for the claiming recipient's share
+    fn record_completion(
+        &mut self,
+        config: Option<&ReputationConfig>,
+        amount: u64,
+        is_token_transfer: bool,
+        receiver_stats_bump: u8,
+        counterparty_bump: Option<u8>,
+        now: i64,
+    ) {
+        // Update stats (create-then-update safe)
         if self.receiver_stats.user == Pubkey::default() {
             self.receiver_stats.set_inner(UserStats {
+                version: USER_STATS_VERSION,
                 user: self.freelancer.key(),
                 completed_escrows: 1, // This is the first completion
                 bump: receiver_stats_bump,
+                ..Default::default()
             });
         } else {
             self.receiver_stats.completed_escrows += 1;
         }
 
-        Ok(())
+        // first completed escrow between this pair -> new distinct counterparty for both
+        let first_completion = match (self.counterparty.as_mut(), counterparty_bump) {
+            (Some(counterparty), Some(bump)) => {
+                let first = counterparty.freelancer == Pubkey::default();
+                if first {
+                    counterparty.set_inner(CounterpartyRecord {
+                        freelancer: self.freelancer.key(),
+                        client: self.client.key(),
+                        completed_escrows: 0,
+                        bump,
+                    });
+                }
+                counterparty.completed_escrows += 1;
+                first
+            }
+            _ => false,
+        };
+        if first_completion {
+            self.client_stats.distinct_freelancers += 1;
+        }
+
+        // the badge is only passed alongside the config, checked in claim_payment
+        let Some(config) = config else {
+            return;
+        };
+        let normalized_value = self.normalized_value(config, amount, is_token_transfer);
+
+        if let Some(badge) = self.badge.as_mut() {
+            badge.record_completion(
+                amount,
+                normalized_value,
+                config.min_escrow_value,
+                first_completion,
+                now,
+            );
+            config.refresh_badge(badge, now);
+        }
+    }
+
+    // SPL claims are priced through the config price table
+    fn normalized_value(
+        &self,
+        config: &ReputationConfig,
+        amount: u64,
+        is_token_transfer: bool,
+    ) -> Option<u64> {
+        let mint = if is_token_transfer {
+            self.token_mint.as_ref().map(|m| (m.key(), m.decimals))
+        } else {
+            None
+        };
+        config.normalized_value(mint, amount)
     }
 }
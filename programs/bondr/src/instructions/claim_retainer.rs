@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
+    ClientMultisig, PayoutApproval, PayoutDestination, Retainer, RetainerClaimed,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct ClaimRetainer<'info> {
+    // receives the rent-refund once the retainer is settled
+    #[account(mut)]
+    pub sender: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+
+    // closed in instruction logic once every period is paid out
+    #[account(
+        mut,
+        seeds = [b"retainer", sender.key().as_ref(), receiver.key().as_ref(), &[reference_seed]],
+        bump = retainer.bump
+    )]
+    pub retainer: Account<'info, Retainer>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", retainer.key().as_ref()],
+        bump = retainer.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // Always the receiver's PDA, so a registered payout destination can't be left out.
+    /// CHECK: read in instruction logic when the receiver registered one
+    #[account(
+        seeds = [b"payout_destination", receiver.key().as_ref()],
+        bump
+    )]
+    pub payout_destination: UncheckedAccount<'info>,
+
+    // receiver group votes on this claim, needed when the destination has a receiver multisig
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [b"payout_approval", payout_destination.key().as_ref(), retainer.key().as_ref()],
+        bump = payout_approval.bump
+    )]
+    pub payout_approval: Option<Box<Account<'info, PayoutApproval>>>,
+
+    #[account(
+        seeds = [b"client_multisig", receiver.key().as_ref()],
+        bump = payout_multisig.bump
+    )]
+    pub payout_multisig: Option<Box<Account<'info, ClientMultisig>>>,
+
+    // SOL claim, the treasury when a payout destination is registered
+    #[account(mut)]
+    pub receiver_sol: SystemAccount<'info>,
+
+    // SPL claim, from the retainer's own token account
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = retainer,
+    )]
+    pub retainer_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub receiver_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimRetainer<'info> {
+    pub fn claim_retainer(&mut self, reference_seed: u8, is_token_transfer: bool) -> Result<()> {
+        // 1. the claim releases due periods itself, no crank needed
+        self.retainer
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+        let retainer_key = self.retainer.key();
+        self.retainer
+            .release_due(retainer_key, Clock::get()?.unix_timestamp);
+
+        let amount = self.retainer.claimable();
+        require!(amount > 0, BondrError::NothingToClaim);
+        let treasury = self.payout_treasury()?;
+
+        // 2. paying out everything released since the last claim
+        let sender_key = self.sender.key();
+        let receiver_key = self.receiver.key();
+        let vault_seeds = &[b"vault", retainer_key.as_ref(), &[self.retainer.vault_bump]];
+        let retainer_seeds = &[
+            b"retainer",
+            sender_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.retainer.bump],
+        ];
+
+        if is_token_transfer {
+            validate_token_accounts(
+                &self.token_program,
+                &self.retainer_token_account,
+                &self.receiver_token_account,
+                &self.token_mint,
+            )?;
+            if let Some(treasury) = treasury {
+                require_keys_eq!(
+                    self.receiver_token_account.as_ref().unwrap().owner,
+                    treasury,
+                    BondrError::PayoutDestinationMismatch
+                );
+            }
+
+            transfer_spl_tokens(
+                self.retainer_token_account.as_ref().unwrap(),
+                self.receiver_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.retainer.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                amount,
+                Some(&[&retainer_seeds[..]]),
+            )?;
+        } else {
+            if let Some(treasury) = treasury {
+                require_keys_eq!(
+                    self.receiver_sol.key(),
+                    treasury,
+                    BondrError::PayoutDestinationMismatch
+                );
+            }
+            transfer_sol(
+                &self.vault.to_account_info(),
+                &self.receiver_sol.to_account_info(),
+                &self.system_program,
+                amount,
+                Some(&[&vault_seeds[..]]),
+            )?;
+        }
+        self.retainer.claimed_amount += amount;
+
+        // 3. the last payout closes the retainer
+        if self.retainer.is_settled() {
+            self.retainer.close(self.sender.to_account_info())?;
+        }
+
+        emit!(RetainerClaimed {
+            sender: sender_key,
+            receiver: receiver_key,
+            retainer: retainer_key,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    // treasury of the receiver's payout destination, as in claim_payment. Every claim of a
+    // retainer guarded by a receiver group needs its own approval
+    fn payout_treasury(&self) -> Result<Option<Pubkey>> {
+        let info = self.payout_destination.to_account_info();
+        let Some(destination) = PayoutDestination::load(&info)? else {
+            return Ok(None);
+        };
+        destination.check_approval(
+            info.key(),
+            self.retainer.key(),
+            self.payout_approval.as_deref(),
+            self.payout_multisig.as_deref(),
+        )?;
+        Ok(Some(destination.treasury))
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, Retainer};
+
+// permissionless crank, anyone can pay to release the due periods of a retainer
+#[derive(Accounts)]
+pub struct CrankRetainer<'info> {
+    #[account(mut)]
+    pub retainer: Account<'info, Retainer>,
+}
+
+impl<'info> CrankRetainer<'info> {
+    pub fn crank_retainer(&mut self) -> Result<()> {
+        // paused, cancelled, unfunded or not due yet all release nothing
+        let key = self.retainer.key();
+        let released = self.retainer.release_due(key, Clock::get()?.unix_timestamp);
+        require!(released > 0, BondrError::NoPeriodDue);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::BondrError,
+    utils::{
+        invoke_settlement_callback, transfer_sol, transfer_spl_tokens, validate_token_accounts,
+    },
+    ClientMultisig, Escrow, EscrowDeclined, SettlementOutcome,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed:u8)]
+pub struct DeclineEscrow<'info> {
+    // sender who funded the escrow – gets the refund and the rent back
+    #[account(mut)]
+    pub sender: SystemAccount<'info>,
+
+    pub receiver: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        seeds = [b"escrow", sender.key().as_ref(), receiver.key().as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", sender.key().as_ref(), receiver.key().as_ref(), &[reference_seed]],
+        bump = escrow.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // Required for multisig escrows so the group can take on a new escrow
+    #[account(mut)]
+    pub multisig: Option<Account<'info, ClientMultisig>>,
+
+    // SPL refund
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    // the refund can't be redirected by the receiver
+    #[account(mut, token::authority = sender)]
+    pub sender_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DeclineEscrow<'info> {
+    pub fn decline_escrow(
+        &mut self,
+        reference_seed: u8,
+        is_token_transfer: bool,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // 1. Only an escrow the receiver never agreed to can be declined
+        require!(!self.escrow.is_accepted, BondrError::AlreadyAccepted);
+        self.escrow
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+
+        // 2. free the multisig linked to this escrow
+        if let Some(expected) = self.escrow.client_multisig {
+            let multisig = self.multisig.as_mut().ok_or(BondrError::MultisigMismatch)?;
+            require_keys_eq!(multisig.key(), expected, BondrError::MultisigMismatch);
+            require_keys_eq!(
+                multisig.pending_escrow,
+                self.escrow.key(),
+                BondrError::MultisigPendingEscrowMismatch
+            );
+
+            multisig.pending_escrow = Pubkey::default();
+            multisig.reset_votes();
+        }
+
+        // 3. Signer seeds for moving funds back to the sender
+        let amount = self.escrow.amount;
+        let sender_key = self.sender.key();
+        let receiver_key = self.receiver.key();
+        let vault_seeds = &[
+            b"vault",
+            sender_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.vault_bump],
+        ];
+        let escrow_seeds = &[
+            b"escrow",
+            sender_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.bump],
+        ];
+
+        // 4. refund, nothing can be released before acceptance
+        if is_token_transfer {
+            validate_token_accounts(
+                &self.token_program,
+                &self.escrow_token_account,
+                &self.sender_token_account,
+                &self.token_mint,
+            )?;
+
+            transfer_spl_tokens(
+                self.escrow_token_account.as_ref().unwrap(),
+                self.sender_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.escrow.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                amount,
+                Some(&[&escrow_seeds[..]]),
+            )?;
+        } else {
+            // the whole vault, including the rent reserve of a team escrow
+            transfer_sol(
+                &self.vault.to_account_info(),
+                &self.sender.to_account_info(),
+                &self.system_program,
+                self.vault.lamports(),
+                Some(&[&vault_seeds[..]]),
+            )?;
+        }
+
+        // 5. the integrator's callback, the escrow is closed on exit
+        invoke_settlement_callback(
+            &self.escrow,
+            remaining_accounts,
+            &escrow_seeds[..],
+            SettlementOutcome::Declined,
+            amount,
+            false,
+        )?;
+
+        emit!(EscrowDeclined {
+            sender: sender_key,
+            receiver: receiver_key,
+            escrow: self.escrow.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
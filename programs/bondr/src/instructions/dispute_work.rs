@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, Escrow, WorkDisputed};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct DisputeWork<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", sender.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> DisputeWork<'info> {
+    pub fn dispute_work(&mut self) -> Result<()> {
+        // 1. only a pending submission, inside its review window
+        require!(!self.escrow.is_released, BondrError::AlreadyReleased);
+        let review_ends_at = self
+            .escrow
+            .review_ends_at
+            .ok_or(BondrError::NoWorkSubmitted)?;
+        require!(
+            Clock::get()?.unix_timestamp < review_ends_at,
+            BondrError::ReviewWindowClosed
+        );
+        require!(!self.escrow.is_disputed, BondrError::AlreadyDisputed);
+
+        // 2. the funds stay locked until the client releases or the freelancer resubmits
+        self.escrow.is_disputed = true;
+
+        emit!(WorkDisputed {
+            sender: self.sender.key(),
+            receiver: self.escrow.receiver,
+            escrow: self.escrow.key(),
+            deliverable_hash: self.escrow.deliverable_hash,
+        });
+
+        Ok(())
+    }
+}
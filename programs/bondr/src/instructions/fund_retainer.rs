@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
+    Retainer, RetainerFunded,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct FundRetainer<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"retainer", sender.key().as_ref(), retainer.receiver.as_ref(), &[reference_seed]],
+        bump = retainer.bump
+    )]
+    pub retainer: Account<'info, Retainer>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", retainer.key().as_ref()],
+        bump = retainer.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // SPL funding, has to land in the retainer's own token account
+    #[account(mut)]
+    pub sender_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = retainer,
+    )]
+    pub retainer_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundRetainer<'info> {
+    pub fn fund_retainer(&mut self, periods: u32, is_token_transfer: bool) -> Result<()> {
+        // 1. whole periods only, and never more than the retainer can still pay out
+        require!(!self.retainer.is_finished(), BondrError::RetainerEnded);
+        require!(periods > 0, BondrError::InvalidAmountZero);
+        self.retainer
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let key = self.retainer.key();
+        self.retainer.release_due(key, now);
+        require!(
+            periods as u64 <= self.retainer.unfunded_periods(),
+            BondrError::RetainerOverfunded
+        );
+
+        // 2. funding the vault/retainer ATA
+        let amount = periods as u64 * self.retainer.period_amount;
+        if is_token_transfer {
+            validate_token_accounts(
+                &self.token_program,
+                &self.sender_token_account,
+                &self.retainer_token_account,
+                &self.token_mint,
+            )?;
+
+            transfer_spl_tokens(
+                self.sender_token_account.as_ref().unwrap(),
+                self.retainer_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.sender.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                amount,
+                None, // No signer seeds needed for sender
+            )?;
+        } else {
+            transfer_sol(
+                &self.sender.to_account_info(),
+                &self.vault.to_account_info(),
+                &self.system_program,
+                amount,
+                None, // No signer seeds needed for sender
+            )?;
+        }
+
+        // 3. periods that were waiting on funds are released right away
+        self.retainer.balance += amount;
+        self.retainer.release_due(key, now);
+
+        emit!(RetainerFunded {
+            retainer: key,
+            amount,
+            balance: self.retainer.balance,
+        });
+
+        Ok(())
+    }
+}
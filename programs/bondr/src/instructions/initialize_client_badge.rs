@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{ClientBadge, ReputationTier};
+
+#[derive(Accounts)]
+pub struct InitializeClientBadge<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    #[account(
+        init,
+        payer = client,
+        space = 8 + ClientBadge::INIT_SPACE,
+        seeds = [b"client_badge", client.key().as_ref()],
+        bump
+    )]
+    pub badge: Account<'info, ClientBadge>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeClientBadge<'info> {
+    pub fn initialize_client_badge(&mut self, bump: u8) -> Result<()> {
+        self.badge.set_inner(ClientBadge {
+            tier: ReputationTier::Unranked,
+            client: self.client.key(),
+            bump,
+        });
+
+        Ok(())
+    }
+}
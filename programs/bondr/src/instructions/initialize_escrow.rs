@@ -1,7 +1,9 @@
 use crate::{
     error::BondrError,
-    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
-    ClientMultisig, Escrow, EscrowCreateEvent, UserStats,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts, vault_minimum},
+    ClientMultisig, Escrow, EscrowAsset, EscrowCallback, EscrowCallbackSet, EscrowCreateEvent,
+    TeamSplit, UserStats, VestingSchedule, ESCROW_VERSION, MAX_CALLBACK_ACCOUNTS,
+    MAX_TEAM_RECIPIENTS, TOTAL_BPS, USER_STATS_VERSION,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::{
@@ -12,7 +14,7 @@ use anchor_spl::{
 #[derive(Accounts)]
 #[instruction(amount:u64, reference_seed: u8)]
 pub struct InitializeEscrow<'info> {
-    // Sender (client) initiating the escrow
+    // Sender (client) initiating the escrow, a wallet or a PDA signing through CPI (e.g. a Squads vault)
     #[account(mut)]
     pub sender: Signer<'info>,
 
@@ -80,10 +82,15 @@ impl<'info> InitializeEscrow<'info> {
         reference_seed: u8,
         is_token_transfer: bool,
         is_multisig: bool,
+        deadline: Option<i64>,
+        terms_hash: Option<[u8; 32]>,
+        vesting: Option<VestingSchedule>,
+        team: Option<Vec<TeamSplit>>,
+        callback: Option<EscrowCallback>,
         bump: u8,
         vault_bump: u8,
         stats_bump: u8,
-    ) -> Result<()> {
+    ) -> Result<u64> {
         // 1. Validate inputs
         require!(amount > 0, BondrError::InvalidAmountZero);
         //max amount check(1000 SOL)
@@ -93,6 +100,22 @@ impl<'info> InitializeEscrow<'info> {
             self.sender.key() != self.receiver.key(),
             BondrError::SelfTransfer
         );
+        if let Some(deadline) = deadline {
+            require!(
+                deadline > Clock::get()?.unix_timestamp,
+                BondrError::InvalidDeadline
+            );
+        }
+        if let Some(schedule) = vesting {
+            require!(schedule.is_valid(), BondrError::InvalidVestingSchedule);
+        }
+        let team = team.unwrap_or_default();
+        if !team.is_empty() {
+            self.validate_team(&team)?;
+        }
+        if let Some(callback) = &callback {
+            require!(callback.is_valid(), BondrError::InvalidCallback);
+        }
 
         // 2. if is_multisig is true, we check here that it should be present
         if is_multisig {
@@ -114,7 +137,14 @@ impl<'info> InitializeEscrow<'info> {
             client_multisig_pubkey = Some(self.client_multisig.as_ref().unwrap().key());
         }
 
+        let escrow_id = self.sender_stats.escrows_funded as u64; // zero on a freshly created stats account
+        let token_mint = if is_token_transfer {
+            self.token_mint.as_ref().map(|mint| mint.key())
+        } else {
+            None
+        };
         self.escrow.set_inner(Escrow {
+            version: ESCROW_VERSION,
             sender: self.sender.key(),
             receiver: self.receiver.key(),
             amount,
@@ -122,7 +152,40 @@ impl<'info> InitializeEscrow<'info> {
             bump,
             vault_bump,
             client_multisig: client_multisig_pubkey,
+            escrow_id,
+            deadline,
+            released_amount: 0,
+            claimed_amount: 0,
+            terms_hash: terms_hash.unwrap_or_default(),
+            is_accepted: false, // see accept_escrow
+            deliverable_hash: [0; 32],
+            review_ends_at: None,
+            is_disputed: false,
+            vesting,
+            team_size: team.len() as u8,
+            team_recipients: [Pubkey::default(); MAX_TEAM_RECIPIENTS],
+            team_bps: [0; MAX_TEAM_RECIPIENTS],
+            team_claimed: [0; MAX_TEAM_RECIPIENTS],
+            is_rejected: false,
+            releasable_at: None,
+            callback_program: None, // see set_callback below
+            callback_account_count: 0,
+            callback_accounts: [Pubkey::default(); MAX_CALLBACK_ACCOUNTS],
+            asset: EscrowAsset::new(token_mint),
+            multisig_released: 0,
         });
+        for (i, split) in team.iter().enumerate() {
+            self.escrow.team_recipients[i] = split.recipient;
+            self.escrow.team_bps[i] = split.bps;
+        }
+        if let Some(callback) = callback {
+            self.escrow.set_callback(&callback);
+            emit!(EscrowCallbackSet {
+                escrow: self.escrow.key(),
+                program: callback.program,
+                accounts: callback.accounts,
+            });
+        }
 
         // 4. Transfer based on is_token_transfer flag
         if is_token_transfer {
@@ -143,11 +206,18 @@ impl<'info> InitializeEscrow<'info> {
                 None, // No signer seeds needed for sender
             )?;
         } else {
+            // team recipients claim separately, a rent-exempt reserve keeps the vault alive in
+            // between. It is swept back to the sender when the escrow closes
+            let reserve = if team.is_empty() {
+                0
+            } else {
+                vault_minimum(false)?
+            };
             transfer_sol(
                 &self.sender.to_account_info(),
                 &self.vault.to_account_info(),
                 &self.system_program,
-                amount,
+                amount + reserve,
                 None, // No signer seeds needed for sender
             )?;
         }
@@ -163,15 +233,10 @@ impl<'info> InitializeEscrow<'info> {
             );
 
             // Ensure sender is part of multisig members (safety)
-            let mut found = false;
-            let active = multisig.member_count as usize;
-            for i in 0..active {
-                if multisig.members[i] == self.sender.key() {
-                    found = true;
-                    break;
-                }
-            }
-            require!(found, BondrError::InvalidMultisigConfig);
+            require!(
+                multisig.member_index(&self.sender.key()).is_some(),
+                BondrError::InvalidMultisigConfig
+            );
 
             // Linking multisig -> pending escrow
             multisig.pending_escrow = self.escrow.key();
@@ -180,11 +245,14 @@ impl<'info> InitializeEscrow<'info> {
         // 6. init user stats
         if self.sender_stats.user == Pubkey::default() {
             self.sender_stats.set_inner(UserStats {
+                version: USER_STATS_VERSION,
                 user: self.sender.key(),
                 completed_escrows: 0,
                 bump: stats_bump,
+                ..Default::default()
             });
         }
+        self.sender_stats.escrows_funded += 1;
 
         // 7. Emit events
         emit!(EscrowCreateEvent {
@@ -195,6 +263,32 @@ impl<'info> InitializeEscrow<'info> {
             is_token_transfer
         });
 
+        // returned to CPI callers, they need it for the receipt PDA
+        Ok(escrow_id)
+    }
+
+    fn validate_team(&self, team: &[TeamSplit]) -> Result<()> {
+        // the receiver leads the team: it accepts the escrow and gets the rounding leftovers
+        // bounties get their receiver later, teams can't be split before that
+        require!(
+            (2..=MAX_TEAM_RECIPIENTS).contains(&team.len())
+                && team[0].recipient == self.receiver.key()
+                && self.receiver.key() != Pubkey::default(),
+            BondrError::InvalidTeamSplits
+        );
+
+        let mut total: u32 = 0;
+        for (i, split) in team.iter().enumerate() {
+            require!(
+                split.bps > 0
+                    && split.recipient != self.sender.key()
+                    && !team[..i].iter().any(|s| s.recipient == split.recipient),
+                BondrError::InvalidTeamSplits
+            );
+            total += split.bps as u32;
+        }
+        require!(total == TOTAL_BPS as u32, BondrError::InvalidTeamSplits);
+
         Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{FreelancerBadge, ReputationTier};
+use crate::{FreelancerBadge, RatingSummary, ReputationTier, FREELANCER_BADGE_VERSION};
 
 #[derive(Accounts)]
 pub struct InitializeFreelancerBadge<'info> {
@@ -22,11 +22,22 @@ pub struct InitializeFreelancerBadge<'info> {
 impl<'info> InitializeFreelancerBadge<'info> {
     pub fn initialize_freelancer_badge(&mut self, bump: u8) -> Result<()> {
         self.badge.set_inner(FreelancerBadge {
+            version: FREELANCER_BADGE_VERSION,
             tier: ReputationTier::Unranked,
             completed_escrows: 0,
             total_value_completed: 0,
             freelancer: self.freelancer.key(),
             bump,
+            qualified_escrows: 0,
+            total_value_normalized: 0,
+            distinct_clients: 0,
+            ratings: RatingSummary::default(),
+            last_completed_at: 0,
+            effective_tier: ReputationTier::Unranked,
+            is_stale: false,
+            failed_escrows: 0,
+            failed_value: 0,
+            asset: Pubkey::default(),
         });
 
         Ok(())
@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
 
-use crate::{ClientMultisig, ClientMultisigCreated, MAX_MULTISIG_MEMBERS};
+use crate::{
+    ClientMultisig, ClientMultisigCreated, CLIENT_MULTISIG_VERSION, INLINE_MULTISIG_MEMBERS,
+    MAX_MULTISIG_MEMBERS, MAX_MULTISIG_TIMELOCK,
+};
 
 #[derive(Accounts)]
+#[instruction(members: Vec<Pubkey>, member_count: u8)]
 pub struct InitializeMultisigClient<'info> {
     #[account(mut)]
     pub client: Signer<'info>,
@@ -10,7 +14,7 @@ pub struct InitializeMultisigClient<'info> {
     #[account(
         init,
         payer = client,
-        space = 8 + ClientMultisig::INIT_SPACE,
+        space = ClientMultisig::space(member_count as usize),
         seeds = [b"client_multisig", client.key().as_ref()],
         bump
     )]
@@ -20,37 +24,31 @@ pub struct InitializeMultisigClient<'info> {
 }
 
 impl<'info> InitializeMultisigClient<'info> {
-    fn validate_unique_members(
-        &self,
-        members: &[Pubkey; MAX_MULTISIG_MEMBERS],
-        member_count: u8,
-    ) -> Result<()> {
-        let active_count = member_count as usize;
-
-        for i in 0..active_count {
-            for j in (i + 1)..active_count {
-                if members[i] == members[j] {
-                    return Err(crate::error::BondrError::DuplicateMember.into());
-                }
-            }
-        }
-        Ok(())
-    }
-
     pub fn init_multisig_client(
         &mut self,
-        members: [Pubkey; MAX_MULTISIG_MEMBERS],
+        members: Vec<Pubkey>,
         member_count: u8,
         threshold: u8,
+        timelock: Option<i64>,
+        weights: Option<Vec<u8>>,
         bump: u8,
     ) -> Result<()> {
-        // 1. basic bounds checks
+        let active_count = member_count as usize;
+
+        // 1. basic bounds checks, entries past member_count are ignored
         require!(
-            member_count > 0 && (member_count as usize) <= MAX_MULTISIG_MEMBERS,
+            active_count > 0
+                && active_count <= MAX_MULTISIG_MEMBERS
+                && members.len() >= active_count,
             crate::error::BondrError::InvalidMultisigConfig
         );
         require!(
-            threshold > 0 && threshold <= member_count,
+            threshold > 0,
+            crate::error::BondrError::InvalidMultisigConfig
+        );
+        let timelock = timelock.unwrap_or(0);
+        require!(
+            (0..=MAX_MULTISIG_TIMELOCK).contains(&timelock),
             crate::error::BondrError::InvalidMultisigConfig
         );
 
@@ -58,33 +56,55 @@ impl<'info> InitializeMultisigClient<'info> {
         require!(
             members
                 .iter()
-                .take(member_count as usize)
+                .take(active_count)
                 .any(|&member| member == self.client.key()),
             crate::error::BondrError::InvalidMultisigConfig
         );
 
-        // 3. duplicate check among active members
-        self.validate_unique_members(&members, member_count)?;
-
-        // 4. preparing approvals array (all zero)
-        let approvals: [u8; MAX_MULTISIG_MEMBERS] = [0u8; MAX_MULTISIG_MEMBERS];
+        // 3. weights default to one vote per member, every active member needs a weight
+        let weights = weights.unwrap_or_else(|| vec![1u8; active_count]);
+        require!(
+            weights.len() >= active_count && weights.iter().take(active_count).all(|&w| w > 0),
+            crate::error::BondrError::InvalidMultisigConfig
+        );
 
-        // 5. setting initial value for multisig account
+        // 4. setting initial value for multisig account, members are added one by one
         self.multisig.set_inner(crate::ClientMultisig {
-            members,
-            member_count,
+            version: CLIENT_MULTISIG_VERSION,
+            members: [Pubkey::default(); INLINE_MULTISIG_MEMBERS],
+            member_count: 0,
             threshold,
-            approvals,
+            approvals: [0u8; INLINE_MULTISIG_MEMBERS],
             pending_escrow: Pubkey::default(),
             bump,
+            timelock,
+            weights: [0u8; INLINE_MULTISIG_MEMBERS],
+            extra_members: Vec::with_capacity(active_count.saturating_sub(INLINE_MULTISIG_MEMBERS)),
         });
 
+        for (&member, &weight) in members.iter().zip(weights.iter()).take(active_count) {
+            // duplicate check among active members
+            require!(
+                self.multisig.member_index(&member).is_none(),
+                crate::error::BondrError::DuplicateMember
+            );
+            self.multisig.push_member(member, weight);
+        }
+
+        // 5. together the members must be able to reach the threshold
+        require!(
+            threshold as u16 <= self.multisig.total_weight(),
+            crate::error::BondrError::MultisigThresholdUnachievable
+        );
+
         // 6. emit events
         emit!(ClientMultisigCreated {
             client: self.client.key(),
             multisig: self.multisig.key(),
             member_count,
             threshold,
+            timelock,
+            weights: weights[..active_count].to_vec(),
         });
 
         Ok(())
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*, error::BondrError, program::Bondr, ReputationConfig, ReputationConfigUpdated,
+    TierThresholds,
+};
+
+#[derive(Accounts)]
+pub struct InitializeReputationConfig<'info> {
+    // must be the program upgrade authority, becomes the config admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ReputationConfig::INIT_SPACE,
+        seeds = [b"reputation_config"],
+        bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Bondr>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ BondrError::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeReputationConfig<'info> {
+    pub fn init_reputation_config(&mut self, bump: u8) -> Result<()> {
+        // 1. seeding tiers with the escrow counts that used to be hard-coded, and value
+        // floors so dust escrows can't farm a tier out of the box
+        let tier = |min_completed_escrows: u32, min_total_value: u64, name: &str, uri: &str| {
+            TierThresholds {
+                min_completed_escrows,
+                min_total_value,
+                min_distinct_counterparties: 0,
+                name: name.to_string(),
+                uri: uri.to_string(),
+            }
+        };
+
+        self.config.set_inner(ReputationConfig {
+            admin: self.admin.key(),
+            tiers: [
+                tier(
+                    VERIFIED_MIN_ESCROWS,
+                    VERIFIED_MIN_VALUE,
+                    VERIFIED_BADGE_NAME,
+                    VERIFIED_METADATA_URI,
+                ),
+                tier(
+                    PROFESSIONAL_MIN_ESCROWS,
+                    PROFESSIONAL_MIN_VALUE,
+                    PROFESSIONAL_BADGE_NAME,
+                    PROFESSIONAL_METADATA_URI,
+                ),
+                tier(
+                    ELITE_MIN_ESCROWS,
+                    ELITE_MIN_VALUE,
+                    ELITE_BADGE_NAME,
+                    ELITE_METADATA_URI,
+                ),
+            ],
+            bump,
+            min_escrow_value: DEFAULT_MIN_ESCROW_VALUE,
+            mint_prices: Vec::new(),
+            client_tiers: [
+                tier(
+                    VERIFIED_MIN_ESCROWS,
+                    VERIFIED_MIN_VALUE,
+                    VERIFIED_CLIENT_BADGE_NAME,
+                    VERIFIED_CLIENT_METADATA_URI,
+                ),
+                tier(
+                    PROFESSIONAL_MIN_ESCROWS,
+                    PROFESSIONAL_MIN_VALUE,
+                    PROFESSIONAL_CLIENT_BADGE_NAME,
+                    PROFESSIONAL_CLIENT_METADATA_URI,
+                ),
+                tier(
+                    ELITE_MIN_ESCROWS,
+                    ELITE_MIN_VALUE,
+                    ELITE_CLIENT_BADGE_NAME,
+                    ELITE_CLIENT_METADATA_URI,
+                ),
+            ],
+            decay_period: DEFAULT_DECAY_PERIOD,
+            failure_penalty: DEFAULT_FAILURE_PENALTY,
+        });
+
+        // 2. emit event
+        emit!(ReputationConfigUpdated {
+            admin: self.admin.key(),
+            config: self.config.key(),
+        });
+
+        Ok(())
+    }
+}
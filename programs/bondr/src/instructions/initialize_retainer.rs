@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts, vault_minimum},
+    EscrowAsset, Retainer, RetainerCreated, RetainerTerms, MAX_RETAINER_PERIOD_LENGTH,
+    RETAINER_VERSION,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct InitializeRetainer<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    ///CHECK: Receiver's public key - validated in instruction logic
+    pub receiver: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + Retainer::INIT_SPACE,
+        seeds = [b"retainer", sender.key().as_ref(), receiver.key().as_ref(), &[reference_seed]],
+        bump,
+    )]
+    pub retainer: Account<'info, Retainer>,
+
+    // same vault PDA scheme as escrows, keyed by the retainer so the two never share a vault
+    #[account(
+        mut,
+        seeds = [b"vault", retainer.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // For SPL token retainers (optional)
+    pub token_program: Option<Program<'info, Token>>,
+
+    #[account(mut)]
+    pub sender_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = token_mint,
+        associated_token::authority = retainer,
+    )]
+    pub retainer_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+}
+
+impl<'info> InitializeRetainer<'info> {
+    pub fn initialize_retainer(
+        &mut self,
+        terms: RetainerTerms,
+        funded_periods: u32,
+        is_token_transfer: bool,
+        bump: u8,
+        vault_bump: u8,
+    ) -> Result<()> {
+        // 1. Validate terms
+        require!(
+            terms.period_amount > 0
+                && (1..=MAX_RETAINER_PERIOD_LENGTH).contains(&terms.period_length)
+                && terms.max_periods > 0,
+            BondrError::InvalidRetainerTerms
+        );
+        //max amount check(1000 SOL) per period
+        require!(
+            terms.period_amount <= 1_000_000_000_000,
+            BondrError::AmountTooLarge
+        );
+        // whole periods keep the vault at zero or above the rent minimum
+        require!(
+            terms.period_amount >= vault_minimum(is_token_transfer)?,
+            BondrError::PeriodBelowRentMinimum
+        );
+        require!(
+            self.sender.key() != self.receiver.key(),
+            BondrError::SelfTransfer
+        );
+        require!(
+            funded_periods <= terms.max_periods,
+            BondrError::RetainerOverfunded
+        );
+        // the mint is recorded even without prefunding, later funding has to match it
+        let token_mint = if is_token_transfer {
+            validate_token_accounts(
+                &self.token_program,
+                &self.sender_token_account,
+                &self.retainer_token_account,
+                &self.token_mint,
+            )?;
+            self.token_mint.as_ref().map(|mint| mint.key())
+        } else {
+            None
+        };
+
+        let now = Clock::get()?.unix_timestamp;
+        let next_period_at = match terms.start_at {
+            Some(start_at) => {
+                require!(start_at > now, BondrError::InvalidDeadline);
+                start_at
+            }
+            None => now
+                .checked_add(terms.period_length)
+                .ok_or(BondrError::TimestampOverflow)?,
+        };
+
+        // 2. prefunding whole periods
+        let balance = funded_periods as u64 * terms.period_amount;
+        self.retainer.set_inner(Retainer {
+            version: RETAINER_VERSION,
+            sender: self.sender.key(),
+            receiver: self.receiver.key(),
+            asset: EscrowAsset::new(token_mint),
+            period_amount: terms.period_amount,
+            period_length: terms.period_length,
+            max_periods: terms.max_periods,
+            periods_released: 0,
+            next_period_at,
+            balance,
+            released_amount: 0,
+            claimed_amount: 0,
+            is_paused: false,
+            is_cancelled: false,
+            bump,
+            vault_bump,
+        });
+
+        if balance > 0 {
+            if is_token_transfer {
+                transfer_spl_tokens(
+                    self.sender_token_account.as_ref().unwrap(),
+                    self.retainer_token_account.as_ref().unwrap(),
+                    self.token_mint.as_ref().unwrap(),
+                    &self.sender.to_account_info(),
+                    self.token_program.as_ref().unwrap(),
+                    balance,
+                    None, // No signer seeds needed for sender
+                )?;
+            } else {
+                transfer_sol(
+                    &self.sender.to_account_info(),
+                    &self.vault.to_account_info(),
+                    &self.system_program,
+                    balance,
+                    None, // No signer seeds needed for sender
+                )?;
+            }
+        }
+
+        emit!(RetainerCreated {
+            sender: self.sender.key(),
+            receiver: self.receiver.key(),
+            retainer: self.retainer.key(),
+            period_amount: terms.period_amount,
+            period_length: terms.period_length,
+            max_periods: terms.max_periods,
+            next_period_at,
+            balance,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{utils::migrate_account, AccountMigrated, Versioned};
+
+// shared by the migrate_* instructions, permissionless since the upgrade is deterministic
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner and discriminator are checked in migrate_account
+    #[account(mut)]
+    pub account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateAccount<'info> {
+    pub fn migrate<T: Versioned>(&mut self) -> Result<()> {
+        let from_version = migrate_account::<T>(
+            &self.account.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.system_program,
+        )?;
+
+        emit!(AccountMigrated {
+            account: self.account.key(),
+            from_version,
+            to_version: T::VERSION,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as MPL_CORE_ID;
+
+use crate::{
+    error::BondrError, utils::create_badge_asset, ClientBadge, ReputationConfig, ReputationTier,
+    UserStats,
+};
+
+#[derive(Accounts)]
+pub struct MintClientReputationNFT<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"client_badge", client.key().as_ref()],
+        bump = badge.bump,
+        constraint = badge.client == client.key()
+    )]
+    pub badge: Account<'info, ClientBadge>,
+
+    #[account(
+        seeds = [b"user_stats", client.key().as_ref()],
+        bump = client_stats.bump
+    )]
+    pub client_stats: Account<'info, UserStats>,
+
+    #[account(
+        seeds = [b"reputation_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    /// CHECK: This will be the new Asset account created by MPL Core
+    #[account(mut)]
+    pub asset: Signer<'info>,
+
+    /// CHECK: MPL Core Program, checked by address constraint
+    #[account(address = MPL_CORE_ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MintClientReputationNFT<'info> {
+    pub fn mint_client_nft(&mut self) -> Result<()> {
+        // 1. determining tier from the client's paid out escrows
+        let tier = self.config.eligible_client_tier(&self.client_stats);
+        if tier == ReputationTier::Unranked {
+            return Err(BondrError::InsufficientEscrows.into());
+        }
+
+        // 2. preventing duplicate minting of the same or a lower tier
+        if self.badge.tier >= tier {
+            return Err(BondrError::NFTAlreadyMinted.into());
+        }
+
+        // 3. metadata based on tier
+        let thresholds = self
+            .config
+            .client_thresholds(&tier)
+            .ok_or(BondrError::InsufficientEscrows)?;
+
+        // 4. creating the asset using client as authority
+        create_badge_asset(
+            &self.mpl_core_program.to_account_info(),
+            &self.asset.to_account_info(),
+            &self.client.to_account_info(),
+            &self.system_program.to_account_info(),
+            thresholds.name.clone(),
+            thresholds.uri.clone(),
+            Vec::new(),
+        )?;
+
+        // 5. updating badge tier
+        self.badge.tier = tier;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
-use mpl_core::{instructions::CreateV2CpiBuilder, types::DataState, ID as MPL_CORE_ID};
+use mpl_core::ID as MPL_CORE_ID;
 
-use crate::{constants::*, error::BondrError, FreelancerBadge, ReputationTier};
+use crate::{
+    error::BondrError,
+    utils::{badge_attributes_plugin, create_badge_asset},
+    FreelancerBadge, ReputationConfig, ReputationTier,
+};
 
 #[derive(Accounts)]
 pub struct MintReputationNFT<'info> {
@@ -16,6 +20,12 @@ pub struct MintReputationNFT<'info> {
     )]
     pub badge: Account<'info, FreelancerBadge>,
 
+    #[account(
+        seeds = [b"reputation_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
     /// CHECK: This will be the new Asset account created by MPL Core
     #[account(mut)]
     pub asset: Signer<'info>,
@@ -39,15 +49,11 @@ impl<'info> MintReputationNFT<'info> {
             BondrError::InvalidMplKey
         );
 
-        // 1. determining tier based on completed escrows
-        let tier = match self.badge.completed_escrows {
-            0..=2 => {
-                return Err(BondrError::InsufficientEscrows.into());
-            }
-            3..=9 => ReputationTier::Verified,
-            10..=24 => ReputationTier::Professional,
-            25.. => ReputationTier::Elite,
-        };
+        // 1. determining tier based on the configured thresholds
+        let tier = self.config.eligible_tier(&self.badge);
+        if tier == ReputationTier::Unranked {
+            return Err(BondrError::InsufficientEscrows.into());
+        }
 
         // 2. verifying if freelancer already has this tier or higher to prevent duplicate minting
         if self.badge.tier >= tier {
@@ -55,41 +61,28 @@ impl<'info> MintReputationNFT<'info> {
         }
 
         // 3. metadata based on tier
-        let (name, uri) = match tier {
-            ReputationTier::Unranked => {
-                return Err(BondrError::InsufficientEscrows.into());
-            }
-            ReputationTier::Verified => ("Bondr Verified Badge", VERIFIED_METADATA_URI),
-            ReputationTier::Professional => ("Bondr Professional Badge", PROFESSIONAL_METADATA_URI),
-            ReputationTier::Elite => ("Bondr Elite Badge", ELITE_METADATA_URI),
-        };
-
-        // 4. create account info references
-        let mpl_core_program = self.mpl_core_program.to_account_info();
-        let asset = self.asset.to_account_info();
-        let freelancer = self.freelancer.to_account_info();
-        let system_program = self.system_program.to_account_info();
-
-        // 5. handle collection if provided
-        // let collection_info = self.collection.as_ref().map(|c| c.to_account_info());
-        // let collection_info = self.collection.to_account_info();
-
-        // 6. creating the asset using freelancer as authority - NO CPI SIGNING NEEDED
-        CreateV2CpiBuilder::new(&mpl_core_program)
-            .asset(&asset)
-            // .collection(Some(&collection_info)) // commented for now
-            .collection(None)
-            .authority(Some(&freelancer)) // Freelancer as authority
-            .payer(&freelancer) // Freelancer pays
-            .owner(Some(&freelancer)) // Freelancer owns
-            .system_program(&system_program)
-            .data_state(DataState::AccountState)
-            .name(name.to_string())
-            .uri(uri.to_string())
-            .invoke()?;
+        let thresholds = self
+            .config
+            .thresholds(&tier)
+            .ok_or(BondrError::InsufficientEscrows)?;
+        let (name, uri) = (thresholds.name.clone(), thresholds.uri.clone());
 
-        // 7. updating badge tier
+        // 4. updating badge tier, the asset is created with the refreshed state
         self.badge.tier = tier.clone();
+        self.badge.asset = self.asset.key();
+        self.config
+            .refresh_badge(&mut self.badge, Clock::get()?.unix_timestamp);
+
+        // 5. creating the asset using freelancer as authority
+        create_badge_asset(
+            &self.mpl_core_program.to_account_info(),
+            &self.asset.to_account_info(),
+            &self.freelancer.to_account_info(),
+            &self.system_program.to_account_info(),
+            name,
+            uri,
+            vec![badge_attributes_plugin(&self.badge)],
+        )?;
 
         Ok(())
     }
@@ -1,17 +1,91 @@
+pub mod accept_bid;
+pub mod accept_escrow;
+pub mod add_multisig_members;
+pub mod amend_escrow;
 pub mod approve_multisig_release;
+pub mod approve_payout;
+pub mod assign_receiver;
+pub mod cancel_escrow;
+pub mod cancel_job;
+pub mod cancel_retainer;
 pub mod claim_payment;
+pub mod claim_retainer;
+pub mod crank_retainer;
+pub mod decline_escrow;
+pub mod dispute_work;
+pub mod fund_retainer;
+pub mod initialize_client_badge;
 pub mod initialize_escrow;
 pub mod initialize_freelancer_badge;
 pub mod initialize_multisig_client;
+pub mod initialize_reputation_config;
+pub mod initialize_retainer;
+pub mod migrate_account;
+pub mod mint_client_reputation_nft;
 pub mod mint_reputation_nft;
+pub mod post_job;
+pub mod reclaim_expired_escrow;
+pub mod refresh_reputation;
+pub mod reject_multisig_release;
 pub mod release_payment;
+pub mod revoke_multisig_approval;
+pub mod set_decay_period;
+pub mod set_failure_penalty;
+pub mod set_min_escrow_value;
+pub mod set_mint_price;
+pub mod set_payout_destination;
+pub mod set_retainer_paused;
+pub mod submit_bid;
+pub mod submit_review;
+pub mod submit_work;
+pub mod top_up_escrow;
 pub mod update_freelancer_badge;
+pub mod update_reputation_tier;
+pub mod veto_multisig_release;
+pub mod withdraw_bid;
 
+pub use accept_bid::*;
+pub use accept_escrow::*;
+pub use add_multisig_members::*;
+pub use amend_escrow::*;
 pub use approve_multisig_release::*;
+pub use approve_payout::*;
+pub use assign_receiver::*;
+pub use cancel_escrow::*;
+pub use cancel_job::*;
+pub use cancel_retainer::*;
 pub use claim_payment::*;
+pub use claim_retainer::*;
+pub use crank_retainer::*;
+pub use decline_escrow::*;
+pub use dispute_work::*;
+pub use fund_retainer::*;
+pub use initialize_client_badge::*;
 pub use initialize_escrow::*;
 pub use initialize_freelancer_badge::*;
 pub use initialize_multisig_client::*;
+pub use initialize_reputation_config::*;
+pub use initialize_retainer::*;
+pub use migrate_account::*;
+pub use mint_client_reputation_nft::*;
 pub use mint_reputation_nft::*;
+pub use post_job::*;
+pub use reclaim_expired_escrow::*;
+pub use refresh_reputation::*;
+pub use reject_multisig_release::*;
 pub use release_payment::*;
-pub use update_freelancer_badge::*;
\ No newline at end of file
+pub use revoke_multisig_approval::*;
+pub use set_decay_period::*;
+pub use set_failure_penalty::*;
+pub use set_min_escrow_value::*;
+pub use set_mint_price::*;
+pub use set_payout_destination::*;
+pub use set_retainer_paused::*;
+pub use submit_bid::*;
+pub use submit_review::*;
+pub use submit_work::*;
+pub use top_up_escrow::*;
+pub use update_freelancer_badge::*;
+pub use update_reputation_tier::*;
+pub use veto_multisig_release::*;
+pub use withdraw_bid::*;
\ No newline at end of file
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts, vault_minimum},
+    Job, JobPosted, JOB_VERSION,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct PostJob<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    #[account(
+        init,
+        payer = client,
+        space = 8 + Job::INIT_SPACE,
+        seeds = [b"job", client.key().as_ref(), &[reference_seed]],
+        bump,
+    )]
+    pub job: Account<'info, Job>,
+
+    // keyed by the job like retainer vaults
+    #[account(
+        mut,
+        seeds = [b"vault", job.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // For SPL token jobs (optional)
+    pub token_program: Option<Program<'info, Token>>,
+
+    #[account(mut)]
+    pub client_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = client,
+        associated_token::mint = token_mint,
+        associated_token::authority = job,
+    )]
+    pub job_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+}
+
+impl<'info> PostJob<'info> {
+    pub fn post_job(
+        &mut self,
+        budget: u64,
+        description_hash: [u8; 32],
+        is_token_transfer: bool,
+        bump: u8,
+        vault_bump: u8,
+    ) -> Result<()> {
+        // 1. Validate the budget, it is moved as a whole so the vault is never left rent-paying
+        require!(
+            budget > 0
+                && budget >= vault_minimum(is_token_transfer)?
+                //max amount check(1000 SOL)
+                && budget <= 1_000_000_000_000,
+            BondrError::InvalidJobBudget
+        );
+
+        // 2. funding the budget up front
+        let token_mint = if is_token_transfer {
+            validate_token_accounts(
+                &self.token_program,
+                &self.client_token_account,
+                &self.job_token_account,
+                &self.token_mint,
+            )?;
+
+            transfer_spl_tokens(
+                self.client_token_account.as_ref().unwrap(),
+                self.job_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.client.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                budget,
+                None, // No signer seeds needed for client
+            )?;
+            Some(self.token_mint.as_ref().unwrap().key())
+        } else {
+            transfer_sol(
+                &self.client.to_account_info(),
+                &self.vault.to_account_info(),
+                &self.system_program,
+                budget,
+                None, // No signer seeds needed for client
+            )?;
+            None
+        };
+
+        self.job.set_inner(Job {
+            version: JOB_VERSION,
+            client: self.client.key(),
+            budget,
+            description_hash,
+            token_mint,
+            bid_count: 0,
+            bump,
+            vault_bump,
+        });
+
+        emit!(JobPosted {
+            client: self.client.key(),
+            job: self.job.key(),
+            budget,
+            token_mint,
+            description_hash,
+        });
+
+        Ok(())
+    }
+}
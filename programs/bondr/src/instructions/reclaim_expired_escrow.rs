@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use mpl_core::ID as MPL_CORE_ID;
+
+use crate::{
+    error::BondrError,
+    utils::{
+        invoke_settlement_callback, transfer_sol, transfer_spl_tokens, update_badge_attributes,
+        validate_token_accounts, vault_minimum,
+    },
+    ClientMultisig, Escrow, EscrowReclaimed, FreelancerBadge, ReputationConfig, SettlementOutcome,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed:u8)]
+pub struct ReclaimExpiredEscrow<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    // closed in instruction logic unless released funds are still waiting to be claimed
+    #[account(
+        mut,
+        seeds = [b"escrow", client.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", client.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // Optional freelancer badge. When present, the failure is recorded on it.
+    #[account(
+        mut,
+        seeds = [b"badge", escrow.receiver.as_ref()],
+        bump = badge.bump
+    )]
+    pub badge: Option<Box<Account<'info, FreelancerBadge>>>,
+
+    // Always the config PDA, a failure can only be recorded once the admin created it.
+    /// CHECK: read in instruction logic when a badge is passed
+    #[account(seeds = [b"reputation_config"], bump)]
+    pub reputation_config: UncheckedAccount<'info>,
+
+    // Required for multisig escrows so the group can take on a new escrow
+    #[account(mut)]
+    pub multisig: Option<Box<Account<'info, ClientMultisig>>>,
+
+    // Optional badge asset. When present, its attributes are rewritten.
+    /// CHECK: checked against badge.asset in update_badge_attributes
+    #[account(mut)]
+    pub asset: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA holding the attributes plugin authority on badge assets
+    #[account(seeds = [b"badge_authority"], bump)]
+    pub badge_authority: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: MPL Core Program, checked by address constraint
+    #[account(address = MPL_CORE_ID)]
+    pub mpl_core_program: Option<UncheckedAccount<'info>>,
+
+    // SPL refund
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub client_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReclaimExpiredEscrow<'info> {
+    pub fn reclaim_expired_escrow(
+        &mut self,
+        reference_seed: u8,
+        is_token_transfer: bool,
+        badge_authority_bump: Option<u8>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // 1. Only the unreleased part of an escrow past its deadline can be reclaimed,
+        // submitted work only while the client disputes it
+        require_keys_eq!(
+            self.client.key(),
+            self.escrow.sender,
+            BondrError::UnauthorizedSender
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !self.escrow.is_released && !self.escrow.review_lapsed(now),
+            BondrError::AlreadyReleased
+        );
+        require!(!self.escrow.awaits_review(), BondrError::WorkNotDisputed);
+        self.escrow
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+
+        require!(
+            self.escrow.deadline.is_some_and(|deadline| now > deadline),
+            BondrError::EscrowNotExpired
+        );
+
+        // 2. the multisig linked to this escrow is freed once the escrow closes
+        if let Some(expected) = self.escrow.client_multisig {
+            let multisig = self.multisig.as_ref().ok_or(BondrError::MultisigMismatch)?;
+            require_keys_eq!(multisig.key(), expected, BondrError::MultisigMismatch);
+            require_keys_eq!(
+                multisig.pending_escrow,
+                self.escrow.key(),
+                BondrError::MultisigPendingEscrowMismatch
+            );
+        }
+
+        // 3. Signer seeds for moving the unreleased part back to the client,
+        // a streaming escrow keeps what vested so far for the freelancer
+        let escrow_amount = self.escrow.amount;
+        self.escrow
+            .release_vested(now, vault_minimum(is_token_transfer)?);
+        let amount = self.escrow.refund_unreleased();
+        let client_key = self.client.key();
+        let receiver_key = self.escrow.receiver;
+        let vault_seeds = &[
+            b"vault",
+            client_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.vault_bump],
+        ];
+        let escrow_seeds = &[
+            b"escrow",
+            client_key.as_ref(),
+            receiver_key.as_ref(),
+            &[reference_seed],
+            &[self.escrow.bump],
+        ];
+
+        // 4. refund
+        if is_token_transfer {
+            validate_token_accounts(
+                &self.token_program,
+                &self.escrow_token_account,
+                &self.client_token_account,
+                &self.token_mint,
+            )?;
+
+            transfer_spl_tokens(
+                self.escrow_token_account.as_ref().unwrap(),
+                self.client_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.escrow.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                amount,
+                Some(&[&escrow_seeds[..]]),
+            )?;
+        } else {
+            transfer_sol(
+                &self.vault.to_account_info(),
+                &self.client.to_account_info(),
+                &self.system_program,
+                amount,
+                Some(&[&vault_seeds[..]]),
+            )?;
+        }
+
+        // 5. Record the failure on the freelancer badge. Only an accepted escrow worth as much as a
+        // qualifying completion counts, so a client can't slash a badge with unaccepted or dust escrows
+        if let Some(badge) = self.badge.as_mut() {
+            let config = ReputationConfig::load(&self.reputation_config)?
+                .ok_or(BondrError::MissingReputationConfig)?;
+            let mint = if is_token_transfer {
+                self.token_mint.as_ref().map(|m| (m.key(), m.decimals))
+            } else {
+                None
+            };
+            let qualifies = config
+                .normalized_value(mint, escrow_amount)
+                .is_some_and(|value| value >= config.min_escrow_value);
+
+            if self.escrow.is_accepted && qualifies {
+                badge.record_failure(config.normalized_value(mint, amount));
+                config.refresh_badge(badge, now);
+
+                update_badge_attributes(
+                    badge,
+                    &self.asset,
+                    &self.badge_authority,
+                    &self.mpl_core_program,
+                    &self.client.to_account_info(),
+                    &self.system_program.to_account_info(),
+                    badge_authority_bump,
+                )?;
+            }
+        }
+
+        // 6. close unless the freelancer still has released funds to claim, the integrator's
+        // callback hears about it when the escrow closes
+        if self.escrow.is_settled() {
+            invoke_settlement_callback(
+                &self.escrow,
+                remaining_accounts,
+                &escrow_seeds[..],
+                SettlementOutcome::Expired,
+                amount,
+                false,
+            )?;
+        }
+        self.close_if_settled(&vault_seeds[..])?;
+
+        emit!(EscrowReclaimed {
+            sender: client_key,
+            receiver: receiver_key,
+            escrow: self.escrow.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    fn close_if_settled(&mut self, vault_seeds: &[&[u8]]) -> Result<()> {
+        if !self.escrow.is_settled() {
+            return Ok(());
+        }
+
+        // the rent reserve of a team escrow goes back with the escrow rent
+        let reserve = self.vault.lamports();
+        if reserve > 0 {
+            transfer_sol(
+                &self.vault.to_account_info(),
+                &self.client.to_account_info(),
+                &self.system_program,
+                reserve,
+                Some(&[vault_seeds]),
+            )?;
+        }
+
+        // checked against escrow.client_multisig before the refund
+        if let (Some(_), Some(multisig)) = (self.escrow.client_multisig, self.multisig.as_mut()) {
+            multisig.pending_escrow = Pubkey::default();
+            multisig.reset_votes();
+        }
+        self.escrow.close(self.client.to_account_info())
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as MPL_CORE_ID;
+
+use crate::{
+    utils::update_badge_attributes, FreelancerBadge, ReputationConfig, ReputationRefreshed,
+};
+
+// permissionless crank, anyone can pay to bring a badge's effective tier up to date
+#[derive(Accounts)]
+pub struct RefreshReputation<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"badge", badge.freelancer.as_ref()],
+        bump = badge.bump
+    )]
+    pub badge: Account<'info, FreelancerBadge>,
+
+    #[account(
+        seeds = [b"reputation_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    // Optional badge asset. When present, its attributes are rewritten.
+    /// CHECK: checked against badge.asset in update_badge_attributes
+    #[account(mut)]
+    pub asset: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA holding the attributes plugin authority on badge assets
+    #[account(seeds = [b"badge_authority"], bump)]
+    pub badge_authority: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: MPL Core Program, checked by address constraint
+    #[account(address = MPL_CORE_ID)]
+    pub mpl_core_program: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefreshReputation<'info> {
+    pub fn refresh_reputation(&mut self, badge_authority_bump: Option<u8>) -> Result<()> {
+        // 1. recomputing the effective tier from the last completion and failures
+        self.config
+            .refresh_badge(&mut self.badge, Clock::get()?.unix_timestamp);
+
+        // 2. making it visible on the asset
+        update_badge_attributes(
+            &self.badge,
+            &self.asset,
+            &self.badge_authority,
+            &self.mpl_core_program,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            badge_authority_bump,
+        )?;
+
+        // 3. indexers re-rank on this event
+        emit!(ReputationRefreshed {
+            freelancer: self.badge.freelancer,
+            tier: self.badge.tier.clone(),
+            effective_tier: self.badge.effective_tier.clone(),
+            is_stale: self.badge.is_stale,
+        });
+
+        Ok(())
+    }
+}
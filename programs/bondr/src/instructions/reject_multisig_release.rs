@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, ClientMultisig, Escrow, MultisigRejectionAdded};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct RejectMultisigRelease<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"client_multisig", escrow.sender.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, ClientMultisig>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.sender.key().as_ref(), escrow.receiver.key().as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> RejectMultisigRelease<'info> {
+    pub fn reject_multisig(&mut self) -> Result<()> {
+        let multisig = &mut self.multisig;
+
+        // 1. checking that pending escrow must match this one
+        require_keys_eq!(
+            multisig.pending_escrow,
+            self.escrow.key(),
+            BondrError::MultisigPendingEscrowMismatch
+        );
+
+        // 2. one vote per member, an approval has to be revoked first
+        let member_index = multisig
+            .member_index(&self.member.key())
+            .ok_or(BondrError::NotMultisigMember)?;
+        require!(
+            multisig.vote(member_index) != 2,
+            BondrError::AlreadyRejected
+        );
+        require!(
+            multisig.vote(member_index) == 0,
+            BondrError::AlreadyApproved
+        );
+        multisig.set_vote(member_index, 2);
+
+        // 3. the threshold can't be reached anymore -> the escrow can only be refunded and the
+        //    multisig is free for a new escrow. cancel_escrow/reclaim_expired_escrow refund it
+        let rejected = multisig.threshold_unreachable();
+        if rejected {
+            self.escrow.unrelease();
+            self.escrow.is_rejected = true;
+            self.escrow.releasable_at = None;
+            self.escrow.client_multisig = None;
+            multisig.pending_escrow = Pubkey::default();
+            multisig.reset_votes();
+        }
+
+        emit!(MultisigRejectionAdded {
+            multisig: multisig.key(),
+            member: self.member.key(),
+            escrow: self.escrow.key(),
+            rejected,
+        });
+
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::{error::BondrError, Escrow};
+use crate::{error::BondrError, Escrow, PaymentReleased};
 
 #[derive(Accounts)]
 #[instruction(reference_seed:u8)]
 pub struct ReleasePayment<'info> {
+    // the escrow sender, may be a PDA signing through CPI (e.g. a Squads vault)
     #[account(mut)]
     pub client: Signer<'info>,
 
@@ -14,21 +15,59 @@ pub struct ReleasePayment<'info> {
         bump = escrow.bump,
     )]
     pub escrow: Account<'info, Escrow>,
+
+    // only read, SOL escrows must keep the vault rent-exempt between partial claims
+    #[account(
+        seeds = [b"vault", escrow.sender.as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
 }
 
 impl<'info> ReleasePayment<'info> {
-    pub fn release_payment(&mut self, _reference_seed: u8) -> Result<()> {
+    // `amount` = None releases everything still locked
+    pub fn release_payment(&mut self, _reference_seed: u8, amount: Option<u64>) -> Result<()> {
         // 1. validation
         require_keys_eq!(
             self.client.key(),
             self.escrow.sender,
             BondrError::UnauthorizedSender
         );
-        // 2. checking if already released
+        // 2. checking the receiver accepted and it's not already released
+        require!(self.escrow.is_accepted, BondrError::EscrowNotAccepted);
         require!(!self.escrow.is_released, BondrError::AlreadyReleased);
+        require!(!self.escrow.is_rejected, BondrError::EscrowRejected);
+        // a multisig escrow is released by its members' approvals, never by one of them alone
+        require!(
+            self.escrow.client_multisig.is_none(),
+            BondrError::MultisigReleaseRequired
+        );
+
+        let unreleased = self.escrow.unreleased();
+        let amount = amount.unwrap_or(unreleased);
+        require!(amount > 0, BondrError::InvalidAmountZero);
+        require!(amount <= unreleased, BondrError::ReleaseExceedsEscrow);
+
+        // 3. a SOL vault can't be drained below the rent-exempt minimum without being emptied,
+        //    so every claimable chunk and the locked remainder have to cover it on their own
+        if amount < unreleased && self.vault.lamports() > 0 {
+            let rent_minimum = Rent::get()?.minimum_balance(0);
+            require!(
+                amount >= rent_minimum && unreleased - amount >= rent_minimum,
+                BondrError::ReleaseBelowRentMinimum
+            );
+        }
+
+        // 4. updating state
+        self.escrow.release(amount);
 
-        // 3. updating state
-        self.escrow.is_released = true;
+        emit!(PaymentReleased {
+            sender: self.escrow.sender,
+            receiver: self.escrow.receiver,
+            escrow: self.escrow.key(),
+            amount,
+            released_amount: self.escrow.released_amount,
+        });
 
         Ok(())
     }
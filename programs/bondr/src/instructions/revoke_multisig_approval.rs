@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, ClientMultisig, Escrow, MultisigApprovalRevoked};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct RevokeMultisigApproval<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"client_multisig", escrow.sender.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, ClientMultisig>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.sender.key().as_ref(), escrow.receiver.key().as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> RevokeMultisigApproval<'info> {
+    pub fn revoke_multisig_approval(&mut self) -> Result<()> {
+        let multisig = &mut self.multisig;
+
+        // 1. checking that pending escrow must match this one
+        require_keys_eq!(
+            multisig.pending_escrow,
+            self.escrow.key(),
+            BondrError::MultisigPendingEscrowMismatch
+        );
+
+        // 2. only a member's own approval can be revoked
+        let member_index = multisig
+            .member_index(&self.member.key())
+            .ok_or(BondrError::NotMultisigMember)?;
+        require!(multisig.vote(member_index) == 1, BondrError::NotApproved);
+        multisig.set_vote(member_index, 0);
+
+        // 3. below the threshold again -> take back the release, unless the freelancer
+        //    already claimed from it. A timelock restarts with the next threshold
+        let below_threshold = !multisig.threshold_met();
+        let unreleased = below_threshold && self.escrow.unrelease();
+        if below_threshold {
+            self.escrow.releasable_at = None;
+        }
+
+        emit!(MultisigApprovalRevoked {
+            multisig: multisig.key(),
+            member: self.member.key(),
+            escrow: self.escrow.key(),
+            unreleased,
+        });
+
+        Ok(())
+    }
+}
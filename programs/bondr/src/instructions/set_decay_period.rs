@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, ReputationConfig, ReputationConfigUpdated};
+
+#[derive(Accounts)]
+pub struct SetDecayPeriod<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation_config"],
+        bump = config.bump,
+        has_one = admin @ BondrError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, ReputationConfig>,
+}
+
+impl<'info> SetDecayPeriod<'info> {
+    pub fn set_decay_period(&mut self, decay_period: i64) -> Result<()> {
+        require!(decay_period >= 0, BondrError::InvalidDecayPeriod);
+
+        // badges pick the new period up on their next refresh
+        self.config.decay_period = decay_period;
+
+        emit!(ReputationConfigUpdated {
+            admin: self.admin.key(),
+            config: self.config.key(),
+        });
+
+        Ok(())
+    }
+}
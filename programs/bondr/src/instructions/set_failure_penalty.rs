@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, ReputationConfig, ReputationConfigUpdated};
+
+#[derive(Accounts)]
+pub struct SetFailurePenalty<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation_config"],
+        bump = config.bump,
+        has_one = admin @ BondrError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, ReputationConfig>,
+}
+
+impl<'info> SetFailurePenalty<'info> {
+    pub fn set_failure_penalty(&mut self, failure_penalty: u32) -> Result<()> {
+        // 0 keeps failures visible on the badge without affecting tiers
+        self.config.failure_penalty = failure_penalty;
+
+        emit!(ReputationConfigUpdated {
+            admin: self.admin.key(),
+            config: self.config.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, ReputationConfig, ReputationConfigUpdated};
+
+#[derive(Accounts)]
+pub struct SetMinEscrowValue<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation_config"],
+        bump = config.bump,
+        has_one = admin @ BondrError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, ReputationConfig>,
+}
+
+impl<'info> SetMinEscrowValue<'info> {
+    pub fn set_min_escrow_value(&mut self, min_escrow_value: u64) -> Result<()> {
+        // only applies to claims recorded from now on, existing badge counters are kept
+        self.config.min_escrow_value = min_escrow_value;
+
+        emit!(ReputationConfigUpdated {
+            admin: self.admin.key(),
+            config: self.config.key(),
+        });
+
+        Ok(())
+    }
+}
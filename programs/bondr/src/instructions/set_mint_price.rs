@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::BondrError, MintPrice, ReputationConfig, ReputationConfigUpdated, MAX_PRICED_MINTS,
+};
+
+#[derive(Accounts)]
+pub struct SetMintPrice<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation_config"],
+        bump = config.bump,
+        has_one = admin @ BondrError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, ReputationConfig>,
+}
+
+impl<'info> SetMintPrice<'info> {
+    pub fn set_mint_price(&mut self, mint: Pubkey, price: u64) -> Result<()> {
+        let prices = &mut self.config.mint_prices;
+        let existing = prices.iter().position(|p| p.mint == mint);
+
+        // 1. price 0 removes the mint from the table
+        match (existing, price) {
+            (Some(i), 0) => {
+                prices.swap_remove(i);
+            }
+            (Some(i), _) => prices[i].price = price,
+            (None, 0) => return Err(BondrError::InvalidAmountZero.into()),
+            (None, _) => {
+                require!(prices.len() < MAX_PRICED_MINTS, BondrError::PriceTableFull);
+                prices.push(MintPrice { mint, price });
+            }
+        }
+
+        // 2. emit event
+        emit!(ReputationConfigUpdated {
+            admin: self.admin.key(),
+            config: self.config.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::BondrError, ClientMultisig, PayoutApproval, PayoutDestination, PayoutDestinationSet,
+    PAYOUT_DESTINATION_VERSION,
+};
+
+#[derive(Accounts)]
+pub struct SetPayoutDestination<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PayoutDestination::INIT_SPACE,
+        seeds = [b"payout_destination", owner.key().as_ref()],
+        bump
+    )]
+    pub destination: Account<'info, PayoutDestination>,
+
+    // the owner's multisig, to attach it as receiver group or to show it approved the change
+    #[account(
+        seeds = [b"client_multisig", owner.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Option<Account<'info, ClientMultisig>>,
+
+    // the receiver group's votes on the change, used up by it. Its destination and target are
+    // checked in instruction logic
+    #[account(mut, close = owner)]
+    pub approval: Option<Account<'info, PayoutApproval>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetPayoutDestination<'info> {
+    pub fn set_payout_destination(
+        &mut self,
+        treasury: Pubkey,
+        with_multisig: bool,
+        bump: u8,
+    ) -> Result<()> {
+        // 1. Input validation
+        require!(
+            treasury != Pubkey::default() && treasury != self.destination.key(),
+            BondrError::InvalidPayoutDestination
+        );
+
+        // 2. a destination guarded by a receiver group only changes with the group's approval,
+        //    otherwise a single key could redirect the group's payouts. The group approves the
+        //    new treasury it keeps guarding, or the destination itself to give it up
+        let target = if with_multisig {
+            treasury
+        } else {
+            self.destination.key()
+        };
+        self.destination.check_approval(
+            self.destination.key(),
+            target,
+            self.approval.as_ref(),
+            self.multisig.as_ref(),
+        )?;
+
+        // 3. attaching the owner's multisig as receiver group
+        let receiver_multisig = if with_multisig {
+            let multisig = self
+                .multisig
+                .as_ref()
+                .ok_or(BondrError::InvalidPayoutDestination)?;
+            Some(multisig.key())
+        } else {
+            None
+        };
+
+        self.destination.set_inner(PayoutDestination {
+            version: PAYOUT_DESTINATION_VERSION,
+            owner: self.owner.key(),
+            treasury,
+            receiver_multisig,
+            bump,
+        });
+
+        emit!(PayoutDestinationSet {
+            owner: self.owner.key(),
+            destination: self.destination.key(),
+            treasury,
+            receiver_multisig,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, Retainer, RetainerPauseChanged};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct SetRetainerPaused<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"retainer", sender.key().as_ref(), retainer.receiver.as_ref(), &[reference_seed]],
+        bump = retainer.bump
+    )]
+    pub retainer: Account<'info, Retainer>,
+}
+
+impl<'info> SetRetainerPaused<'info> {
+    pub fn set_retainer_paused(&mut self, is_paused: bool) -> Result<()> {
+        require!(!self.retainer.is_finished(), BondrError::RetainerEnded);
+        require!(
+            self.retainer.is_paused != is_paused,
+            BondrError::RetainerPauseUnchanged
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        if is_paused {
+            // 1. periods that were already due stay earned
+            let key = self.retainer.key();
+            self.retainer.release_due(key, now);
+        } else {
+            // 2. the paused periods are skipped, the schedule picks up from the next one
+            self.retainer.skip_missed_periods(now);
+        }
+        self.retainer.is_paused = is_paused;
+
+        emit!(RetainerPauseChanged {
+            retainer: self.retainer.key(),
+            is_paused,
+            next_period_at: self.retainer.next_period_at,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, utils::vault_minimum, Bid, BidSubmitted, Job, MAX_BID_TIMELINE};
+
+#[derive(Accounts)]
+pub struct SubmitBid<'info> {
+    #[account(mut)]
+    pub freelancer: Signer<'info>,
+
+    // any open job, it is closed once a bid is accepted or the client cancels it
+    #[account(mut)]
+    pub job: Account<'info, Job>,
+
+    #[account(
+        init,
+        payer = freelancer,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", job.key().as_ref(), freelancer.key().as_ref()],
+        bump,
+    )]
+    pub bid: Account<'info, Bid>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitBid<'info> {
+    pub fn submit_bid(
+        &mut self,
+        price: u64,
+        timeline: i64,
+        proposal_hash: [u8; 32],
+        bump: u8,
+    ) -> Result<()> {
+        // 1. Validate the bid, the accepted price funds an escrow vault of its own
+        require!(
+            self.freelancer.key() != self.job.client,
+            BondrError::SelfTransfer
+        );
+        require!(
+            price > 0
+                && price <= self.job.budget
+                && price >= vault_minimum(self.job.token_mint.is_some())?
+                && (1..=MAX_BID_TIMELINE).contains(&timeline),
+            BondrError::InvalidBid
+        );
+
+        // 2. Record the bid
+        self.bid.set_inner(Bid {
+            job: self.job.key(),
+            freelancer: self.freelancer.key(),
+            price,
+            timeline,
+            proposal_hash,
+            created_at: Clock::get()?.unix_timestamp,
+            bump,
+        });
+        self.job.bid_count += 1;
+
+        emit!(BidSubmitted {
+            job: self.job.key(),
+            bid: self.bid.key(),
+            freelancer: self.freelancer.key(),
+            price,
+            timeline,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::BondrError, EscrowReceipt, FreelancerBadge, Review, ReviewSubmitted, UserStats,
+    MAX_RATING, MIN_RATING,
+};
+
+#[derive(Accounts)]
+pub struct SubmitReview<'info> {
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+
+    #[account(
+        seeds = [b"receipt", receipt.sender.as_ref(), &receipt.escrow_id.to_le_bytes()],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, EscrowReceipt>,
+
+    // init fails if this reviewer already reviewed the escrow
+    #[account(
+        init,
+        payer = reviewer,
+        space = 8 + Review::INIT_SPACE,
+        seeds = [b"review", receipt.key().as_ref(), reviewer.key().as_ref()],
+        bump
+    )]
+    pub review: Account<'info, Review>,
+
+    // stats of the other party, checked against the receipt in instruction logic
+    #[account(
+        mut,
+        seeds = [b"user_stats", reviewee_stats.user.as_ref()],
+        bump = reviewee_stats.bump
+    )]
+    pub reviewee_stats: Account<'info, UserStats>,
+
+    // Always the receiver's badge PDA, so a client's rating can't skip a badge that exists.
+    /// CHECK: read and written in instruction logic when the client reviews the freelancer
+    #[account(
+        mut,
+        seeds = [b"badge", receipt.receiver.as_ref()],
+        bump
+    )]
+    pub reviewee_badge: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitReview<'info> {
+    pub fn submit_review(&mut self, rating: u8, content_hash: [u8; 32], bump: u8) -> Result<()> {
+        // 1. validation, only settled escrows are reviewed
+        require!(
+            (MIN_RATING..=MAX_RATING).contains(&rating),
+            BondrError::InvalidRating
+        );
+        require!(self.receipt.settled_at > 0, BondrError::EscrowNotSettled);
+
+        // 2. each party reviews the other one
+        let reviewer = self.reviewer.key();
+        let reviewee = if reviewer == self.receipt.sender {
+            self.receipt.receiver
+        } else if reviewer == self.receipt.receiver {
+            self.receipt.sender
+        } else {
+            return Err(BondrError::NotEscrowParty.into());
+        };
+        require_keys_eq!(
+            self.reviewee_stats.user,
+            reviewee,
+            BondrError::NotEscrowParty
+        );
+
+        // 3. storing the review
+        self.review.set_inner(Review {
+            receipt: self.receipt.key(),
+            reviewer,
+            reviewee,
+            rating,
+            content_hash,
+            created_at: Clock::get()?.unix_timestamp,
+            bump,
+        });
+
+        // 4. updating aggregates
+        self.reviewee_stats.ratings.record(rating);
+        let badge_info = self.reviewee_badge.to_account_info();
+        if reviewee == self.receipt.receiver && !badge_info.data_is_empty() {
+            let mut badge =
+                FreelancerBadge::try_deserialize(&mut &badge_info.try_borrow_data()?[..])?;
+            badge.ratings.record(rating);
+            badge.try_serialize(&mut &mut badge_info.try_borrow_mut_data()?[..])?;
+        }
+
+        emit!(ReviewSubmitted {
+            receipt: self.receipt.key(),
+            reviewer,
+            reviewee,
+            rating,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, Escrow, WorkSubmitted, REVIEW_WINDOW};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct SubmitWork<'info> {
+    pub receiver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.sender.as_ref(), receiver.key().as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> SubmitWork<'info> {
+    pub fn submit_work(&mut self, deliverable_hash: [u8; 32]) -> Result<()> {
+        // 1. only accepted escrows with something left to release
+        require!(self.escrow.is_accepted, BondrError::EscrowNotAccepted);
+        require!(!self.escrow.is_released, BondrError::AlreadyReleased);
+        // a lapsed review would release the unvested rest of a stream
+        require!(
+            self.escrow.vesting.is_none(),
+            BondrError::StreamingReviewUnsupported
+        );
+
+        // 2. a (re)submission opens a fresh review window and clears a previous dispute
+        let review_ends_at = Clock::get()?.unix_timestamp + REVIEW_WINDOW;
+        self.escrow.deliverable_hash = deliverable_hash;
+        self.escrow.review_ends_at = Some(review_ends_at);
+        self.escrow.is_disputed = false;
+
+        emit!(WorkSubmitted {
+            sender: self.escrow.sender,
+            receiver: self.receiver.key(),
+            escrow: self.escrow.key(),
+            deliverable_hash,
+            review_ends_at,
+        });
+
+        Ok(())
+    }
+}
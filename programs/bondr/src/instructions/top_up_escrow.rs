@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::BondrError,
+    utils::{transfer_sol, transfer_spl_tokens, validate_token_accounts},
+    Escrow, EscrowAsset, EscrowToppedUp,
+};
+
+#[derive(Accounts)]
+#[instruction(reference_seed:u8)]
+pub struct TopUpEscrow<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", sender.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", sender.key().as_ref(), escrow.receiver.as_ref(), &[reference_seed]],
+        bump = escrow.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // SPL top-up, has to land in the escrow's own token account
+    #[account(mut)]
+    pub sender_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+    )]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TopUpEscrow<'info> {
+    pub fn top_up_escrow(&mut self, amount: u64, is_token_transfer: bool) -> Result<()> {
+        // 1. Only the sender adds funds, while some of the escrow is still locked
+        require_keys_eq!(
+            self.sender.key(),
+            self.escrow.sender,
+            BondrError::UnauthorizedSender
+        );
+        require!(!self.escrow.is_released, BondrError::AlreadyReleased);
+        require!(amount > 0, BondrError::InvalidAmountZero);
+        // the top-up has to be in the escrow's asset, escrows migrated without one can't grow
+        require!(
+            self.escrow.asset != EscrowAsset::Unrecorded,
+            BondrError::EscrowAssetMismatch
+        );
+        self.escrow
+            .check_asset(is_token_transfer, self.token_mint.as_ref().map(|m| m.key()))?;
+
+        //max amount check(1000 SOL) on the grown escrow
+        let new_amount = self.escrow.amount + amount;
+        require!(new_amount <= 1_000_000_000_000, BondrError::AmountTooLarge);
+
+        // 2. funding the vault/escrow ATA
+        if is_token_transfer {
+            validate_token_accounts(
+                &self.token_program,
+                &self.sender_token_account,
+                &self.escrow_token_account,
+                &self.token_mint,
+            )?;
+
+            transfer_spl_tokens(
+                self.sender_token_account.as_ref().unwrap(),
+                self.escrow_token_account.as_ref().unwrap(),
+                self.token_mint.as_ref().unwrap(),
+                &self.sender.to_account_info(),
+                self.token_program.as_ref().unwrap(),
+                amount,
+                None, // No signer seeds needed for sender
+            )?;
+        } else {
+            transfer_sol(
+                &self.sender.to_account_info(),
+                &self.vault.to_account_info(),
+                &self.system_program,
+                amount,
+                None, // No signer seeds needed for sender
+            )?;
+        }
+
+        // 3. the added part is locked until released like the rest
+        self.escrow.amount = new_amount;
+
+        emit!(EscrowToppedUp {
+            sender: self.escrow.sender,
+            receiver: self.escrow.receiver,
+            escrow: self.escrow.key(),
+            amount,
+            new_amount,
+        });
+
+        Ok(())
+    }
+}
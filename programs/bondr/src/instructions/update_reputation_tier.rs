@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::BondrError, ReputationConfig, ReputationConfigUpdated, ReputationTier, TierThresholds,
+    MAX_TIER_NAME_LEN, MAX_TIER_URI_LEN,
+};
+
+#[derive(Accounts)]
+pub struct UpdateReputationTier<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation_config"],
+        bump = config.bump,
+        has_one = admin @ BondrError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, ReputationConfig>,
+}
+
+impl<'info> UpdateReputationTier<'info> {
+    pub fn update_tier(
+        &mut self,
+        tier: ReputationTier,
+        thresholds: TierThresholds,
+        for_clients: bool,
+    ) -> Result<()> {
+        // 1. metadata must fit in the space reserved for it
+        require!(
+            !thresholds.name.is_empty() && thresholds.name.len() <= MAX_TIER_NAME_LEN,
+            BondrError::InvalidTierConfig
+        );
+        require!(
+            !thresholds.uri.is_empty() && thresholds.uri.len() <= MAX_TIER_URI_LEN,
+            BondrError::InvalidTierConfig
+        );
+
+        // 2. a ranked tier needs at least one completed escrow
+        require!(
+            thresholds.min_completed_escrows > 0,
+            BondrError::InvalidTierConfig
+        );
+
+        // 3. replacing the tier entry on the freelancer or client ladder,
+        // a higher tier can't be easier to reach than the one below it
+        let index = ReputationConfig::tier_index(&tier).ok_or(BondrError::InvalidTierConfig)?;
+        let ladder = if for_clients {
+            &mut self.config.client_tiers
+        } else {
+            &mut self.config.tiers
+        };
+        ladder[index] = thresholds;
+        require!(
+            ReputationConfig::is_ladder_ordered(ladder),
+            BondrError::InvalidTierConfig
+        );
+
+        // 4. emit event
+        emit!(ReputationConfigUpdated {
+            admin: self.admin.key(),
+            config: self.config.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, ClientMultisig, Escrow, MultisigReleaseVetoed};
+
+#[derive(Accounts)]
+#[instruction(reference_seed: u8)]
+pub struct VetoMultisigRelease<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"client_multisig", escrow.sender.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, ClientMultisig>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.sender.key().as_ref(), escrow.receiver.key().as_ref(), &[reference_seed]],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> VetoMultisigRelease<'info> {
+    pub fn veto_multisig(&mut self) -> Result<()> {
+        let multisig = &mut self.multisig;
+
+        // 1. checking that pending escrow must match this one
+        require_keys_eq!(
+            multisig.pending_escrow,
+            self.escrow.key(),
+            BondrError::MultisigPendingEscrowMismatch
+        );
+
+        // 2. any single member can veto, approvers included
+        multisig
+            .member_index(&self.member.key())
+            .ok_or(BondrError::NotMultisigMember)?;
+
+        // 3. only while the release is still timelocked
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.escrow
+                .releasable_at
+                .is_some_and(|releasable_at| now < releasable_at),
+            BondrError::NoTimelockedRelease
+        );
+
+        // 4. back to square one, the group has to approve again
+        multisig.reset_votes();
+        self.escrow.unrelease();
+        self.escrow.releasable_at = None;
+
+        emit!(MultisigReleaseVetoed {
+            multisig: multisig.key(),
+            member: self.member.key(),
+            escrow: self.escrow.key(),
+        });
+
+        Ok(())
+    }
+}
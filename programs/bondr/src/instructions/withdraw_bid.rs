@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::Bid;
+
+#[derive(Accounts)]
+pub struct WithdrawBid<'info> {
+    #[account(mut)]
+    pub freelancer: Signer<'info>,
+
+    // works after the job is gone too, so losing bidders get their rent back
+    #[account(
+        mut,
+        close = freelancer,
+        seeds = [b"bid", bid.job.as_ref(), freelancer.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, Bid>,
+}
+
+impl<'info> WithdrawBid<'info> {
+    pub fn withdraw_bid(&mut self) -> Result<()> {
+        // closed by the account constraint, bid_count keeps counting submissions
+        Ok(())
+    }
+}
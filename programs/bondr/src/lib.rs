@@ -1,4 +1,5 @@
-#![allow(unexpected_cfgs, deprecated)]
+// too_many_arguments also covers the instruction builders generated for the `cpi` feature
+#![allow(unexpected_cfgs, deprecated, clippy::too_many_arguments)]
 
 pub mod constants;
 pub mod error;
@@ -28,29 +29,252 @@ pub mod bondr {
         reference_seed: u8,
         is_token_transfer: bool,
         is_multisig: bool,
-    ) -> Result<()> {
+        deadline: Option<i64>,
+        terms_hash: Option<[u8; 32]>,
+        vesting: Option<VestingSchedule>,
+        team: Option<Vec<TeamSplit>>,
+        callback: Option<EscrowCallback>,
+    ) -> Result<u64> {
         ctx.accounts.init_escrow(
             amount,
             reference_seed,
             is_token_transfer,
             is_multisig,
+            deadline,
+            terms_hash,
+            vesting,
+            team,
+            callback,
             ctx.bumps.escrow,
             ctx.bumps.vault,
             ctx.bumps.sender_stats,
         )
     }
 
-    pub fn release_payment(ctx: Context<ReleasePayment>, reference_seed: u8) -> Result<()> {
-        ctx.accounts.release_payment(reference_seed)
+    pub fn accept_escrow(
+        ctx: Context<AcceptEscrow>,
+        _reference_seed: u8,
+        terms_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.accept_escrow(terms_hash)
+    }
+
+    pub fn decline_escrow<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DeclineEscrow<'info>>,
+        reference_seed: u8,
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .decline_escrow(reference_seed, is_token_transfer, ctx.remaining_accounts)
+    }
+
+    pub fn submit_work(
+        ctx: Context<SubmitWork>,
+        _reference_seed: u8,
+        deliverable_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.submit_work(deliverable_hash)
+    }
+
+    pub fn dispute_work(ctx: Context<DisputeWork>, _reference_seed: u8) -> Result<()> {
+        ctx.accounts.dispute_work()
+    }
+
+    pub fn release_payment(
+        ctx: Context<ReleasePayment>,
+        reference_seed: u8,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.release_payment(reference_seed, amount)
+    }
+
+    pub fn claim_payment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimPayment<'info>>,
+        reference_seed: u8,
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts.claim_payment(
+            reference_seed,
+            is_token_transfer,
+            ctx.bumps.receiver_stats,
+            ctx.bumps.counterparty,
+            ctx.bumps.receipt,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn cancel_escrow<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelEscrow<'info>>,
+        reference_seed: u8,
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .cancel_escrow(reference_seed, is_token_transfer, ctx.remaining_accounts)
+    }
+
+    pub fn top_up_escrow(
+        ctx: Context<TopUpEscrow>,
+        _reference_seed: u8,
+        amount: u64,
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts.top_up_escrow(amount, is_token_transfer)
+    }
+
+    pub fn amend_escrow(
+        ctx: Context<AmendEscrow>,
+        reference_seed: u8,
+        is_token_transfer: bool,
+        amendment: EscrowAmendment,
+    ) -> Result<()> {
+        ctx.accounts.amend_escrow(
+            reference_seed,
+            is_token_transfer,
+            amendment,
+            ctx.bumps.new_escrow,
+            ctx.bumps.new_vault,
+        )
+    }
+
+    pub fn initialize_retainer(
+        ctx: Context<InitializeRetainer>,
+        _reference_seed: u8,
+        terms: RetainerTerms,
+        funded_periods: u32,
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts.initialize_retainer(
+            terms,
+            funded_periods,
+            is_token_transfer,
+            ctx.bumps.retainer,
+            ctx.bumps.vault,
+        )
+    }
+
+    pub fn fund_retainer(
+        ctx: Context<FundRetainer>,
+        _reference_seed: u8,
+        periods: u32,
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts.fund_retainer(periods, is_token_transfer)
+    }
+
+    pub fn crank_retainer(ctx: Context<CrankRetainer>) -> Result<()> {
+        ctx.accounts.crank_retainer()
+    }
+
+    pub fn set_retainer_paused(
+        ctx: Context<SetRetainerPaused>,
+        _reference_seed: u8,
+        is_paused: bool,
+    ) -> Result<()> {
+        ctx.accounts.set_retainer_paused(is_paused)
+    }
+
+    pub fn cancel_retainer(
+        ctx: Context<CancelRetainer>,
+        reference_seed: u8,
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .cancel_retainer(reference_seed, is_token_transfer)
+    }
+
+    pub fn claim_retainer(
+        ctx: Context<ClaimRetainer>,
+        reference_seed: u8,
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .claim_retainer(reference_seed, is_token_transfer)
+    }
+
+    pub fn assign_receiver(
+        ctx: Context<AssignReceiver>,
+        reference_seed: u8,
+        _new_reference_seed: u8,
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts.assign_receiver(
+            reference_seed,
+            is_token_transfer,
+            ctx.bumps.new_escrow,
+            ctx.bumps.new_vault,
+        )
+    }
+
+    pub fn post_job(
+        ctx: Context<PostJob>,
+        _reference_seed: u8,
+        budget: u64,
+        description_hash: [u8; 32],
+        is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts.post_job(
+            budget,
+            description_hash,
+            is_token_transfer,
+            ctx.bumps.job,
+            ctx.bumps.vault,
+        )
+    }
+
+    pub fn submit_bid(
+        ctx: Context<SubmitBid>,
+        price: u64,
+        timeline: i64,
+        proposal_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts
+            .submit_bid(price, timeline, proposal_hash, ctx.bumps.bid)
+    }
+
+    pub fn withdraw_bid(ctx: Context<WithdrawBid>) -> Result<()> {
+        ctx.accounts.withdraw_bid()
     }
 
-    pub fn claim_payment(
-        ctx: Context<ClaimPayment>,
+    pub fn accept_bid(
+        ctx: Context<AcceptBid>,
+        job_reference_seed: u8,
+        reference_seed: u8,
+        callback: Option<EscrowCallback>,
+    ) -> Result<()> {
+        ctx.accounts.accept_bid(
+            job_reference_seed,
+            reference_seed,
+            callback,
+            ctx.bumps.escrow,
+            ctx.bumps.vault,
+            ctx.bumps.client_stats,
+        )
+    }
+
+    pub fn cancel_job(ctx: Context<CancelJob>, reference_seed: u8) -> Result<()> {
+        ctx.accounts.cancel_job(reference_seed)
+    }
+
+    pub fn reclaim_expired_escrow<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReclaimExpiredEscrow<'info>>,
         reference_seed: u8,
         is_token_transfer: bool,
+    ) -> Result<()> {
+        ctx.accounts.reclaim_expired_escrow(
+            reference_seed,
+            is_token_transfer,
+            ctx.bumps.badge_authority,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn submit_review(
+        ctx: Context<SubmitReview>,
+        rating: u8,
+        content_hash: [u8; 32],
     ) -> Result<()> {
         ctx.accounts
-            .claim_payment(reference_seed, is_token_transfer, ctx.bumps.receiver_stats)
+            .submit_review(rating, content_hash, ctx.bumps.review)
     }
 
     pub fn initialize_freelancer_badge(ctx: Context<InitializeFreelancerBadge>) -> Result<()> {
@@ -65,14 +289,101 @@ pub mod bondr {
         ctx.accounts.mint_nft()
     }
 
+    pub fn initialize_client_badge(ctx: Context<InitializeClientBadge>) -> Result<()> {
+        ctx.accounts.initialize_client_badge(ctx.bumps.badge)
+    }
+
+    pub fn mint_client_reputation_nft(ctx: Context<MintClientReputationNFT>) -> Result<()> {
+        ctx.accounts.mint_client_nft()
+    }
+
+    pub fn refresh_reputation(ctx: Context<RefreshReputation>) -> Result<()> {
+        ctx.accounts.refresh_reputation(ctx.bumps.badge_authority)
+    }
+
+    pub fn initialize_reputation_config(ctx: Context<InitializeReputationConfig>) -> Result<()> {
+        ctx.accounts.init_reputation_config(ctx.bumps.config)
+    }
+
+    pub fn update_reputation_tier(
+        ctx: Context<UpdateReputationTier>,
+        tier: ReputationTier,
+        thresholds: TierThresholds,
+    ) -> Result<()> {
+        ctx.accounts.update_tier(tier, thresholds, false)
+    }
+
+    pub fn update_client_reputation_tier(
+        ctx: Context<UpdateReputationTier>,
+        tier: ReputationTier,
+        thresholds: TierThresholds,
+    ) -> Result<()> {
+        ctx.accounts.update_tier(tier, thresholds, true)
+    }
+
+    pub fn set_mint_price(ctx: Context<SetMintPrice>, mint: Pubkey, price: u64) -> Result<()> {
+        ctx.accounts.set_mint_price(mint, price)
+    }
+
+    pub fn set_min_escrow_value(
+        ctx: Context<SetMinEscrowValue>,
+        min_escrow_value: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_min_escrow_value(min_escrow_value)
+    }
+
+    pub fn set_decay_period(ctx: Context<SetDecayPeriod>, decay_period: i64) -> Result<()> {
+        ctx.accounts.set_decay_period(decay_period)
+    }
+
+    pub fn set_failure_penalty(
+        ctx: Context<SetFailurePenalty>,
+        failure_penalty: u32,
+    ) -> Result<()> {
+        ctx.accounts.set_failure_penalty(failure_penalty)
+    }
+
     pub fn initialize_multisig_client(
         ctx: Context<InitializeMultisigClient>,
-        members: [Pubkey; MAX_MULTISIG_MEMBERS],
+        members: Vec<Pubkey>,
         member_count: u8,
         threshold: u8,
+        timelock: Option<i64>,
+        weights: Option<Vec<u8>>,
+    ) -> Result<()> {
+        ctx.accounts.init_multisig_client(
+            members,
+            member_count,
+            threshold,
+            timelock,
+            weights,
+            ctx.bumps.multisig,
+        )
+    }
+
+    pub fn add_multisig_members<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddMultisigMembers<'info>>,
+        members: Vec<Pubkey>,
+        weights: Option<Vec<u8>>,
     ) -> Result<()> {
         ctx.accounts
-            .init_multisig_client(members, member_count, threshold, ctx.bumps.multisig)
+            .add_members(members, weights, ctx.remaining_accounts)
+    }
+
+    pub fn migrate_escrow(ctx: Context<MigrateAccount>) -> Result<()> {
+        ctx.accounts.migrate::<Escrow>()
+    }
+
+    pub fn migrate_user_stats(ctx: Context<MigrateAccount>) -> Result<()> {
+        ctx.accounts.migrate::<UserStats>()
+    }
+
+    pub fn migrate_freelancer_badge(ctx: Context<MigrateAccount>) -> Result<()> {
+        ctx.accounts.migrate::<FreelancerBadge>()
+    }
+
+    pub fn migrate_client_multisig(ctx: Context<MigrateAccount>) -> Result<()> {
+        ctx.accounts.migrate::<ClientMultisig>()
     }
 
     pub fn approve_multisig_release(
@@ -81,4 +392,38 @@ pub mod bondr {
     ) -> Result<()> {
         ctx.accounts.approve_multisig()
     }
+
+    pub fn revoke_multisig_approval(
+        ctx: Context<RevokeMultisigApproval>,
+        _reference_seed: u8,
+    ) -> Result<()> {
+        ctx.accounts.revoke_multisig_approval()
+    }
+
+    pub fn reject_multisig_release(
+        ctx: Context<RejectMultisigRelease>,
+        _reference_seed: u8,
+    ) -> Result<()> {
+        ctx.accounts.reject_multisig()
+    }
+
+    pub fn veto_multisig_release(
+        ctx: Context<VetoMultisigRelease>,
+        _reference_seed: u8,
+    ) -> Result<()> {
+        ctx.accounts.veto_multisig()
+    }
+
+    pub fn set_payout_destination(
+        ctx: Context<SetPayoutDestination>,
+        treasury: Pubkey,
+        with_multisig: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_payout_destination(treasury, with_multisig, ctx.bumps.destination)
+    }
+
+    pub fn approve_payout(ctx: Context<ApprovePayout>, target: Pubkey) -> Result<()> {
+        ctx.accounts.approve_payout(target, ctx.bumps.approval)
+    }
 }
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+use crate::ReputationTier;
+
+// client counterpart of FreelancerBadge, the counters live on the client's UserStats
+#[account]
+#[derive(InitSpace)]
+pub struct ClientBadge {
+    pub tier: ReputationTier,
+    pub client: Pubkey,
+    pub bump: u8,
+}
@@ -1,18 +1,131 @@
 use anchor_lang::prelude::*;
 
-use crate::MAX_MULTISIG_MEMBERS;
+use crate::INLINE_MULTISIG_MEMBERS;
 
 #[account]
 #[derive(InitSpace)]
 pub struct ClientMultisig {
-    pub members: [Pubkey; MAX_MULTISIG_MEMBERS], // max 5 members allowed as per now
-    pub member_count: u8,                        // how many active entries in `members`
-    pub threshold: u8,                           // approvals required
-    pub approvals: [u8; MAX_MULTISIG_MEMBERS], // 0 = not approved, 1 = approved; parallel to members
-    pub pending_escrow: Pubkey,                // escrow PDA tied to this multisig
+    pub version: u8, // see CLIENT_MULTISIG_VERSION, new fields are appended below
+    pub members: [Pubkey; INLINE_MULTISIG_MEMBERS], // first 5 members, the rest are in `extra_members`
+    pub member_count: u8,                           // how many active members, inline and extra
+    pub threshold: u8,                              // approval weight required
+    pub approvals: [u8; INLINE_MULTISIG_MEMBERS], // 0 = no vote, 1 = approved, 2 = rejected; parallel to members
+    pub pending_escrow: Pubkey,                   // escrow PDA tied to this multisig
     pub bump: u8,
+    pub timelock: i64, // v2: seconds between reaching the threshold and the claim, 0 = none
+    pub weights: [u8; INLINE_MULTISIG_MEMBERS], // v3: voting weight per member, parallel to members
+    // v4: members past the inline ones, the account is sized for them (see `space`)
+    #[max_len(0)]
+    pub extra_members: Vec<MultisigMember>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct MultisigMember {
+    pub key: Pubkey,
+    pub weight: u8,
+    pub vote: u8, // same as `approvals`
+}
+
+impl ClientMultisig {
+    // account size for `member_count` members, only members past the inline ones add to it
+    pub fn space(member_count: usize) -> usize {
+        8 + Self::INIT_SPACE
+            + member_count.saturating_sub(INLINE_MULTISIG_MEMBERS) * MultisigMember::INIT_SPACE
+    }
+
+    // members are indexed inline first, then through `extra_members`
+    pub fn member(&self, index: usize) -> Pubkey {
+        match index.checked_sub(INLINE_MULTISIG_MEMBERS) {
+            None => self.members[index],
+            Some(extra) => self.extra_members[extra].key,
+        }
+    }
+
+    pub fn weight(&self, index: usize) -> u8 {
+        match index.checked_sub(INLINE_MULTISIG_MEMBERS) {
+            None => self.weights[index],
+            Some(extra) => self.extra_members[extra].weight,
+        }
+    }
+
+    pub fn vote(&self, index: usize) -> u8 {
+        match index.checked_sub(INLINE_MULTISIG_MEMBERS) {
+            None => self.approvals[index],
+            Some(extra) => self.extra_members[extra].vote,
+        }
+    }
+
+    pub fn set_vote(&mut self, index: usize, vote: u8) {
+        match index.checked_sub(INLINE_MULTISIG_MEMBERS) {
+            None => self.approvals[index] = vote,
+            Some(extra) => self.extra_members[extra].vote = vote,
+        }
+    }
+
+    pub fn reset_votes(&mut self) {
+        self.approvals = [0u8; INLINE_MULTISIG_MEMBERS];
+        self.extra_members.iter_mut().for_each(|m| m.vote = 0);
+    }
+
+    // appends an active member, callers check for duplicates and the member limit
+    pub fn push_member(&mut self, key: Pubkey, weight: u8) {
+        let index = self.member_count as usize;
+        if index < INLINE_MULTISIG_MEMBERS {
+            self.members[index] = key;
+            self.weights[index] = weight;
+            self.approvals[index] = 0;
+        } else {
+            self.extra_members.push(MultisigMember {
+                key,
+                weight,
+                vote: 0,
+            });
+        }
+        self.member_count += 1;
+    }
+
+    pub fn member_index(&self, member: &Pubkey) -> Option<usize> {
+        (0..self.member_count as usize).find(|&i| self.member(i) == *member)
+    }
+
+    // summed weight of the active members whose vote matches
+    fn weight_where(&self, vote: impl Fn(u8) -> bool) -> u16 {
+        (0..self.member_count as usize)
+            .filter(|&i| vote(self.vote(i)))
+            .map(|i| self.weight(i) as u16)
+            .sum()
+    }
+
+    // summed weight of the members whose bit is set, see PayoutApproval
+    pub fn bitmap_weight(&self, bitmap: u32) -> u16 {
+        (0..self.member_count as usize)
+            .filter(|&i| bitmap & (1 << i) != 0)
+            .map(|i| self.weight(i) as u16)
+            .sum()
+    }
+
+    pub fn approved_weight(&self) -> u16 {
+        self.weight_where(|a| a == 1)
+    }
+
+    pub fn rejected_weight(&self) -> u16 {
+        self.weight_where(|a| a == 2)
+    }
+
+    pub fn total_weight(&self) -> u16 {
+        self.weight_where(|_| true)
+    }
+
+    pub fn threshold_met(&self) -> bool {
+        self.approved_weight() >= self.threshold as u16
+    }
+
+    // too much weight rejected, the remaining members can't reach the threshold
+    pub fn threshold_unreachable(&self) -> bool {
+        self.total_weight() - self.rejected_weight() < self.threshold as u16
+    }
 }
 
 // space it will take -
-// INIT_SPACE = 32*8 + 1 + 1 + 8 + 32 + 1 = 256 + 1 + 1 + 8 + 32 + 1 = 299 bytes
-// space = 8 + ClientMultisig::INIT_SPACE  // 8 + 299 = 307
+// INIT_SPACE = 1 + 32*5 + 1 + 1 + 5 + 32 + 1 + 8 + 5 + 4 = 218 bytes
+// space = 8 + ClientMultisig::INIT_SPACE + 34 per member past 5  // 226 for 5, 1144 for 32
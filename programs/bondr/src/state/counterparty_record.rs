@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+// one per freelancer/client pair, lets claim_payment count distinct clients
+#[account]
+#[derive(InitSpace)]
+pub struct CounterpartyRecord {
+    pub freelancer: Pubkey,
+    pub client: Pubkey,
+    pub completed_escrows: u32,
+    pub bump: u8,
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    error::BondrError, EscrowCallback, MAX_CALLBACK_ACCOUNTS, MAX_TEAM_RECIPIENTS, TOTAL_BPS,
+};
+
 #[account]
 #[derive(InitSpace)]
 pub struct Escrow {
+    pub version: u8, // see ESCROW_VERSION, new fields are appended below
     pub sender: Pubkey,
     pub receiver: Pubkey,
     pub amount: u64,
@@ -10,4 +15,222 @@ pub struct Escrow {
     pub bump: u8,
     pub vault_bump: u8,
     pub client_multisig: Option<Pubkey>, //optional field if multisig present
+    pub escrow_id: u64, // sender's escrows_funded at creation, unique per sender unlike the reusable seeds
+    pub deadline: Option<i64>, // after this an unreleased escrow can be reclaimed by the sender
+    pub released_amount: u64, // v2: released by the client so far, claimable by the freelancer
+    pub claimed_amount: u64, // v2: already paid out to the freelancer
+    pub terms_hash: [u8; 32], // v3: hash of the off-chain terms, committed to by both parties
+    pub is_accepted: bool, // v3: the receiver accepted the terms, required before any release
+    pub deliverable_hash: [u8; 32], // v4: hash of the submitted work, zeros until submit_work
+    pub review_ends_at: Option<i64>, // v4: end of the client review window of the latest submission
+    pub is_disputed: bool, // v4: the client disputed the submission, no auto-release
+    pub vesting: Option<VestingSchedule>, // v5: streaming escrow, released linearly over time
+    pub team_size: u8,  // v6: recipients of a team escrow, 0 pays everything to `receiver`
+    pub team_recipients: [Pubkey; MAX_TEAM_RECIPIENTS], // v6: team_recipients[0] is `receiver`
+    pub team_bps: [u16; MAX_TEAM_RECIPIENTS], // v6: split of each recipient, adds up to TOTAL_BPS
+    pub team_claimed: [u64; MAX_TEAM_RECIPIENTS], // v6: paid out to each recipient
+    pub is_rejected: bool, // v7: the multisig rejected the release, only a refund is left
+    pub releasable_at: Option<i64>, // v8: multisig timelock, nothing can be claimed before this
+    pub callback_program: Option<Pubkey>, // v9: notified when the escrow settles, see EscrowCallback
+    pub callback_account_count: u8,       // v9: registered entries in callback_accounts
+    pub callback_accounts: [Pubkey; MAX_CALLBACK_ACCOUNTS], // v9: passed to the callback after the escrow
+    pub asset: EscrowAsset, // v10: what the escrow was funded with, every transfer must move it
+    pub multisig_released: u64, // v11: part of released_amount released by the multisig threshold
+}
+
+impl Escrow {
+    // still locked, can only be released or refunded
+    pub fn unreleased(&self) -> u64 {
+        self.amount - self.released_amount
+    }
+
+    // released but not paid out yet
+    pub fn claimable(&self) -> u64 {
+        self.released_amount - self.claimed_amount
+    }
+
+    pub fn release(&mut self, amount: u64) {
+        self.released_amount += amount;
+        self.is_released = self.released_amount == self.amount;
+    }
+
+    // takes back the multisig's release unless the freelancer already claimed from it, returns
+    // whether it did. Partial and vested releases made before it stay, see revoke_multisig_approval
+    pub fn unrelease(&mut self) -> bool {
+        let pending = self.multisig_released;
+        if pending == 0 || self.claimable() < pending {
+            return false;
+        }
+        self.released_amount -= pending;
+        self.multisig_released = 0;
+        self.is_released = false;
+        true
+    }
+
+    // the refunded remainder leaves the escrow, what was released stays claimable.
+    // A dispute ends with the refund, the client doesn't lose it
+    pub fn refund_unreleased(&mut self) -> u64 {
+        let refund = self.unreleased();
+        self.amount = self.released_amount;
+        self.is_released = true;
+        self.is_disputed = false;
+        refund
+    }
+
+    // submitted work nobody acted on within the review window is released to the freelancer
+    pub fn review_lapsed(&self, now: i64) -> bool {
+        !self.is_released
+            && !self.is_disputed
+            && !self.is_rejected
+            && self.review_ends_at.is_some_and(|end| now >= end)
+    }
+
+    // submitted work can only be refunded while the client contests it, through a dispute or a
+    // multisig rejection. Once its review window lapsed it counts as released, see review_lapsed
+    pub fn awaits_review(&self) -> bool {
+        self.review_ends_at.is_some() && !self.is_disputed && !self.is_rejected
+    }
+
+    // releases what vested since the last release. `dust` is the smallest balance the vault can
+    // keep: a remainder below it vests with the rest, a new chunk below it waits for the next call
+    pub fn release_vested(&mut self, now: i64, dust: u64) {
+        let Some(schedule) = self.vesting else {
+            return;
+        };
+        if self.is_released || !self.is_accepted || self.is_rejected {
+            return;
+        }
+
+        let vested = schedule.vested(self.amount, now).max(self.released_amount);
+        if self.amount - vested < dust {
+            self.release(self.unreleased());
+        } else if vested - self.claimed_amount >= dust {
+            self.release(vested - self.released_amount);
+        }
+    }
+
+    // funded without a receiver, see assign_receiver
+    pub fn is_bounty(&self) -> bool {
+        self.receiver == Pubkey::default()
+    }
+
+    pub fn is_team(&self) -> bool {
+        self.team_size > 0
+    }
+
+    pub fn team_index(&self, recipient: &Pubkey) -> Option<usize> {
+        self.team_recipients[..self.team_size as usize]
+            .iter()
+            .position(|r| r == recipient)
+    }
+
+    // recipient's part of `total` released so far. Every share rounds down, so a share never
+    // shrinks as more is released; the lead gets the rounding leftovers once everything is released
+    pub fn team_share(&self, index: usize, total: u64) -> u64 {
+        let split =
+            |i: usize| (total as u128 * self.team_bps[i] as u128 / TOTAL_BPS as u128) as u64;
+        if index == 0 && self.is_released && total == self.amount {
+            total - (1..self.team_size as usize).map(split).sum::<u64>()
+        } else {
+            split(index)
+        }
+    }
+
+    // nothing left to claim or refund, the escrow can be closed
+    pub fn is_settled(&self) -> bool {
+        self.claimed_amount == self.amount
+    }
+
+    // `token_mint` is the mint passed along, only looked at for token transfers
+    pub fn check_asset(&self, is_token_transfer: bool, token_mint: Option<Pubkey>) -> Result<()> {
+        let token_mint = if is_token_transfer { token_mint } else { None };
+        require!(
+            self.asset.matches(token_mint),
+            BondrError::EscrowAssetMismatch
+        );
+        Ok(())
+    }
+
+    pub fn callback_accounts(&self) -> &[Pubkey] {
+        &self.callback_accounts[..self.callback_account_count as usize]
+    }
+
+    // callers check `EscrowCallback::is_valid` first
+    pub fn set_callback(&mut self, callback: &EscrowCallback) {
+        self.callback_program = Some(callback.program);
+        self.callback_account_count = callback.accounts.len() as u8;
+        self.callback_accounts[..callback.accounts.len()].copy_from_slice(&callback.accounts);
+    }
+}
+
+// Escrows migrated from before v10 are `Unrecorded`: they still pay out of their own vault,
+// but can't be topped up since nothing says which asset the top-up should be in
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscrowAsset {
+    Unrecorded,
+    Sol,
+    Token { mint: Pubkey },
+}
+
+impl EscrowAsset {
+    // `token_mint` is the mint of a token transfer, None for SOL
+    pub fn new(token_mint: Option<Pubkey>) -> Self {
+        match token_mint {
+            Some(mint) => Self::Token { mint },
+            None => Self::Sol,
+        }
+    }
+
+    pub fn matches(&self, token_mint: Option<Pubkey>) -> bool {
+        match self {
+            Self::Unrecorded => true,
+            Self::Sol => token_mint.is_none(),
+            Self::Token { mint } => token_mint == Some(*mint),
+        }
+    }
+}
+
+// linear unlock between start and end, nothing unlocks before the cliff
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub start: i64,
+    pub cliff: Option<i64>,
+    pub end: i64,
+}
+
+impl VestingSchedule {
+    pub fn is_valid(&self) -> bool {
+        let cliff = self.cliff.unwrap_or(self.start);
+        self.start < self.end && cliff >= self.start && cliff <= self.end
+    }
+
+    pub fn vested(&self, total: u64, now: i64) -> u64 {
+        if now < self.cliff.unwrap_or(self.start) {
+            return 0;
+        }
+        if now >= self.end {
+            return total;
+        }
+        let elapsed = (now - self.start) as u128;
+        let duration = (self.end - self.start) as u128;
+        (total as u128 * elapsed / duration) as u64
+    }
+}
+
+// one recipient of a team escrow, see initialize_escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TeamSplit {
+    pub recipient: Pubkey,
+    pub bps: u16,
+}
+
+// changes agreed on by both parties, see amend_escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EscrowAmendment {
+    pub amount: Option<u64>, // lower amount, the difference goes back to the sender
+    pub deadline: Option<i64>, // new deadline, must be in the future
+    pub remove_deadline: bool,
+    pub terms_hash: Option<[u8; 32]>, // new terms, accepted by both signatures
+    pub receiver: Option<Pubkey>, // new receiver, the escrow moves to new PDAs and needs a new acceptance
+    pub new_reference_seed: u8,   // seed of the moved escrow, only used with `receiver`
 }
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+// written by claim_payment, outlives the Escrow account so settled escrows can still be reviewed
+#[account]
+#[derive(InitSpace)]
+pub struct EscrowReceipt {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub escrow_id: u64, // see Escrow.escrow_id
+    pub amount: u64,
+    pub settled_at: i64, // 0 until the claim that settles the escrow, reviews wait for it
+    pub bump: u8,
+}
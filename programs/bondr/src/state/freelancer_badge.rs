@@ -1,13 +1,70 @@
 use anchor_lang::prelude::*;
 
-use crate::ReputationTier;
+use crate::{RatingSummary, ReputationTier};
 
 #[account]
 #[derive(InitSpace)]
-pub struct FreelancerBadge{
-	pub tier: ReputationTier, //enum - Unranked -> Verified -> Professional -> Elite
-	pub completed_escrows: u32,
-	pub total_value_completed: u64,
-	pub freelancer: Pubkey,
-	pub bump: u8
+pub struct FreelancerBadge {
+    pub version: u8, // see FREELANCER_BADGE_VERSION, new fields are appended below
+    pub tier: ReputationTier, //enum - Unranked -> Verified -> Professional -> Elite
+    pub completed_escrows: u32,
+    pub total_value_completed: u64,
+    pub freelancer: Pubkey,
+    pub bump: u8,
+    // verified counters, only fed by claim_payment and used for tier eligibility
+    pub qualified_escrows: u32, // claims worth at least ReputationConfig.min_escrow_value
+    pub total_value_normalized: u64, // claim value priced through ReputationConfig.mint_prices
+    pub distinct_clients: u32,
+    pub ratings: RatingSummary, // reviews received as the receiver of a settled escrow
+    // activity, see refresh_reputation
+    pub last_completed_at: i64,
+    pub effective_tier: ReputationTier, // `tier` lowered by inactivity, what rankings should use
+    pub is_stale: bool,                 // effective_tier is below the minted tier
+    // reliability, fed by reclaim_expired_escrow
+    pub failed_escrows: u32,
+    pub failed_value: u64, // normalized like total_value_normalized
+    pub asset: Pubkey,     // latest minted badge asset, carries the attributes plugin
+}
+
+impl FreelancerBadge {
+    // normalized_value is None for mints without a configured price
+    pub fn record_completion(
+        &mut self,
+        amount: u64,
+        normalized_value: Option<u64>,
+        min_escrow_value: u64,
+        new_client: bool,
+        now: i64,
+    ) {
+        self.completed_escrows += 1;
+        self.last_completed_at = now;
+        self.total_value_completed += amount;
+
+        // dust and unpriced escrows still show up in the raw counters, but never count towards a tier
+        if let Some(value) = normalized_value.filter(|v| *v >= min_escrow_value) {
+            self.qualified_escrows += 1;
+            self.total_value_normalized += value;
+        }
+        if new_client {
+            self.distinct_clients += 1;
+        }
+    }
+
+    pub fn record_failure(&mut self, normalized_value: Option<u64>) {
+        self.failed_escrows += 1;
+        self.failed_value += normalized_value.unwrap_or(0);
+    }
+
+    // effective tier drops one step per full decay period without a completed escrow, 0 disables decay,
+    // and never exceeds what the counters still qualify for (see ReputationConfig::eligible_tier)
+    pub fn refresh(&mut self, now: i64, decay_period: i64, eligible_tier: ReputationTier) {
+        let idle_periods = if decay_period > 0 && self.last_completed_at > 0 {
+            (now - self.last_completed_at).max(0) / decay_period
+        } else {
+            0
+        };
+
+        self.effective_tier = self.tier.lowered(idle_periods as u64).min(eligible_tier);
+        self.is_stale = self.effective_tier < self.tier;
+    }
 }
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+// funded job listing, freelancers bid on it and accept_bid turns the winning bid into an escrow
+#[account]
+#[derive(InitSpace)]
+pub struct Job {
+    pub version: u8, // see JOB_VERSION
+    pub client: Pubkey,
+    pub budget: u64,                // funded at posting, caps every bid
+    pub description_hash: [u8; 32], // hash of the off-chain job description
+    pub token_mint: Option<Pubkey>, // None for SOL jobs, bids are priced in this mint otherwise
+    pub bid_count: u32,             // bids submitted so far, withdrawn ones included
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+// a freelancer's offer on a job, one per freelancer and job
+#[account]
+#[derive(InitSpace)]
+pub struct Bid {
+    pub job: Pubkey,
+    pub freelancer: Pubkey,
+    pub price: u64,
+    pub timeline: i64, // seconds from acceptance, becomes the escrow deadline
+    pub proposal_hash: [u8; 32], // hash of the off-chain proposal, becomes the escrow terms
+    pub created_at: i64,
+    pub bump: u8,
+}
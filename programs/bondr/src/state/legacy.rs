@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    ClientMultisig, Escrow, EscrowAsset, FreelancerBadge, RatingSummary, ReputationTier, UserStats,
+    CLIENT_MULTISIG_VERSION, ESCROW_VERSION, FREELANCER_BADGE_VERSION, INLINE_MULTISIG_MEMBERS,
+    MAX_CALLBACK_ACCOUNTS, MAX_TEAM_RECIPIENTS, USER_STATS_VERSION,
+};
+
+// Layouts deployed before accounts carried a version byte. Only used by migrate_*,
+// detected by their exact data length (8 byte discriminator + LEGACY_SPACE).
+
+#[derive(AnchorDeserialize)]
+pub struct LegacyEscrow {
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub amount: u64,
+    pub is_released: bool,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub client_multisig: Option<Pubkey>,
+}
+
+impl LegacyEscrow {
+    pub const LEGACY_SPACE: usize = 32 + 32 + 8 + 1 + 1 + 1 + (1 + 32);
+    // top bit marks ids that were not taken from the sender's escrows_funded counter
+    pub const LEGACY_ESCROW_ID_FLAG: u64 = 1 << 63;
+
+    pub fn upgrade(self, address: &Pubkey) -> Escrow {
+        // the escrow address is unique among live escrows, so it doubles as a receipt id
+        let address_bytes: [u8; 8] = address.to_bytes()[..8].try_into().unwrap();
+
+        Escrow {
+            version: ESCROW_VERSION,
+            sender: self.sender,
+            receiver: self.receiver,
+            amount: self.amount,
+            is_released: self.is_released,
+            bump: self.bump,
+            vault_bump: self.vault_bump,
+            client_multisig: self.client_multisig,
+            escrow_id: u64::from_le_bytes(address_bytes) | Self::LEGACY_ESCROW_ID_FLAG,
+            deadline: None,
+            released_amount: if self.is_released { self.amount } else { 0 },
+            claimed_amount: 0,
+            terms_hash: [0; 32],
+            is_accepted: true,
+            deliverable_hash: [0; 32],
+            review_ends_at: None,
+            is_disputed: false,
+            vesting: None,
+            team_size: 0,
+            team_recipients: [Pubkey::default(); MAX_TEAM_RECIPIENTS],
+            team_bps: [0; MAX_TEAM_RECIPIENTS],
+            team_claimed: [0; MAX_TEAM_RECIPIENTS],
+            is_rejected: false,
+            releasable_at: None,
+            callback_program: None,
+            callback_account_count: 0,
+            callback_accounts: [Pubkey::default(); MAX_CALLBACK_ACCOUNTS],
+            asset: EscrowAsset::Unrecorded,
+            multisig_released: 0,
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub struct LegacyUserStats {
+    pub user: Pubkey,
+    pub completed_escrows: u32,
+    pub bump: u8,
+}
+
+impl LegacyUserStats {
+    pub const LEGACY_SPACE: usize = 32 + 4 + 1;
+
+    pub fn upgrade(self) -> UserStats {
+        UserStats {
+            version: USER_STATS_VERSION,
+            user: self.user,
+            completed_escrows: self.completed_escrows,
+            bump: self.bump,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub struct LegacyFreelancerBadge {
+    pub tier: ReputationTier,
+    pub completed_escrows: u32,
+    pub total_value_completed: u64,
+    pub freelancer: Pubkey,
+    pub bump: u8,
+}
+
+impl LegacyFreelancerBadge {
+    pub const LEGACY_SPACE: usize = 1 + 4 + 8 + 32 + 1;
+
+    pub fn upgrade(self) -> FreelancerBadge {
+        // legacy counters were self-reported, the verified counters start from zero
+        FreelancerBadge {
+            version: FREELANCER_BADGE_VERSION,
+            effective_tier: self.tier.clone(),
+            tier: self.tier,
+            completed_escrows: self.completed_escrows,
+            total_value_completed: self.total_value_completed,
+            freelancer: self.freelancer,
+            bump: self.bump,
+            qualified_escrows: 0,
+            total_value_normalized: 0,
+            distinct_clients: 0,
+            ratings: RatingSummary::default(),
+            last_completed_at: 0,
+            is_stale: false,
+            failed_escrows: 0,
+            failed_value: 0,
+            asset: Pubkey::default(),
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub struct LegacyClientMultisig {
+    pub members: [Pubkey; INLINE_MULTISIG_MEMBERS],
+    pub member_count: u8,
+    pub threshold: u8,
+    pub approvals: [u8; INLINE_MULTISIG_MEMBERS],
+    pub pending_escrow: Pubkey,
+    pub bump: u8,
+}
+
+impl LegacyClientMultisig {
+    pub const LEGACY_SPACE: usize =
+        32 * INLINE_MULTISIG_MEMBERS + 1 + 1 + INLINE_MULTISIG_MEMBERS + 32 + 1;
+
+    pub fn upgrade(self) -> ClientMultisig {
+        ClientMultisig {
+            version: CLIENT_MULTISIG_VERSION,
+            members: self.members,
+            member_count: self.member_count,
+            threshold: self.threshold,
+            approvals: self.approvals,
+            pending_escrow: self.pending_escrow,
+            bump: self.bump,
+            timelock: 0,
+            weights: [1u8; INLINE_MULTISIG_MEMBERS],
+            extra_members: Vec::new(),
+        }
+    }
+}
+
+// implemented by every versioned account, see utils::migrate_account
+pub trait Versioned: AccountSerialize + AccountDeserialize + Discriminator + Space + Sized {
+    const VERSION: u8;
+    const LEGACY_SPACE: usize;
+
+    // `data` is the legacy account data without its discriminator
+    fn from_legacy(data: &[u8], address: &Pubkey) -> Result<Self>;
+    fn set_version(&mut self, version: u8);
+
+    // fills fields appended after `from_version` that can't stay zeroed
+    fn upgrade_from(&mut self, _from_version: u8) {}
+}
+
+impl Versioned for Escrow {
+    const VERSION: u8 = ESCROW_VERSION;
+    const LEGACY_SPACE: usize = LegacyEscrow::LEGACY_SPACE;
+
+    fn from_legacy(mut data: &[u8], address: &Pubkey) -> Result<Self> {
+        Ok(LegacyEscrow::deserialize(&mut data)?.upgrade(address))
+    }
+
+    fn upgrade_from(&mut self, from_version: u8) {
+        // v2 tracks releases in released_amount, a released v1 escrow released everything
+        if from_version == 1 && self.is_released {
+            self.released_amount = self.amount;
+        }
+        // v3 requires an acceptance, escrows funded before the handshake existed keep working
+        if from_version < 3 {
+            self.is_accepted = true;
+        }
+        // before v11 taking back a multisig release cleared every release nothing was claimed from
+        if from_version < 11
+            && self.client_multisig.is_some()
+            && self.is_released
+            && self.claimed_amount == 0
+        {
+            self.multisig_released = self.released_amount;
+        }
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl Versioned for UserStats {
+    const VERSION: u8 = USER_STATS_VERSION;
+    const LEGACY_SPACE: usize = LegacyUserStats::LEGACY_SPACE;
+
+    fn from_legacy(mut data: &[u8], _address: &Pubkey) -> Result<Self> {
+        Ok(LegacyUserStats::deserialize(&mut data)?.upgrade())
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl Versioned for FreelancerBadge {
+    const VERSION: u8 = FREELANCER_BADGE_VERSION;
+    const LEGACY_SPACE: usize = LegacyFreelancerBadge::LEGACY_SPACE;
+
+    fn from_legacy(mut data: &[u8], _address: &Pubkey) -> Result<Self> {
+        Ok(LegacyFreelancerBadge::deserialize(&mut data)?.upgrade())
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl Versioned for ClientMultisig {
+    const VERSION: u8 = CLIENT_MULTISIG_VERSION;
+    const LEGACY_SPACE: usize = LegacyClientMultisig::LEGACY_SPACE;
+
+    fn from_legacy(mut data: &[u8], _address: &Pubkey) -> Result<Self> {
+        Ok(LegacyClientMultisig::deserialize(&mut data)?.upgrade())
+    }
+
+    fn upgrade_from(&mut self, from_version: u8) {
+        // v3 weighs votes, multisigs created before that count every member once
+        if from_version < 3 {
+            self.weights = [1u8; INLINE_MULTISIG_MEMBERS];
+        }
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
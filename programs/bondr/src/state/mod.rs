@@ -1,11 +1,31 @@
+pub mod client_badge;
 pub mod client_multisig;
+pub mod counterparty_record;
 pub mod escrow;
+pub mod escrow_receipt;
 pub mod freelancer_badge;
+pub mod job;
+pub mod legacy;
+pub mod payout_destination;
+pub mod reputation_config;
 pub mod reputation_tier;
+pub mod retainer;
+pub mod review;
+pub mod settlement_callback;
 pub mod user_stats;
 
+pub use client_badge::*;
 pub use client_multisig::*;
+pub use counterparty_record::*;
 pub use escrow::*;
+pub use escrow_receipt::*;
 pub use freelancer_badge::*;
+pub use job::*;
+pub use legacy::*;
+pub use payout_destination::*;
+pub use reputation_config::*;
 pub use reputation_tier::*;
+pub use retainer::*;
+pub use review::*;
+pub use settlement_callback::*;
 pub use user_stats::*;
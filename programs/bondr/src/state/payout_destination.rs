@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, ClientMultisig};
+
+// where a receiver's claims are paid to, set by the receiver with set_payout_destination
+#[account]
+#[derive(InitSpace)]
+pub struct PayoutDestination {
+    pub version: u8, // see PAYOUT_DESTINATION_VERSION
+    pub owner: Pubkey,
+    pub treasury: Pubkey, // SOL lands here, tokens in a token account it owns
+    pub receiver_multisig: Option<Pubkey>, // owner's ClientMultisig, approves every payout when set
+    pub bump: u8,
+}
+
+impl PayoutDestination {
+    // the receiver's destination, None when they never registered one
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, BondrError::InvalidPayoutDestination);
+        Self::try_deserialize(&mut &info.try_borrow_data()?[..]).map(Some)
+    }
+
+    // with a receiver group, `target` (a claimed escrow or retainer, or a destination change)
+    // needs the group's approval. `destination` is this account's address
+    pub fn check_approval(
+        &self,
+        destination: Pubkey,
+        target: Pubkey,
+        approval: Option<&Account<PayoutApproval>>,
+        multisig: Option<&Account<ClientMultisig>>,
+    ) -> Result<()> {
+        let Some(group) = self.receiver_multisig else {
+            return Ok(());
+        };
+        let multisig = multisig.ok_or(BondrError::PayoutNotApproved)?;
+        let approval = approval.ok_or(BondrError::PayoutNotApproved)?;
+        require_keys_eq!(multisig.key(), group, BondrError::NoReceiverMultisig);
+        require!(
+            approval.approves(destination, self.treasury, target, multisig),
+            BondrError::PayoutNotApproved
+        );
+        Ok(())
+    }
+}
+
+// a receiver group's votes on one payout, the target is the escrow or retainer being claimed,
+// the proposed treasury when the destination changes, or the destination itself to drop the
+// group. Closed once used
+#[account]
+#[derive(InitSpace)]
+pub struct PayoutApproval {
+    pub destination: Pubkey,
+    pub target: Pubkey,
+    pub approved: u32, // bitmap over the multisig's member indexes
+    pub bump: u8,
+    pub treasury: Pubkey, // the destination's treasury the votes were cast against
+}
+
+impl PayoutApproval {
+    // `multisig` is the destination's receiver group. Votes only hold while the destination
+    // still pays the treasury they were cast against
+    pub fn approves(
+        &self,
+        destination: Pubkey,
+        treasury: Pubkey,
+        target: Pubkey,
+        multisig: &ClientMultisig,
+    ) -> bool {
+        self.destination == destination
+            && self.treasury == treasury
+            && self.target == target
+            && multisig.bitmap_weight(self.approved) >= multisig.threshold as u16
+    }
+}
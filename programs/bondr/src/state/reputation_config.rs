@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    FreelancerBadge, ReputationTier, UserStats, MAX_PRICED_MINTS, MAX_TIER_NAME_LEN,
+    MAX_TIER_URI_LEN, REPUTATION_TIER_COUNT,
+};
+
+// lamport value of one whole token (10^decimals base units)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct MintPrice {
+    pub mint: Pubkey,
+    pub price: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct TierThresholds {
+    pub min_completed_escrows: u32,
+    pub min_total_value: u64, // normalized (lamports), see ReputationConfig.mint_prices
+    pub min_distinct_counterparties: u32, // clients for freelancer tiers, freelancers for client tiers; 0 = not enforced
+    #[max_len(MAX_TIER_NAME_LEN)]
+    pub name: String,
+    #[max_len(MAX_TIER_URI_LEN)]
+    pub uri: String,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReputationConfig {
+    pub admin: Pubkey,
+    pub tiers: [TierThresholds; REPUTATION_TIER_COUNT], // indexed by tier - 1 -> Verified, Professional, Elite
+    pub bump: u8,
+    pub min_escrow_value: u64, // normalized value a claim needs to count towards a tier
+    #[max_len(MAX_PRICED_MINTS)]
+    pub mint_prices: Vec<MintPrice>, // SPL mints without an entry never count towards a tier
+    pub client_tiers: [TierThresholds; REPUTATION_TIER_COUNT], // same layout as `tiers`, matched against UserStats
+    pub decay_period: i64, // seconds of inactivity per tier step lost, 0 = no decay
+    pub failure_penalty: u32, // qualified escrows cancelled out by each failed escrow
+}
+
+impl ReputationConfig {
+    // the singleton config, None on deployments where the admin never created it
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        Self::try_deserialize(&mut &info.try_borrow_data()?[..]).map(Some)
+    }
+
+    pub fn tier_index(tier: &ReputationTier) -> Option<usize> {
+        match tier {
+            ReputationTier::Unranked => None,
+            ReputationTier::Verified => Some(0),
+            ReputationTier::Professional => Some(1),
+            ReputationTier::Elite => Some(2),
+        }
+    }
+
+    pub fn thresholds(&self, tier: &ReputationTier) -> Option<&TierThresholds> {
+        Self::tier_index(tier).map(|i| &self.tiers[i])
+    }
+
+    pub fn client_thresholds(&self, tier: &ReputationTier) -> Option<&TierThresholds> {
+        Self::tier_index(tier).map(|i| &self.client_tiers[i])
+    }
+
+    // highest tier whose thresholds the badge satisfies after failure penalties, Unranked if none
+    pub fn eligible_tier(&self, badge: &FreelancerBadge) -> ReputationTier {
+        let penalty = badge.failed_escrows.saturating_mul(self.failure_penalty);
+
+        Self::highest_tier(
+            &self.tiers,
+            badge.qualified_escrows.saturating_sub(penalty),
+            badge
+                .total_value_normalized
+                .saturating_sub(badge.failed_value),
+            badge.distinct_clients,
+        )
+    }
+
+    pub fn refresh_badge(&self, badge: &mut FreelancerBadge, now: i64) {
+        let eligible_tier = self.eligible_tier(badge);
+        badge.refresh(now, self.decay_period, eligible_tier);
+    }
+
+    // every threshold of a tier is at least the one of the tier below it
+    pub fn is_ladder_ordered(tiers: &[TierThresholds; REPUTATION_TIER_COUNT]) -> bool {
+        tiers.windows(2).all(|pair| {
+            let (lower, higher) = (&pair[0], &pair[1]);
+            higher.min_completed_escrows >= lower.min_completed_escrows
+                && higher.min_total_value >= lower.min_total_value
+                && higher.min_distinct_counterparties >= lower.min_distinct_counterparties
+        })
+    }
+
+    // cancelled escrows and lost disputes weigh against the client like failed escrows do
+    // against a freelancer
+    pub fn eligible_client_tier(&self, stats: &UserStats) -> ReputationTier {
+        let failures = stats.cancellations.saturating_add(stats.disputes_lost);
+        let penalty = failures.saturating_mul(self.failure_penalty);
+
+        Self::highest_tier(
+            &self.client_tiers,
+            stats.escrows_paid_out.saturating_sub(penalty),
+            stats.total_volume,
+            stats.distinct_freelancers,
+        )
+    }
+
+    fn highest_tier(
+        tiers: &[TierThresholds; REPUTATION_TIER_COUNT],
+        escrows: u32,
+        value: u64,
+        counterparties: u32,
+    ) -> ReputationTier {
+        [
+            ReputationTier::Elite,
+            ReputationTier::Professional,
+            ReputationTier::Verified,
+        ]
+        .into_iter()
+        .find(|tier| {
+            let t = &tiers[Self::tier_index(tier).unwrap()];
+            escrows >= t.min_completed_escrows
+                && value >= t.min_total_value
+                && counterparties >= t.min_distinct_counterparties
+        })
+        .unwrap_or(ReputationTier::Unranked)
+    }
+
+    // lamport-equivalent value of an escrow, mint = None for SOL escrows
+    pub fn normalized_value(&self, mint: Option<(Pubkey, u8)>, amount: u64) -> Option<u64> {
+        let Some((mint, decimals)) = mint else {
+            return Some(amount);
+        };
+
+        let price = self.mint_prices.iter().find(|p| p.mint == mint)?.price;
+        let value = (amount as u128)
+            .checked_mul(price as u128)?
+            .checked_div(10u128.checked_pow(decimals as u32)?)?;
+
+        u64::try_from(value).ok()
+    }
+}
@@ -9,6 +9,18 @@ pub enum ReputationTier {
     Elite = 3,        // 25+ escrows completed
 }
 
+impl ReputationTier {
+    // one tier lower per step, never below Unranked
+    pub fn lowered(&self, steps: u64) -> ReputationTier {
+        match (self.clone() as u64).saturating_sub(steps) {
+            0 => ReputationTier::Unranked,
+            1 => ReputationTier::Verified,
+            2 => ReputationTier::Professional,
+            _ => ReputationTier::Elite,
+        }
+    }
+}
+
 impl Space for ReputationTier {
     const INIT_SPACE: usize = 1;
 }
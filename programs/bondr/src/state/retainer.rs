@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, EscrowAsset, RetainerPeriodsReleased};
+
+// recurring payment agreement, a crank moves each due period from the prefunded balance
+// to the freelancer's claimable balance
+#[account]
+#[derive(InitSpace)]
+pub struct Retainer {
+    pub version: u8, // see RETAINER_VERSION
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub asset: EscrowAsset, // what the retainer is funded with, every transfer must move it
+    pub period_amount: u64,
+    pub period_length: i64, // seconds
+    pub max_periods: u32,
+    pub periods_released: u32,
+    pub next_period_at: i64, // the next period is released once this has passed
+    pub balance: u64,        // funded but not released yet, always whole periods
+    pub released_amount: u64, // released to the freelancer so far
+    pub claimed_amount: u64, // already paid out to the freelancer
+    pub is_paused: bool,
+    pub is_cancelled: bool,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Retainer {
+    pub fn check_asset(&self, is_token_transfer: bool, token_mint: Option<Pubkey>) -> Result<()> {
+        let token_mint = if is_token_transfer { token_mint } else { None };
+        require!(
+            self.asset.matches(token_mint),
+            BondrError::EscrowAssetMismatch
+        );
+        Ok(())
+    }
+
+    // periods that can still be funded
+    pub fn unfunded_periods(&self) -> u64 {
+        let remaining = (self.max_periods - self.periods_released) as u64;
+        remaining - self.balance / self.period_amount
+    }
+
+    // moves every funded period that is due to the claimable balance, returns how many.
+    // Every instruction touching the retainer runs this, the crank only matters when nobody else does
+    pub fn release_due(&mut self, retainer: Pubkey, now: i64) -> u32 {
+        if self.is_paused || self.is_cancelled || now < self.next_period_at {
+            return 0;
+        }
+
+        // computed at once, a short period left alone for long would not fit a loop's compute
+        let elapsed = ((now - self.next_period_at) / self.period_length) as u64 + 1;
+        let funded = self.balance / self.period_amount;
+        let remaining = (self.max_periods - self.periods_released) as u64;
+        let released = elapsed.min(funded).min(remaining) as u32;
+
+        self.balance -= released as u64 * self.period_amount;
+        self.released_amount += released as u64 * self.period_amount;
+        self.periods_released += released;
+        self.next_period_at += released as i64 * self.period_length;
+
+        if released > 0 {
+            emit!(RetainerPeriodsReleased {
+                retainer,
+                periods: released,
+                amount: released as u64 * self.period_amount,
+                periods_released: self.periods_released,
+            });
+        }
+        released
+    }
+
+    // periods that fell due while paused are skipped, not paid later
+    pub fn skip_missed_periods(&mut self, now: i64) {
+        if now >= self.next_period_at {
+            let missed = (now - self.next_period_at) / self.period_length + 1;
+            self.next_period_at += missed * self.period_length;
+        }
+    }
+
+    pub fn claimable(&self) -> u64 {
+        self.released_amount - self.claimed_amount
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.is_cancelled || self.periods_released == self.max_periods
+    }
+
+    // nothing left to release, refund or claim, the retainer can be closed
+    pub fn is_settled(&self) -> bool {
+        self.is_finished() && self.balance == 0 && self.claimable() == 0
+    }
+}
+
+// agreed once at creation, see initialize_retainer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RetainerTerms {
+    pub period_amount: u64,
+    pub period_length: i64,
+    pub max_periods: u32,
+    pub start_at: Option<i64>, // first period is due here, one period from now when None
+}
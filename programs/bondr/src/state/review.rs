@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+// one per receipt and reviewer, its existence is what stops a second review
+#[account]
+#[derive(InitSpace)]
+pub struct Review {
+    pub receipt: Pubkey,
+    pub reviewer: Pubkey,
+    pub reviewee: Pubkey,
+    pub rating: u8,
+    pub content_hash: [u8; 32], // hash of the off-chain review text
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default, Debug)]
+pub struct RatingSummary {
+    pub count: u32,
+    pub sum: u64,
+    pub average: u16, // x100, 450 -> 4.50
+}
+
+impl RatingSummary {
+    pub fn record(&mut self, rating: u8) {
+        self.count += 1;
+        self.sum += rating as u64;
+        self.average = (self.sum * 100 / self.count as u64) as u16;
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::MAX_CALLBACK_ACCOUNTS;
+
+// a program notified when the escrow settles, registered by initialize_escrow or accept_bid
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EscrowCallback {
+    pub program: Pubkey,
+    pub accounts: Vec<Pubkey>, // passed to the callback after the escrow
+}
+
+impl EscrowCallback {
+    pub fn is_valid(&self) -> bool {
+        self.accounts.len() <= MAX_CALLBACK_ACCOUNTS && self.program != crate::ID
+    }
+}
+
+// how an escrow with a settlement callback ended
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettlementOutcome {
+    Paid = 0,      // claim_payment paid out the last of the escrow
+    Cancelled = 1, // cancel_escrow refunded the sender
+    Declined = 2,  // the receiver declined, the sender got everything back
+    Expired = 3,   // reclaim_expired_escrow refunded the sender after the deadline
+}
+
+// instruction data of the callback, after SETTLEMENT_CALLBACK_DISCRIMINATOR.
+// Anchor integrators implement it as `on_bondr_settlement(escrow_id: u64, outcome: u8, amount: u64)`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SettlementCallback {
+    pub escrow_id: u64,
+    pub outcome: SettlementOutcome,
+    pub amount: u64, // paid to the receivers for Paid, refunded to the sender otherwise
+}
@@ -1,9 +1,20 @@
 use anchor_lang::prelude::*;
 
+use crate::RatingSummary;
+
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct UserStats {
+    pub version: u8, // see USER_STATS_VERSION, new fields are appended below
     pub user: Pubkey,
     pub completed_escrows: u32,
     pub bump: u8,
+    // client-side reputation
+    pub escrows_funded: u32,
+    pub escrows_paid_out: u32,
+    pub total_volume: u64, // normalized (lamports), see ReputationConfig.mint_prices
+    pub distinct_freelancers: u32,
+    pub cancellations: u32,
+    pub disputes_lost: u32,     // disputed escrows the client paid out anyway
+    pub ratings: RatingSummary, // reviews received on settled escrows, either side
 }
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{CreateV2CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{Attribute, Attributes, DataState, Plugin, PluginAuthority, PluginAuthorityPair},
+};
+
+use crate::{error::BondrError, FreelancerBadge};
+
+// Mint a badge asset owned by, authorised and paid for by `owner` - NO CPI SIGNING NEEDED
+pub fn create_badge_asset<'info>(
+    mpl_core_program: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    name: String,
+    uri: String,
+    plugins: Vec<PluginAuthorityPair>,
+) -> Result<()> {
+    CreateV2CpiBuilder::new(mpl_core_program)
+        .asset(asset)
+        .collection(None)
+        .authority(Some(owner))
+        .payer(owner)
+        .owner(Some(owner))
+        .system_program(system_program)
+        .data_state(DataState::AccountState)
+        .name(name)
+        .uri(uri)
+        .plugins(plugins)
+        .invoke()?;
+
+    Ok(())
+}
+
+// reliability data shown on freelancer badge assets
+pub fn badge_attributes(badge: &FreelancerBadge) -> Plugin {
+    let attribute = |key: &str, value: String| Attribute {
+        key: key.to_string(),
+        value,
+    };
+
+    Plugin::Attributes(Attributes {
+        attribute_list: vec![
+            attribute("tier", format!("{:?}", badge.tier)),
+            attribute("effective_tier", format!("{:?}", badge.effective_tier)),
+            attribute("failed_escrows", badge.failed_escrows.to_string()),
+            attribute("stale", badge.is_stale.to_string()),
+        ],
+    })
+}
+
+// the badge_authority PDA holds the plugin so the program can keep it current without the owner
+pub fn badge_attributes_plugin(badge: &FreelancerBadge) -> PluginAuthorityPair {
+    let (badge_authority, _) = Pubkey::find_program_address(&[b"badge_authority"], &crate::ID);
+
+    PluginAuthorityPair {
+        plugin: badge_attributes(badge),
+        authority: Some(PluginAuthority::Address {
+            address: badge_authority,
+        }),
+    }
+}
+
+// Rewrite the attributes of the badge's latest asset, badge_authority PDA signs
+#[allow(clippy::too_many_arguments)]
+pub fn update_badge_attributes<'info>(
+    badge: &FreelancerBadge,
+    asset: &Option<UncheckedAccount<'info>>,
+    badge_authority: &Option<UncheckedAccount<'info>>,
+    mpl_core_program: &Option<UncheckedAccount<'info>>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    badge_authority_bump: Option<u8>,
+) -> Result<()> {
+    // asset update is optional, badges without a minted asset only get the on-chain counters
+    let Some(asset) = asset else {
+        return Ok(());
+    };
+    let (Some(badge_authority), Some(mpl_core_program), Some(bump)) =
+        (badge_authority, mpl_core_program, badge_authority_bump)
+    else {
+        return Err(BondrError::MissingBadgeAssetAccounts.into());
+    };
+    require_keys_eq!(asset.key(), badge.asset, BondrError::InvalidBadgeAsset);
+
+    UpdatePluginV1CpiBuilder::new(mpl_core_program)
+        .asset(asset)
+        .collection(None)
+        .payer(payer)
+        .authority(Some(badge_authority))
+        .system_program(system_program)
+        .plugin(badge_attributes(badge))
+        .invoke_signed(&[&[b"badge_authority", &[bump]]])?;
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::BondrError, utils::transfer_sol, Versioned};
+
+// Upgrade a program account to the latest layout of `T` in place, returns the version it came from.
+// Legacy (unversioned) accounts are converted field by field, versioned accounts only miss
+// appended fields and are zero-extended. `payer` tops up rent for the bigger account.
+pub fn migrate_account<'info, T: Versioned>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u8> {
+    // 1. only our own accounts of the right type
+    require_keys_eq!(
+        *account.owner,
+        crate::ID,
+        BondrError::InvalidMigrationAccount
+    );
+    let new_len = 8 + T::INIT_SPACE;
+
+    // 2. decoding the old layout
+    let (mut migrated, from_version) = {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() > 8 && data[..8] == *T::DISCRIMINATOR,
+            BondrError::InvalidMigrationAccount
+        );
+
+        if data.len() == 8 + T::LEGACY_SPACE {
+            (T::from_legacy(&data[8..], account.key)?, 0)
+        } else {
+            // version is always the first field
+            let version = data[8];
+            require!(
+                version < T::VERSION || data.len() < new_len,
+                BondrError::AlreadyMigrated
+            );
+
+            let mut padded = data.to_vec();
+            padded.resize(padded.len().max(new_len), 0);
+            (T::try_deserialize(&mut padded.as_slice())?, version)
+        }
+    };
+    migrated.upgrade_from(from_version);
+    migrated.set_version(T::VERSION);
+
+    // 3. growing the account, payer covers the extra rent
+    if account.data_len() < new_len {
+        let missing_rent = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(account.lamports());
+        if missing_rent > 0 {
+            transfer_sol(payer, account, system_program, missing_rent, None)?;
+        }
+        account.realloc(new_len, true)?;
+    }
+
+    // 4. writing the new layout
+    let mut data = account.try_borrow_mut_data()?;
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    Ok(from_version)
+}
@@ -1,3 +1,9 @@
+pub mod badge_assets;
+pub mod migration;
+pub mod settlement_callback;
 pub mod transfers;
 
+pub use badge_assets::*;
+pub use migration::*;
+pub use settlement_callback::*;
 pub use transfers::*;
@@ -0,0 +1,100 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+use crate::{
+    error::BondrError, Escrow, SettlementCallback, SettlementCallbackInvoked,
+    SettlementCallbackSkipped, SettlementOutcome, SETTLEMENT_CALLBACK_DISCRIMINATOR,
+};
+
+// Notifies the escrow's callback program, if it registered one, before the escrow closes.
+// `remaining_accounts` are the callback program followed by the registered accounts. The escrow
+// PDA is passed first and signs, so the callee can check the call came from Bondr.
+// Only the receiver's claim can skip the callback by leaving the remaining accounts out, so a
+// failing or hostile callback can't hold the receiver's money hostage. Refunds go back to the
+// sender who registered the callback and always notify it.
+pub fn invoke_settlement_callback<'info>(
+    escrow: &Account<'info, Escrow>,
+    remaining_accounts: &[AccountInfo<'info>],
+    escrow_seeds: &[&[u8]],
+    outcome: SettlementOutcome,
+    amount: u64,
+    skippable: bool,
+) -> Result<()> {
+    let Some(program_id) = escrow.callback_program else {
+        return Ok(());
+    };
+
+    // 1. skipped, integrators reconcile from the event
+    if skippable && remaining_accounts.is_empty() {
+        emit!(SettlementCallbackSkipped {
+            escrow: escrow.key(),
+            program: program_id,
+            escrow_id: escrow.escrow_id,
+            outcome,
+            amount,
+        });
+        return Ok(());
+    }
+
+    // 2. the accounts must be exactly the ones registered, in order
+    let (program, accounts) = remaining_accounts
+        .split_first()
+        .ok_or(BondrError::CallbackAccountsMismatch)?;
+    require_keys_eq!(
+        program.key(),
+        program_id,
+        BondrError::CallbackAccountsMismatch
+    );
+    require!(
+        accounts.len() == escrow.callback_accounts().len()
+            && accounts
+                .iter()
+                .zip(escrow.callback_accounts())
+                .all(|(info, key)| info.key() == *key),
+        BondrError::CallbackAccountsMismatch
+    );
+
+    // 3. writability is taken from the transaction, nothing is passed as a signer but the escrow
+    let mut metas = vec![AccountMeta::new_readonly(escrow.key(), true)];
+    metas.extend(accounts.iter().map(|info| match info.is_writable {
+        true => AccountMeta::new(info.key(), false),
+        false => AccountMeta::new_readonly(info.key(), false),
+    }));
+
+    let mut data = SETTLEMENT_CALLBACK_DISCRIMINATOR.to_vec();
+    SettlementCallback {
+        escrow_id: escrow.escrow_id,
+        outcome,
+        amount,
+    }
+    .serialize(&mut data)?;
+
+    // 4. a failing callback fails this transaction, a claim can settle again without it
+    let mut infos = vec![escrow.to_account_info()];
+    infos.extend_from_slice(accounts);
+    infos.push(program.clone());
+    invoke_signed(
+        &Instruction {
+            program_id,
+            accounts: metas,
+            data,
+        },
+        &infos,
+        &[escrow_seeds],
+    )?;
+
+    emit!(SettlementCallbackInvoked {
+        escrow: escrow.key(),
+        program: program_id,
+        escrow_id: escrow.escrow_id,
+        outcome,
+        amount,
+    });
+
+    Ok(())
+}
@@ -28,6 +28,15 @@ pub fn transfer_sol<'info>(
     transfer(cpi_ctx, amount)
 }
 
+// Smallest balance a SOL vault can keep without being emptied, token accounts have no minimum
+pub fn vault_minimum(is_token_transfer: bool) -> Result<u64> {
+    if is_token_transfer {
+        Ok(0)
+    } else {
+        Ok(Rent::get()?.minimum_balance(0))
+    }
+}
+
 // Transfer SPL tokens from one account to another
 pub fn transfer_spl_tokens<'info>(
     from: &Account<'info, TokenAccount>,
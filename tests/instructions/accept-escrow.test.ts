@@ -0,0 +1,220 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    TOKEN_PROGRAM_ID,
+    getAccount,
+    getAssociatedTokenAddressSync,
+    getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+import { createHash } from "crypto";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    acceptEscrow,
+    createFundedMint,
+    deriveEscrowPDAs,
+    deriveUserStatsPDA,
+    expectError,
+    fundEscrow,
+    NO_TERMS,
+} from "../utils/helpers";
+
+describe("accept_escrow() / decline_escrow()", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 2);
+    const termsHash = Array.from(createHash("sha256").update("Landing page, 2 revisions").digest());
+
+    const initEscrow = async (refSeed: number, terms: number[] | null) => {
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
+        await program.methods
+            .initializeEscrow(amount, refSeed, false, false, null, terms, null, null, null)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                senderStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                clientMultisig: null,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: null,
+                senderTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                associatedTokenProgram: null,
+            })
+            .signers([client])
+            .rpc();
+        return { escrowPda, vaultPda };
+    };
+
+    const release = async (refSeed: number, escrowPda: PublicKey, vaultPda: PublicKey) => {
+        await program.methods
+            .releasePayment(refSeed, null)
+            .accountsStrict({
+                client: client.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+            })
+            .signers([client])
+            .rpc();
+    };
+
+    // SPL declines refund into `refundAccount`, the sender's ATA unless given
+    const decline = async (
+        refSeed: number,
+        escrowPda: PublicKey,
+        vaultPda: PublicKey,
+        mint: PublicKey | null = null,
+        refundAccount: PublicKey | null = mint && getAssociatedTokenAddressSync(mint, client.publicKey)
+    ) => {
+        await program.methods
+            .declineEscrow(refSeed, mint !== null)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                multisig: null,
+                escrowTokenAccount: mint && getAssociatedTokenAddressSync(mint, escrowPda, true),
+                senderTokenAccount: refundAccount,
+                tokenMint: mint,
+                tokenProgram: mint && TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 3_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+    });
+
+    describe("accept", () => {
+        const refSeed = 91;
+        let escrowPda: PublicKey;
+        let vaultPda: PublicKey;
+
+        before(async () => {
+            ({ escrowPda, vaultPda } = await initEscrow(refSeed, termsHash));
+        });
+
+        it("stores the sender's terms and starts unaccepted", async () => {
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.deepEqual(Array.from(escrow.termsHash), termsHash);
+            assert.isFalse(escrow.isAccepted);
+        });
+
+        it("fails to release before the receiver accepts", async () => {
+            try {
+                await release(refSeed, escrowPda, vaultPda);
+                assert.fail("Expected EscrowNotAccepted");
+            } catch (err: any) {
+                const msg = err.error?.errorMessage || err.message;
+                assert.strictEqual(msg, "Escrow has not been accepted by the receiver");
+            }
+        });
+
+        it("fails to accept different terms", async () => {
+            try {
+                await acceptEscrow(client.publicKey, freelancer, refSeed, NO_TERMS);
+                assert.fail("Expected TermsMismatch");
+            } catch (err: any) {
+                const msg = err.error?.errorMessage || err.message;
+                assert.strictEqual(msg, "Terms hash doesn't match the escrow terms");
+            }
+        });
+
+        it("accepts the terms and unlocks the release", async () => {
+            await acceptEscrow(client.publicKey, freelancer, refSeed, termsHash);
+
+            let escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.isAccepted);
+
+            await release(refSeed, escrowPda, vaultPda);
+            escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.isReleased);
+        });
+
+        it("fails to accept twice", async () => {
+            try {
+                await acceptEscrow(client.publicKey, freelancer, refSeed, termsHash);
+                assert.fail("Expected AlreadyAccepted");
+            } catch (err: any) {
+                const msg = err.error?.errorMessage || err.message;
+                assert.strictEqual(msg, "Escrow already accepted");
+            }
+        });
+
+        it("fails to decline an accepted escrow", async () => {
+            try {
+                await decline(refSeed, escrowPda, vaultPda);
+                assert.fail("Expected AlreadyAccepted");
+            } catch (err: any) {
+                const msg = err.error?.errorMessage || err.message;
+                assert.strictEqual(msg, "Escrow already accepted");
+            }
+        });
+    });
+
+    describe("decline", () => {
+        const refSeed = 92;
+
+        it("refunds the sender and closes the escrow", async () => {
+            const { escrowPda, vaultPda } = await initEscrow(refSeed, null);
+            const clientBefore = await connection.getBalance(client.publicKey);
+
+            await decline(refSeed, escrowPda, vaultPda);
+
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            assert.strictEqual(await connection.getBalance(vaultPda), 0);
+
+            // refund plus the escrow rent, the receiver paid the fee
+            const clientAfter = await connection.getBalance(client.publicKey);
+            assert.isAbove(clientAfter - clientBefore, amount.toNumber());
+        });
+
+        describe("SPL", () => {
+            const splSeed = 93;
+            let mint: PublicKey;
+            let escrowPda: PublicKey;
+            let vaultPda: PublicKey;
+
+            before(async () => {
+                mint = await createFundedMint(client, amount);
+                ({ escrowPda, vaultPda } = await fundEscrow(client, freelancer.publicKey, splSeed, amount, { mint }));
+            });
+
+            it("fails to refund into the receiver's token account", async () => {
+                const receiverAta = await getOrCreateAssociatedTokenAccount(
+                    connection,
+                    freelancer,
+                    mint,
+                    freelancer.publicKey
+                );
+                await expectError(
+                    () => decline(splSeed, escrowPda, vaultPda, mint, receiverAta.address),
+                    "A token owner constraint was violated"
+                );
+            });
+
+            it("refunds the tokens to the sender", async () => {
+                await decline(splSeed, escrowPda, vaultPda, mint);
+
+                assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+                const senderAta = await getAccount(connection, getAssociatedTokenAddressSync(mint, client.publicKey));
+                assert.strictEqual(Number(senderAta.amount), amount.toNumber());
+            });
+        });
+    });
+});
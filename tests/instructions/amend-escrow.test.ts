@@ -0,0 +1,173 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import { deriveEscrowPDAs, deriveUserStatsPDA } from "../utils/helpers";
+
+describe("amend_escrow()", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let newFreelancer: Keypair;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+
+    const refSeed = 81;
+    const newRefSeed = 82;
+    const amount = new anchor.BN(LAMPORTS_PER_SOL);
+
+    const amendment = (overrides: Record<string, unknown>) => ({
+        amount: null,
+        deadline: null,
+        removeDeadline: false,
+        termsHash: null,
+        receiver: null,
+        newReferenceSeed: 0,
+        ...overrides,
+    });
+
+    const amend = async (
+        changes: ReturnType<typeof amendment>,
+        receiver: Keypair = freelancer,
+        moveTo?: { escrow: PublicKey; vault: PublicKey }
+    ) => {
+        await program.methods
+            .amendEscrow(refSeed, false, changes as any)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: receiver.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                multisig: null,
+                newEscrow: moveTo ? moveTo.escrow : null,
+                newVault: moveTo ? moveTo.vault : null,
+                escrowTokenAccount: null,
+                senderTokenAccount: null,
+                newEscrowTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                associatedTokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client, receiver])
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+        newFreelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 3_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+            connection.requestAirdrop(newFreelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed));
+
+        await program.methods
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                senderStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                clientMultisig: null,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: null,
+                senderTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                associatedTokenProgram: null,
+            })
+            .signers([client])
+            .rpc();
+    });
+
+    it("lowers the amount and refunds the difference", async () => {
+        const newAmount = new anchor.BN(LAMPORTS_PER_SOL * 0.6);
+        const vaultBefore = await connection.getBalance(vaultPda);
+
+        await amend(amendment({ amount: newAmount }));
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.strictEqual(escrow.amount.toNumber(), newAmount.toNumber());
+        assert.strictEqual(
+            vaultBefore - (await connection.getBalance(vaultPda)),
+            amount.sub(newAmount).toNumber()
+        );
+    });
+
+    it("fails to raise the amount", async () => {
+        try {
+            await amend(amendment({ amount }));
+            assert.fail("Expected InvalidAmount");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Invalid Amount");
+        }
+    });
+
+    it("sets and removes the deadline", async () => {
+        const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+        await amend(amendment({ deadline }));
+        let escrow = await program.account.escrow.fetch(escrowPda);
+        assert.strictEqual(escrow.deadline!.toNumber(), deadline.toNumber());
+
+        await amend(amendment({ removeDeadline: true }));
+        escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isNull(escrow.deadline);
+    });
+
+    it("fails with a deadline in the past", async () => {
+        try {
+            await amend(amendment({ deadline: new anchor.BN(1) }));
+            assert.fail("Expected InvalidDeadline");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Deadline must be in the future");
+        }
+    });
+
+    it("fails without the receiver of the escrow", async () => {
+        try {
+            await amend(amendment({ removeDeadline: true }), newFreelancer);
+            assert.fail("Expected seeds constraint failure");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.match(msg, /(seeds|constraint)/i);
+        }
+    });
+
+    it("moves the escrow to a new receiver", async () => {
+        const { escrowPda: newEscrowPda, vaultPda: newVaultPda } = await deriveEscrowPDAs(
+            client.publicKey,
+            newFreelancer.publicKey,
+            newRefSeed
+        );
+        const before = await program.account.escrow.fetch(escrowPda);
+        const locked = await connection.getBalance(vaultPda);
+
+        await amend(
+            amendment({ receiver: newFreelancer.publicKey, newReferenceSeed: newRefSeed }),
+            freelancer,
+            { escrow: newEscrowPda, vault: newVaultPda }
+        );
+
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        assert.strictEqual(await connection.getBalance(vaultPda), 0);
+        assert.strictEqual(await connection.getBalance(newVaultPda), locked);
+
+        const moved = await program.account.escrow.fetch(newEscrowPda);
+        assert.strictEqual(moved.receiver.toBase58(), newFreelancer.publicKey.toBase58());
+        assert.strictEqual(moved.amount.toNumber(), before.amount.toNumber());
+        assert.strictEqual(moved.escrowId.toNumber(), before.escrowId.toNumber());
+
+        // the new receiver hasn't agreed to anything yet
+        assert.isFalse(moved.isAccepted);
+    });
+});
@@ -4,7 +4,7 @@ import { assert } from "chai";
 import { describe, it, before } from "mocha";
 
 import { program, createFundedKeypair } from "../utils/setup";
-import { deriveEscrowPDAs } from "../utils/helpers";
+import { acceptEscrow, deriveEscrowPDAs } from "../utils/helpers";
 
 describe("approve_multisig_release()", () => {
     const refSeed = 55;
@@ -60,7 +60,7 @@ describe("approve_multisig_release()", () => {
         const threshold = 3;
 
         await program.methods
-            .initializeMultisigClient(members, memberCount, threshold)
+            .initializeMultisigClient(members, memberCount, threshold, null, null)
             .accountsPartial({
                 client: client.publicKey,
                 multisig: multisigPda,
@@ -71,7 +71,7 @@ describe("approve_multisig_release()", () => {
 
         // Initialize escrow with multisig
         await program.methods
-            .initializeEscrow(amount, refSeed, false, true)
+            .initializeEscrow(amount, refSeed, false, true, null, null, null, null, null)
             .accountsPartial({
                 sender: client.publicKey,
                 receiver: freelancer.publicKey,
@@ -88,6 +88,28 @@ describe("approve_multisig_release()", () => {
             })
             .signers([client])
             .rpc();
+
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+    });
+
+    // runs before the approvals below reach the threshold
+    describe("Direct release", () => {
+        it("member tries to release directly", async () => {
+            try {
+                await program.methods
+                    .releasePayment(refSeed, null)
+                    .accountsStrict({ client: client.publicKey, escrow: escrowPda, vault: vaultPda })
+                    .signers([client])
+                    .rpc();
+                assert.fail("instruction should have thrown MultisigReleaseRequired");
+            } catch (err: any) {
+                const errorMessage = err.error?.errorMessage || err.message;
+                assert.strictEqual(errorMessage, "Multisig escrows are released through member approvals");
+            }
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isFalse(escrow.isReleased, "escrow must stay locked");
+        });
     });
 
     describe("Positive Tests", () => {
@@ -0,0 +1,186 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import { acceptEscrow, deriveEscrowPDAs, ensureReputationConfig, expectError, fundEscrow } from "../utils/helpers";
+
+describe("bounty escrows / assign_receiver()", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let configPda: PublicKey;
+
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 2);
+
+    // bounties are funded against the default key until a receiver is assigned
+    const initBounty = async (refSeed: number, deadline: anchor.BN | null = null) => {
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, PublicKey.default, refSeed);
+        await fundEscrow(client, PublicKey.default, refSeed, amount, { deadline });
+        return { escrowPda, vaultPda };
+    };
+
+    const assign = async (refSeed: number, newRefSeed: number, receiver: PublicKey, signer: Keypair = client) => {
+        const bounty = await deriveEscrowPDAs(client.publicKey, PublicKey.default, refSeed);
+        const moved = await deriveEscrowPDAs(client.publicKey, receiver, newRefSeed);
+        await program.methods
+            .assignReceiver(refSeed, newRefSeed, false)
+            .accountsPartial({
+                sender: signer.publicKey,
+                escrow: bounty.escrowPda,
+                vault: bounty.vaultPda,
+                receiver,
+                multisig: null,
+                newEscrow: moved.escrowPda,
+                newVault: moved.vaultPda,
+                escrowTokenAccount: null,
+                newEscrowTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                associatedTokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+        return moved;
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 3_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        configPda = await ensureReputationConfig();
+    });
+
+    describe("assign", () => {
+        const refSeed = 121;
+        const newRefSeed = 122;
+
+        it("funds a bounty without a receiver", async () => {
+            const { escrowPda, vaultPda } = await initBounty(refSeed);
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.receiver.equals(PublicKey.default));
+            assert.strictEqual(escrow.amount.toString(), amount.toString());
+            assert.isAtLeast(await connection.getBalance(vaultPda), amount.toNumber());
+        });
+
+        it("fails to assign the client as receiver", async () => {
+            await expectError(() => assign(refSeed, newRefSeed, client.publicKey), "Can't send money to self");
+        });
+
+        it("fails to assign from someone other than the client", async () => {
+            await expectError(
+                () => assign(refSeed, newRefSeed, freelancer.publicKey, freelancer),
+                "A seeds constraint was violated"
+            );
+        });
+
+        it("moves the funds to the assigned receiver's escrow", async () => {
+            const bounty = await deriveEscrowPDAs(client.publicKey, PublicKey.default, refSeed);
+            const bountyEscrow = await program.account.escrow.fetch(bounty.escrowPda);
+
+            const { escrowPda, vaultPda } = await assign(refSeed, newRefSeed, freelancer.publicKey);
+
+            assert.isNull(await program.account.escrow.fetchNullable(bounty.escrowPda));
+            assert.strictEqual(await connection.getBalance(bounty.vaultPda), 0);
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.receiver.equals(freelancer.publicKey));
+            assert.strictEqual(escrow.amount.toString(), amount.toString());
+            assert.strictEqual(escrow.escrowId.toString(), bountyEscrow.escrowId.toString());
+            assert.isFalse(escrow.isAccepted);
+            assert.strictEqual(await connection.getBalance(vaultPda), amount.toNumber());
+        });
+
+        it("lets the assigned receiver accept and get paid", async () => {
+            const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, newRefSeed);
+            await acceptEscrow(client.publicKey, freelancer, newRefSeed);
+
+            await program.methods
+                .releasePayment(newRefSeed, null)
+                .accountsStrict({
+                    client: client.publicKey,
+                    escrow: escrowPda,
+                    vault: vaultPda,
+                })
+                .signers([client])
+                .rpc();
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.isReleased);
+        });
+
+        it("fails to assign an escrow that already has a receiver", async () => {
+            const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, newRefSeed);
+            const other = Keypair.generate().publicKey;
+            const moved = await deriveEscrowPDAs(client.publicKey, other, 123);
+
+            await expectError(
+                () =>
+                    program.methods
+                        .assignReceiver(newRefSeed, 123, false)
+                        .accountsPartial({
+                            sender: client.publicKey,
+                            escrow: escrowPda,
+                            vault: vaultPda,
+                            receiver: other,
+                            multisig: null,
+                            newEscrow: moved.escrowPda,
+                            newVault: moved.vaultPda,
+                            escrowTokenAccount: null,
+                            newEscrowTokenAccount: null,
+                            tokenMint: null,
+                            tokenProgram: null,
+                            associatedTokenProgram: null,
+                            systemProgram: SystemProgram.programId,
+                        })
+                        .signers([client])
+                        .rpc(),
+                "Escrow already has a receiver"
+            );
+        });
+    });
+
+    describe("refund", () => {
+        const refSeed = 124;
+
+        it("refunds an unassigned bounty once its deadline passes", async () => {
+            const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 2);
+            const { escrowPda, vaultPda } = await initBounty(refSeed, deadline);
+            await sleep(4000);
+
+            const clientBefore = await connection.getBalance(client.publicKey);
+            await program.methods
+                .reclaimExpiredEscrow(refSeed, false)
+                .accountsPartial({
+                    client: client.publicKey,
+                    escrow: escrowPda,
+                    vault: vaultPda,
+                    badge: null,
+                    reputationConfig: configPda,
+                    multisig: null,
+                    asset: null,
+                    badgeAuthority: null,
+                    mplCoreProgram: null,
+                    escrowTokenAccount: null,
+                    clientTokenAccount: null,
+                    tokenMint: null,
+                    tokenProgram: null,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([client])
+                .rpc();
+
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            const clientAfter = await connection.getBalance(client.publicKey);
+            assert.isAbove(clientAfter - clientBefore, amount.toNumber() - 10_000);
+        });
+    });
+});
@@ -0,0 +1,117 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import { acceptEscrow, deriveEscrowPDAs, deriveUserStatsPDA } from "../utils/helpers";
+
+describe("cancel_escrow()", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let clientStatsPda: PublicKey;
+
+    const amount = new anchor.BN(500_000_000);
+
+    const initEscrow = async (refSeed: number) => {
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
+
+        await program.methods
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                senderStats: clientStatsPda,
+                clientMultisig: null,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: null,
+                senderTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                associatedTokenProgram: null,
+            })
+            .signers([client])
+            .rpc();
+
+        return { escrowPda, vaultPda };
+    };
+
+    const cancel = async (signer: Keypair, refSeed: number, escrowPda: PublicKey, vaultPda: PublicKey) => {
+        await program.methods
+            .cancelEscrow(refSeed, false)
+            .accountsPartial({
+                client: signer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                clientStats: (await deriveUserStatsPDA(signer.publicKey)).statsPda,
+                multisig: null,
+                escrowTokenAccount: null,
+                clientTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 3_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        ({ statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey));
+    });
+
+    it("refunds the client and records a cancellation", async () => {
+        const refSeed = 10;
+        const { escrowPda, vaultPda } = await initEscrow(refSeed);
+
+        await cancel(client, refSeed, escrowPda, vaultPda);
+
+        const vaultBalance = await connection.getBalance(vaultPda);
+        assert.strictEqual(vaultBalance, 0);
+
+        const escrowInfo = await connection.getAccountInfo(escrowPda);
+        assert.isNull(escrowInfo, "escrow should be closed to the client");
+
+        const stats = await program.account.userStats.fetch(clientStatsPda);
+        assert.strictEqual(stats.escrowsFunded, 1);
+        assert.strictEqual(stats.cancellations, 1);
+        assert.strictEqual(stats.escrowsPaidOut, 0);
+    });
+
+    it("fails once the receiver accepted", async () => {
+        const refSeed = 11;
+        const { escrowPda, vaultPda } = await initEscrow(refSeed);
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+
+        try {
+            await cancel(client, refSeed, escrowPda, vaultPda);
+            assert.fail("Expected AlreadyAccepted");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Escrow already accepted");
+        }
+    });
+
+    it("fails when someone other than the sender cancels", async () => {
+        const refSeed = 12;
+        const { escrowPda, vaultPda } = await initEscrow(refSeed);
+
+        try {
+            await cancel(freelancer, refSeed, escrowPda, vaultPda);
+            assert.fail("Expected cancel by freelancer to fail");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.match(msg, /(seeds|constraint|not initialized|Unauthorised)/i);
+        }
+    });
+});
@@ -4,7 +4,13 @@ import { assert } from "chai";
 import { describe, it } from "mocha";
 
 import { program, connection, sleep } from "../utils/setup";
-import { deriveEscrowPDAs, deriveUserStatsPDA } from "../utils/helpers";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+} from "../utils/helpers";
 
 describe("claim_payment()", () => {
     let client: Keypair;
@@ -13,6 +19,7 @@ describe("claim_payment()", () => {
     let escrowBump: number;
     let vaultPda: PublicKey;
     let vaultBump: number;
+    let configPda: PublicKey;
 
     const refSeed = 77;
     const amount = new anchor.BN(2_000_000);
@@ -28,6 +35,7 @@ describe("claim_payment()", () => {
             connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
         ]);
         await sleep(3000);
+        configPda = await ensureReputationConfig();
 
         // 3. Derive PDAs using helpers
         const escrowPDAs = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
@@ -40,7 +48,7 @@ describe("claim_payment()", () => {
 
         // 4. Initialize escrow
         await program.methods
-            .initializeEscrow(amount, refSeed, false, false)
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
             .accountsPartial({
                 sender: client.publicKey,
                 receiver: freelancer.publicKey,
@@ -58,17 +66,53 @@ describe("claim_payment()", () => {
             .signers([client])
             .rpc();
 
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+
         // 5. Release the escrow
         await program.methods
-            .releasePayment(refSeed)
+            .releasePayment(refSeed, null)
             .accountsStrict({
                 client: client.publicKey,
                 escrow: escrowPda,
+                vault: vaultPda,
             })
             .signers([client])
             .rpc();
     });
 
+    it("fails without the counterparty record once the reputation config exists", async () => {
+        try {
+            await program.methods
+                .claimPayment(refSeed, false)
+                .accountsPartial({
+                    client: client.publicKey,
+                    freelancer: freelancer.publicKey,
+                    escrow: escrowPda,
+                    vault: vaultPda,
+                    receiverStats: (await deriveUserStatsPDA(freelancer.publicKey)).statsPda,
+                    clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                    badge: null,
+                    counterparty: null,
+                    reputationConfig: configPda,
+                    multisig: null,
+                    payoutApproval: null,
+                    payoutMultisig: null,
+                    receiverSol: freelancer.publicKey,
+                    escrowTokenAccount: null,
+                    receiverTokenAccount: null,
+                    tokenMint: null,
+                    tokenProgram: null,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([freelancer])
+                .rpc();
+            assert.fail("Expected MissingCounterpartyRecord");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Counterparty record is required once the reputation config exists");
+        }
+    });
+
     it("claims payment successfully", async () => {
         const { statsPda: freelancerStatsPda, statsBump: freelancerStatsBump } = await deriveUserStatsPDA(freelancer.publicKey);
 
@@ -80,7 +124,13 @@ describe("claim_payment()", () => {
                 escrow: escrowPda,
                 vault: vaultPda,
                 receiverStats: freelancerStatsPda,
+                clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey),
+                reputationConfig: configPda,
                 multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
                 receiverSol: freelancer.publicKey,
                 escrowTokenAccount: null,
                 receiverTokenAccount: null,
@@ -93,6 +143,16 @@ describe("claim_payment()", () => {
 
         const stats = await program.account.userStats.fetch(freelancerStatsPda);
         assert.strictEqual(stats.completedEscrows, 1);
+
+        // client side of the same escrow
+        const clientStats = await program.account.userStats.fetch(
+            (await deriveUserStatsPDA(client.publicKey)).statsPda
+        );
+        assert.strictEqual(clientStats.escrowsFunded, 1);
+        assert.strictEqual(clientStats.escrowsPaidOut, 1);
+        assert.strictEqual(clientStats.completedEscrows, 1);
+        assert.strictEqual(clientStats.distinctFreelancers, 1);
+        assert.strictEqual(clientStats.totalVolume.toNumber(), amount.toNumber());
     });
 
     it("fails if payment not released", async () => {
@@ -116,7 +176,7 @@ describe("claim_payment()", () => {
         const { statsPda: newClientStatsPda } = await deriveUserStatsPDA(newClient.publicKey);
 
         await program.methods
-            .initializeEscrow(newAmount, newRefSeed, false, false)
+            .initializeEscrow(newAmount, newRefSeed, false, false, null, null, null, null, null)
             .accountsPartial({
                 sender: newClient.publicKey,
                 receiver: newFreelancer.publicKey,
@@ -134,6 +194,8 @@ describe("claim_payment()", () => {
             .signers([newClient])
             .rpc();
 
+        await acceptEscrow(newClient.publicKey, newFreelancer, newRefSeed);
+
         const { statsPda: freelancerStatsPda, statsBump: freelancerStatsBump } = await deriveUserStatsPDA(newFreelancer.publicKey);
 
         try {
@@ -145,7 +207,13 @@ describe("claim_payment()", () => {
                     escrow: newEscrowPda,
                     vault: newVaultPda,
                     receiverStats: freelancerStatsPda,
+                    clientStats: (await deriveUserStatsPDA(newClient.publicKey)).statsPda,
+                    badge: null,
+                    counterparty: await deriveCounterpartyPDA(newFreelancer.publicKey, newClient.publicKey),
+                    reputationConfig: configPda,
                     multisig: null,
+                    payoutApproval: null,
+                    payoutMultisig: null,
                     receiverSol: newFreelancer.publicKey,
                     escrowTokenAccount: null,
                     receiverTokenAccount: null,
@@ -180,7 +248,13 @@ describe("claim_payment()", () => {
                     escrow: wrongEscrowPda,
                     vault: wrongVaultPda,
                     receiverStats: clientStatsPda,
+                    clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                    badge: null,
+                    counterparty: await deriveCounterpartyPDA(client.publicKey, client.publicKey),
+                    reputationConfig: configPda,
                     multisig: null,
+                    payoutApproval: null,
+                    payoutMultisig: null,
                     receiverSol: client.publicKey,
                     escrowTokenAccount: null,
                     receiverTokenAccount: null,
@@ -220,7 +294,7 @@ describe("claim_payment()", () => {
 
         // Initialize multisig client
         await program.methods
-            .initializeMultisigClient([multisigClient.publicKey, member1.publicKey, member2.publicKey, member3.publicKey, new PublicKey("11111111111111111111111111111111")], 3, 2)
+            .initializeMultisigClient([multisigClient.publicKey, member1.publicKey, member2.publicKey, member3.publicKey, new PublicKey("11111111111111111111111111111111")], 3, 2, null, null)
             .accountsStrict({
                 client: multisigClient.publicKey,
                 multisig: multisigPda,
@@ -234,7 +308,7 @@ describe("claim_payment()", () => {
 
         // Initialize escrow with multisig
         await program.methods
-            .initializeEscrow(amount, refSeed, false, true)
+            .initializeEscrow(amount, refSeed, false, true, null, null, null, null, null)
             .accountsPartial({
                 sender: multisigClient.publicKey,
                 receiver: freelancer.publicKey,
@@ -252,6 +326,8 @@ describe("claim_payment()", () => {
             .signers([multisigClient])
             .rpc();
 
+        await acceptEscrow(multisigClient.publicKey, freelancer, refSeed);
+
         // Approve with only 1 member (threshold is 2)
         await program.methods
             .approveMultisigRelease(refSeed)
@@ -266,9 +342,8 @@ describe("claim_payment()", () => {
 
         const { statsPda: receiverStatsPda, statsBump: receiverStatsBump } = await deriveUserStatsPDA(freelancer.publicKey);
 
-        // Try claim without enough approvals
-        try {
-            await program.methods
+        const claimWith = async (multisig: PublicKey | null) =>
+            program.methods
                 .claimPayment(refSeed, false)
                 .accountsPartial({
                     client: multisigClient.publicKey,
@@ -276,7 +351,13 @@ describe("claim_payment()", () => {
                     escrow: multisigEscrowPDAs.escrowPda,
                     vault: multisigEscrowPDAs.vaultPda,
                     receiverStats: receiverStatsPda,
-                    multisig: multisigPda,
+                    clientStats: (await deriveUserStatsPDA(multisigClient.publicKey)).statsPda,
+                    badge: null,
+                    counterparty: await deriveCounterpartyPDA(freelancer.publicKey, multisigClient.publicKey),
+                    reputationConfig: configPda,
+                    multisig,
+                    payoutApproval: null,
+                    payoutMultisig: null,
                     receiverSol: freelancer.publicKey,
                     escrowTokenAccount: null,
                     receiverTokenAccount: null,
@@ -286,11 +367,24 @@ describe("claim_payment()", () => {
                 })
                 .signers([freelancer])
                 .rpc();
+
+        // Try claim without enough approvals
+        try {
+            await claimWith(multisigPda);
             assert.fail("Expected MultisigThresholdNotMet");
         } catch (err: any) {
             const code = err.error?.errorCode?.code;
             assert.strictEqual(code, "MultisigThresholdNotMet");
         }
+
+        // leaving the multisig out doesn't skip the threshold
+        try {
+            await claimWith(null);
+            assert.fail("Expected MultisigMismatch");
+        } catch (err: any) {
+            const code = err.error?.errorCode?.code;
+            assert.strictEqual(code, "MultisigMismatch");
+        }
     });
 
     it("fails to claim payment if multisig pending escrow does not match", async () => {
@@ -321,7 +415,7 @@ describe("claim_payment()", () => {
                 member2.publicKey,
                 member3.publicKey,
                 new PublicKey("11111111111111111111111111111111"),
-            ], 3, 2)
+            ], 3, 2, null, null)
             .accountsStrict({
                 client: multisigClient.publicKey,
                 multisig: multisigPda,
@@ -335,7 +429,7 @@ describe("claim_payment()", () => {
         const msEscrowPDAs = await deriveEscrowPDAs(multisigClient.publicKey, freelancer.publicKey, msRefSeed);
 
         await program.methods
-            .initializeEscrow(amount, msRefSeed, false, true)
+            .initializeEscrow(amount, msRefSeed, false, true, null, null, null, null, null)
             .accountsPartial({
                 sender: multisigClient.publicKey,
                 receiver: freelancer.publicKey,
@@ -353,13 +447,15 @@ describe("claim_payment()", () => {
             .signers([multisigClient])
             .rpc();
 
+        await acceptEscrow(multisigClient.publicKey, freelancer, msRefSeed);
+
         // 2) Initialize a second NON-multisig escrow for same client
         const nonMsRefSeed = 99;
         const nonMsEscrowPDAs = await deriveEscrowPDAs(multisigClient.publicKey, freelancer.publicKey, nonMsRefSeed);
         const { statsPda: clientStatsPda } = await deriveUserStatsPDA(multisigClient.publicKey);
 
         await program.methods
-            .initializeEscrow(amount, nonMsRefSeed, false, false)
+            .initializeEscrow(amount, nonMsRefSeed, false, false, null, null, null, null, null)
             .accountsPartial({
                 sender: multisigClient.publicKey,
                 receiver: freelancer.publicKey,
@@ -377,6 +473,8 @@ describe("claim_payment()", () => {
             .signers([multisigClient])
             .rpc();
 
+        await acceptEscrow(multisigClient.publicKey, freelancer, nonMsRefSeed);
+
         const { statsPda: receiverStatsPda } = await deriveUserStatsPDA(freelancer.publicKey);
 
         // 3) Try to claim the NON-multisig escrow while providing multisig -> should mismatch pending_escrow (which points to msEscrow)
@@ -389,7 +487,13 @@ describe("claim_payment()", () => {
                     escrow: nonMsEscrowPDAs.escrowPda,
                     vault: nonMsEscrowPDAs.vaultPda,
                     receiverStats: receiverStatsPda,
+                    clientStats: (await deriveUserStatsPDA(multisigClient.publicKey)).statsPda,
+                    badge: null,
+                    counterparty: await deriveCounterpartyPDA(freelancer.publicKey, multisigClient.publicKey),
+                    reputationConfig: configPda,
                     multisig: multisigPda,
+                    payoutApproval: null,
+                    payoutMultisig: null,
                     receiverSol: freelancer.publicKey,
                     escrowTokenAccount: null,
                     receiverTokenAccount: null,
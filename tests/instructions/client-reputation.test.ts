@@ -0,0 +1,247 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    deriveClientBadgePDA,
+    deriveEscrowPDAs,
+    deriveFreelancerBadgePDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    completeEscrows,
+} from "../utils/helpers";
+
+describe("client reputation", () => {
+    let client: Keypair;
+    let freelancerA: Keypair;
+    let freelancerB: Keypair;
+    let clientBadgePda: PublicKey;
+    let clientStatsPda: PublicKey;
+    let configPda: PublicKey;
+
+    const amount = new anchor.BN(20_000_000);
+
+    const mintClientNft = async (signer: Keypair, badge: PublicKey, stats: PublicKey) => {
+        const asset = Keypair.generate();
+        await program.methods
+            .mintClientReputationNft()
+            .accountsStrict({
+                client: signer.publicKey,
+                badge,
+                clientStats: stats,
+                config: configPda,
+                asset: asset.publicKey,
+                mplCoreProgram: MPL_CORE_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer, asset])
+            .rpc();
+    };
+
+    // completeEscrows records every claim on the freelancer badge
+    const initFreelancerBadge = async (freelancer: Keypair) => {
+        const { badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey);
+        await program.methods
+            .initializeFreelancerBadge()
+            .accountsStrict({
+                freelancer: freelancer.publicKey,
+                badge: badgePda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancerA = Keypair.generate();
+        freelancerB = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 2_000_000_000),
+            connection.requestAirdrop(freelancerA.publicKey, 1_000_000_000),
+            connection.requestAirdrop(freelancerB.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        await initFreelancerBadge(freelancerA);
+        await initFreelancerBadge(freelancerB);
+        configPda = await ensureReputationConfig();
+        ({ clientBadgePda } = await deriveClientBadgePDA(client.publicKey));
+        ({ statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey));
+
+        await program.methods
+            .initializeClientBadge()
+            .accountsStrict({
+                client: client.publicKey,
+                badge: clientBadgePda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+    });
+
+    it("initializes an unranked client badge", async () => {
+        const badge = await program.account.clientBadge.fetch(clientBadgePda);
+        assert.strictEqual(badge.client.toBase58(), client.publicKey.toBase58());
+        assert.deepStrictEqual(badge.tier, { unranked: {} });
+    });
+
+    it("tracks funded and paid out escrows across freelancers", async () => {
+        await completeEscrows(client, freelancerA, 2, amount);
+        await completeEscrows(client, freelancerB, 1, amount);
+
+        const stats = await program.account.userStats.fetch(clientStatsPda);
+        assert.strictEqual(stats.escrowsFunded, 3);
+        assert.strictEqual(stats.escrowsPaidOut, 3);
+        assert.strictEqual(stats.completedEscrows, 3);
+        assert.strictEqual(stats.distinctFreelancers, 2);
+        assert.strictEqual(stats.totalVolume.toNumber(), amount.toNumber() * 3);
+        assert.strictEqual(stats.cancellations, 0);
+    });
+
+    it("mints the Verified client NFT once the tier is reached", async () => {
+        await mintClientNft(client, clientBadgePda, clientStatsPda);
+
+        const badge = await program.account.clientBadge.fetch(clientBadgePda);
+        assert.deepStrictEqual(badge.tier, { verified: {} });
+    });
+
+    it("fails to mint the same client tier twice", async () => {
+        try {
+            await mintClientNft(client, clientBadgePda, clientStatsPda);
+            assert.fail("Expected NFTAlreadyMinted");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "NFT for this tier already minted");
+        }
+    });
+
+    it("fails to mint without paid out escrows", async () => {
+        const newClient = Keypair.generate();
+        const freelancer = Keypair.generate();
+        await Promise.all([
+            connection.requestAirdrop(newClient.publicKey, 1_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        // a single paid out escrow stays below the Verified client tier
+        await initFreelancerBadge(freelancer);
+        await completeEscrows(newClient, freelancer, 1, amount);
+
+        const { clientBadgePda: newBadgePda } = await deriveClientBadgePDA(newClient.publicKey);
+        const { statsPda: newStatsPda } = await deriveUserStatsPDA(newClient.publicKey);
+
+        await program.methods
+            .initializeClientBadge()
+            .accountsStrict({
+                client: newClient.publicKey,
+                badge: newBadgePda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([newClient])
+            .rpc();
+
+        try {
+            await mintClientNft(newClient, newBadgePda, newStatsPda);
+            assert.fail("Expected InsufficientEscrows");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Insufficient escrows completed for NFT minting");
+        }
+    });
+
+    it("counts cancellations against the client tier", async () => {
+        const newClient = Keypair.generate();
+        const freelancer = Keypair.generate();
+        await Promise.all([
+            connection.requestAirdrop(newClient.publicKey, 1_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        // enough paid out escrows for Verified, then one cancelled escrow
+        await initFreelancerBadge(freelancer);
+        await completeEscrows(newClient, freelancer, 3, amount);
+
+        const refSeed = 9;
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(newClient.publicKey, freelancer.publicKey, refSeed);
+        const { statsPda: newStatsPda } = await deriveUserStatsPDA(newClient.publicKey);
+        await program.methods
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
+            .accountsPartial({
+                sender: newClient.publicKey,
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                senderStats: newStatsPda,
+                clientMultisig: null,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: null,
+                senderTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                associatedTokenProgram: null,
+            })
+            .signers([newClient])
+            .rpc();
+        await program.methods
+            .cancelEscrow(refSeed, false)
+            .accountsPartial({
+                client: newClient.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                clientStats: newStatsPda,
+                multisig: null,
+                escrowTokenAccount: null,
+                clientTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([newClient])
+            .rpc();
+
+        const stats = await program.account.userStats.fetch(newStatsPda);
+        assert.strictEqual(stats.escrowsPaidOut, 3);
+        assert.strictEqual(stats.cancellations, 1);
+
+        const { clientBadgePda: newBadgePda } = await deriveClientBadgePDA(newClient.publicKey);
+        await program.methods
+            .initializeClientBadge()
+            .accountsStrict({
+                client: newClient.publicKey,
+                badge: newBadgePda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([newClient])
+            .rpc();
+
+        // the cancellation cancels out `failure_penalty` (3) paid out escrows
+        try {
+            await mintClientNft(newClient, newBadgePda, newStatsPda);
+            assert.fail("Expected InsufficientEscrows");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Insufficient escrows completed for NFT minting");
+        }
+    });
+
+    it("fails to mint with another client's badge", async () => {
+        const stranger = Keypair.generate();
+        await connection.requestAirdrop(stranger.publicKey, 1_000_000_000);
+        await sleep(3000);
+
+        try {
+            await mintClientNft(stranger, clientBadgePda, clientStatsPda);
+            assert.fail("Expected seeds constraint failure");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.match(msg, /(seeds|constraint)/i);
+        }
+    });
+});
@@ -37,6 +37,20 @@ describe("Bondr Devnet E2E", () => {
             program.programId
         );
 
+        // reputation config is initialized once per cluster by the program upgrade authority
+        const [configPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("reputation_config")],
+            program.programId
+        );
+        const [counterpartyPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("counterparty"), freelancer.publicKey.toBuffer(), client.publicKey.toBuffer()],
+            program.programId
+        );
+        const [clientStatsPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("user_stats"), client.publicKey.toBuffer()],
+            program.programId
+        );
+
         try {
             await program.methods
                 .initializeFreelancerBadge()
@@ -72,7 +86,7 @@ describe("Bondr Devnet E2E", () => {
 
             // 1) Init escrow
             await program.methods
-                .initializeEscrow(amount, refSeed, false, false)
+                .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
                 .accountsPartial({
                     sender: client.publicKey,
                     receiver: freelancer.publicKey,
@@ -90,14 +104,24 @@ describe("Bondr Devnet E2E", () => {
                 .signers([client])
                 .rpc();
 
+            await program.methods
+                .acceptEscrow(refSeed, Array(32).fill(0))
+                .accountsStrict({
+                    receiver: freelancer.publicKey,
+                    escrow: escrowPda,
+                })
+                .signers([freelancer])
+                .rpc();
+
             console.log(`📝 Escrow ${i} initialized:`, escrowPda.toBase58());
 
             // 2) Release payment
             await program.methods
-                .releasePayment(refSeed)
+                .releasePayment(refSeed, null)
                 .accountsStrict({
                     client: client.publicKey,
                     escrow: escrowPda,
+                    vault: vaultPda,
                 })
                 .signers([client])
                 .rpc();
@@ -113,7 +137,13 @@ describe("Bondr Devnet E2E", () => {
                     escrow: escrowPda,
                     vault: vaultPda,
                     receiverStats: statsPda,
+                    clientStats: clientStatsPda,
+                    badge: badgePda,
+                    counterparty: counterpartyPda,
+                    reputationConfig: configPda,
                     multisig: null,
+                    payoutApproval: null,
+                    payoutMultisig: null,
                     receiverSol: freelancer.publicKey,
                     escrowTokenAccount: null,
                     receiverTokenAccount: null,
@@ -126,21 +156,11 @@ describe("Bondr Devnet E2E", () => {
 
             console.log(`✅ Escrow ${i} payment claimed`);
 
-            // 4) Update freelancer badge
-            await program.methods
-                .updateFreelancerBadge(amount)
-                .accountsStrict({
-                    freelancer: freelancer.publicKey,
-                    badge: badgePda,
-                })
-                .signers([freelancer])
-                .rpc();
-
-            // 5) Check badge stats
+            // 4) Check badge stats (recorded by the claim)
             const badge = await program.account.freelancerBadge.fetch(badgePda);
             console.log(`📊 Badge stats after escrow ${i}: ${badge.completedEscrows} completed, tier: ${JSON.stringify(badge.tier)}`);
 
-            // 6) Try to mint NFT at milestone (3 escrows = Verified tier)
+            // 5) Try to mint NFT at milestone (3 escrows = Verified tier)
             if (badge.completedEscrows >= 3) {
                 const asset = Keypair.generate();
                 const collection = Keypair.generate();
@@ -152,6 +172,7 @@ describe("Bondr Devnet E2E", () => {
                         .accountsStrict({
                             freelancer: freelancer.publicKey,
                             badge: badgePda,
+                            config: configPda,
                             asset: asset.publicKey,
                             collection: collection.publicKey,
                             mplCoreProgram: MPL_PROGRAM_ID,
@@ -238,6 +259,18 @@ describe("Bondr Devnet E2E", () => {
             [Buffer.from("user_stats"), freelancer.publicKey.toBuffer()],
             program.programId
         );
+        const [clientStatsPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("user_stats"), client.publicKey.toBuffer()],
+            program.programId
+        );
+        const [configPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("reputation_config")],
+            program.programId
+        );
+        const [counterpartyPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("counterparty"), freelancer.publicKey.toBuffer(), client.publicKey.toBuffer()],
+            program.programId
+        );
 
         // 1) Initialize multisig client
         const members: PublicKey[] = [
@@ -252,7 +285,7 @@ describe("Bondr Devnet E2E", () => {
 
         try {
             await program.methods
-                .initializeMultisigClient(members, memberCount, threshold)
+                .initializeMultisigClient(members, memberCount, threshold, null, null)
                 .accountsStrict({
                     client: client.publicKey,
                     multisig: multisigPda,
@@ -267,7 +300,7 @@ describe("Bondr Devnet E2E", () => {
 
         // 2) Initialize escrow with multisig enabled
         await program.methods
-            .initializeEscrow(amount, refSeed, false, true)
+            .initializeEscrow(amount, refSeed, false, true, null, null, null, null, null)
             .accountsPartial({
                 sender: client.publicKey,
                 receiver: freelancer.publicKey,
@@ -285,6 +318,15 @@ describe("Bondr Devnet E2E", () => {
             .signers([client])
             .rpc();
 
+        await program.methods
+            .acceptEscrow(refSeed, Array(32).fill(0))
+            .accountsStrict({
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+            })
+            .signers([freelancer])
+            .rpc();
+
         console.log("📝 Multisig escrow initialized:", escrowPda.toBase58());
 
         // 3) First member approval
@@ -330,7 +372,13 @@ describe("Bondr Devnet E2E", () => {
                 escrow: escrowPda,
                 vault: vaultPda,
                 receiverStats: statsPda,
+                clientStats: clientStatsPda,
+                badge: null,
+                counterparty: counterpartyPda,
+                reputationConfig: configPda,
                 multisig: multisigPda,
+                payoutApproval: null,
+                payoutMultisig: null,
                 receiverSol: freelancer.publicKey,
                 escrowTokenAccount: null,
                 receiverTokenAccount: null,
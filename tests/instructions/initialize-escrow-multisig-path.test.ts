@@ -44,7 +44,7 @@ describe("initialize_escrow() - Multisig Path", () => {
         const threshold = 2;
 
         await program.methods
-            .initializeMultisigClient(members, memberCount, threshold)
+            .initializeMultisigClient(members, memberCount, threshold, null, null)
             .accountsPartial({
                 client: client.publicKey,
                 multisig: multisigPda,
@@ -59,7 +59,7 @@ describe("initialize_escrow() - Multisig Path", () => {
 
         // Step 3: Initialize escrow with multisig
         await program.methods
-            .initializeEscrow(amount, refSeed, false, true)
+            .initializeEscrow(amount, refSeed, false, true, null, null, null, null, null)
             .accountsPartial({
                 sender: client.publicKey,
                 receiver: freelancer.publicKey,
@@ -96,7 +96,7 @@ describe("initialize_escrow() - Multisig Path", () => {
 
         try {
             await program.methods
-                .initializeEscrow(amount, newRefSeed, false, true)
+                .initializeEscrow(amount, newRefSeed, false, true, null, null, null, null, null)
                 .accountsPartial({
                     sender: newClient.publicKey,
                     receiver: newFreelancer.publicKey,
@@ -144,7 +144,7 @@ describe("initialize_escrow() - Multisig Path", () => {
         const threshold = 2;
 
         await program.methods
-            .initializeMultisigClient(members, memberCount, threshold)
+            .initializeMultisigClient(members, memberCount, threshold, null, null)
             .accountsPartial({
                 client: newClient.publicKey,
                 multisig: newMultisigPda,
@@ -168,7 +168,7 @@ describe("initialize_escrow() - Multisig Path", () => {
         ];
 
         await program.methods
-            .initializeMultisigClient(anotherMembers, 3, 2)
+            .initializeMultisigClient(anotherMembers, 3, 2, null, null)
             .accountsPartial({
                 client: anotherClient.publicKey,
                 multisig: anotherMultisigPda,
@@ -182,7 +182,7 @@ describe("initialize_escrow() - Multisig Path", () => {
 
         try {
             await program.methods
-                .initializeEscrow(amount, newRefSeed, false, true)
+                .initializeEscrow(amount, newRefSeed, false, true, null, null, null, null, null)
                 .accountsPartial({
                     sender: newClient.publicKey,
                     receiver: newFreelancer.publicKey,
@@ -235,7 +235,7 @@ describe("initialize_escrow() - Multisig Path", () => {
         const threshold = 2;
 
         await program.methods
-            .initializeMultisigClient(members, memberCount, threshold)
+            .initializeMultisigClient(members, memberCount, threshold, null, null)
             .accountsPartial({
                 client: newClient.publicKey,
                 multisig: newMultisigPda,
@@ -249,7 +249,7 @@ describe("initialize_escrow() - Multisig Path", () => {
         const statsPDAs = await deriveUserStatsPDA(newClient.publicKey);
 
         await program.methods
-            .initializeEscrow(amount, refSeed1, false, true)
+            .initializeEscrow(amount, refSeed1, false, true, null, null, null, null, null)
             .accountsPartial({
                 sender: newClient.publicKey,
                 receiver: newFreelancer1.publicKey,
@@ -272,7 +272,7 @@ describe("initialize_escrow() - Multisig Path", () => {
 
         try {
             await program.methods
-                .initializeEscrow(amount, refSeed2, false, true)
+                .initializeEscrow(amount, refSeed2, false, true, null, null, null, null, null)
                 .accountsPartial({
                     sender: newClient.publicKey,
                     receiver: newFreelancer2.publicKey,
@@ -306,7 +306,7 @@ describe("initialize_escrow() - Multisig Path", () => {
 
         try {
             await program.methods
-                .initializeEscrow(amount, newRefSeed, false, false)
+                .initializeEscrow(amount, newRefSeed, false, false, null, null, null, null, null)
                 .accountsPartial({
                     sender: newClient.publicKey,
                     receiver: newFreelancer.publicKey,
@@ -341,7 +341,7 @@ describe("initialize_escrow() - Multisig Path", () => {
         // If clientMultisig is required in the account structure, provide null or default
         // Check your IDL to see if clientMultisig is marked as optional
         await program.methods
-            .initializeEscrow(amount, newRefSeed, false, false)
+            .initializeEscrow(amount, newRefSeed, false, false, null, null, null, null, null)
             .accountsPartial({
                 sender: newClient.publicKey,
                 receiver: newFreelancer.publicKey,
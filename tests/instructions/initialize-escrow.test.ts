@@ -42,7 +42,7 @@ describe("initialize_escrow()", () => {
 
     it("Initializes an escrow & sender stats correctly", async () => {
         await program.methods
-            .initializeEscrow(amount, refSeed, false, false)
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
             .accountsPartial({
                 sender: sender.publicKey,
                 receiver: receiver.publicKey,
@@ -84,7 +84,7 @@ describe("initialize_escrow()", () => {
 
         try {
             await program.methods
-                .initializeEscrow(zeroAmount, testSeed, false, false)
+                .initializeEscrow(zeroAmount, testSeed, false, false, null, null, null, null, null)
                 .accountsPartial({
                     sender: sender.publicKey,
                     receiver: receiver.publicKey,
@@ -118,7 +118,7 @@ describe("initialize_escrow()", () => {
 
         try {
             await program.methods
-                .initializeEscrow(amount, selfSeed, false, false)
+                .initializeEscrow(amount, selfSeed, false, false, null, null, null, null, null)
                 .accountsPartial({
                     sender: sender.publicKey,
                     receiver: sender.publicKey,
@@ -145,7 +145,7 @@ describe("initialize_escrow()", () => {
     it("fails if escrow PDA already exists (duplicate escrow)", async () => {
         try {
             await program.methods
-                .initializeEscrow(amount, refSeed, false, false)
+                .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
                 .accountsPartial({
                     sender: sender.publicKey,
                     receiver: receiver.publicKey,
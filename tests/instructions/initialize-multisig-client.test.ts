@@ -42,7 +42,7 @@ describe("initialize_multisig_client()", () => {
         const threshold = 2;
 
         await program.methods
-            .initializeMultisigClient(members, memberCount, threshold)
+            .initializeMultisigClient(members, memberCount, threshold, null, null)
             .accountsPartial({
                 client: creator.publicKey,
                 multisig: multisigPda,
@@ -64,6 +64,9 @@ describe("initialize_multisig_client()", () => {
             assert.strictEqual(multisigAccount.approvals[i], 0);
         }
 
+        // one vote per member by default
+        assert.deepEqual(Array.from(multisigAccount.weights), [1, 1, 1, 0, 0]);
+
         assert.ok(multisigAccount.pendingEscrow.equals(PublicKey.default));
         assert.strictEqual(multisigAccount.bump, multisigBump);
     });
@@ -91,7 +94,7 @@ describe("initialize_multisig_client()", () => {
 
         try {
             await program.methods
-                .initializeMultisigClient(members, memberCount, invalidThreshold)
+                .initializeMultisigClient(members, memberCount, invalidThreshold, null, null)
                 .accountsPartial({
                     client: newCreator.publicKey,
                     multisig: newMultisigPda,
@@ -99,10 +102,10 @@ describe("initialize_multisig_client()", () => {
                 })
                 .signers([newCreator])
                 .rpc();
-            assert.fail("instruction should have thrown InvalidMultisigConfig");
+            assert.fail("instruction should have thrown MultisigThresholdUnachievable");
         } catch (err: any) {
             const errorMessage = err.error?.errorMessage || err.message;
-            assert.strictEqual(errorMessage, "Invalid multisig config");
+            assert.strictEqual(errorMessage, "Multisig threshold exceeds the members' total weight");
         }
     });
 
@@ -127,7 +130,7 @@ describe("initialize_multisig_client()", () => {
 
         try {
             await program.methods
-                .initializeMultisigClient(members, memberCount, threshold)
+                .initializeMultisigClient(members, memberCount, threshold, null, null)
                 .accountsPartial({
                     client: newCreator.publicKey,
                     multisig: newMultisigPda,
@@ -163,7 +166,7 @@ describe("initialize_multisig_client()", () => {
 
         try {
             await program.methods
-                .initializeMultisigClient(members, memberCount, threshold)
+                .initializeMultisigClient(members, memberCount, threshold, null, null)
                 .accountsPartial({
                     client: newCreator.publicKey,
                     multisig: newMultisigPda,
@@ -192,7 +195,7 @@ describe("initialize_multisig_client()", () => {
 
         try {
             await program.methods
-                .initializeMultisigClient(members, memberCount, threshold)
+                .initializeMultisigClient(members, memberCount, threshold, null, null)
                 .accountsPartial({
                     client: newCreator.publicKey,
                     multisig: newMultisigPda,
@@ -227,7 +230,7 @@ describe("initialize_multisig_client()", () => {
 
         try {
             await program.methods
-                .initializeMultisigClient(members, memberCount, threshold)
+                .initializeMultisigClient(members, memberCount, threshold, null, null)
                 .accountsPartial({
                     client: newCreator.publicKey,
                     multisig: newMultisigPda,
@@ -242,7 +245,7 @@ describe("initialize_multisig_client()", () => {
         }
     });
 
-    it("fails with member count exceeding MAX_MULTISIG_MEMBERS", async () => {
+    it("fails with member count exceeding the members passed", async () => {
         const newCreator = await createFundedKeypair();
 
         const [newMultisigPda, newMultisigBump] = await PublicKey.findProgramAddress(
@@ -262,7 +265,7 @@ describe("initialize_multisig_client()", () => {
 
         try {
             await program.methods
-                .initializeMultisigClient(members, memberCount, threshold)
+                .initializeMultisigClient(members, memberCount, threshold, null, null)
                 .accountsPartial({
                     client: newCreator.publicKey,
                     multisig: newMultisigPda,
@@ -291,7 +294,7 @@ describe("initialize_multisig_client()", () => {
 
         try {
             await program.methods
-                .initializeMultisigClient(members, memberCount, threshold)
+                .initializeMultisigClient(members, memberCount, threshold, null, null)
                 .accountsPartial({
                     client: creator.publicKey,
                     multisig: multisigPda,
@@ -0,0 +1,218 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+import { createHash } from "crypto";
+
+import { program, connection, sleep } from "../utils/setup";
+import { deriveBidPDA, deriveEscrowPDAs, deriveJobPDAs, deriveUserStatsPDA, expectError } from "../utils/helpers";
+
+describe("jobs / bids", () => {
+    let client: Keypair;
+    let alice: Keypair;
+    let bob: Keypair;
+
+    const budget = new anchor.BN(LAMPORTS_PER_SOL);
+    const descriptionHash = Array.from(createHash("sha256").update("Logo redesign").digest());
+    const proposalHash = (text: string) => Array.from(createHash("sha256").update(text).digest());
+
+    const postJob = async (refSeed: number) => {
+        const { jobPda, vaultPda } = await deriveJobPDAs(client.publicKey, refSeed);
+        await program.methods
+            .postJob(refSeed, budget, descriptionHash, false)
+            .accountsPartial({
+                client: client.publicKey,
+                job: jobPda,
+                vault: vaultPda,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: null,
+                clientTokenAccount: null,
+                jobTokenAccount: null,
+                tokenMint: null,
+                associatedTokenProgram: null,
+            })
+            .signers([client])
+            .rpc();
+        return { jobPda, vaultPda };
+    };
+
+    const submitBid = async (jobPda: PublicKey, freelancer: Keypair, price: anchor.BN, timeline = 7 * 24 * 60 * 60) => {
+        const bidPda = await deriveBidPDA(jobPda, freelancer.publicKey);
+        await program.methods
+            .submitBid(price, new anchor.BN(timeline), proposalHash(freelancer.publicKey.toBase58()))
+            .accountsPartial({
+                freelancer: freelancer.publicKey,
+                job: jobPda,
+                bid: bidPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+        return bidPda;
+    };
+
+    const acceptBid = async (
+        jobRefSeed: number,
+        refSeed: number,
+        freelancer: PublicKey,
+        callback: { program: PublicKey; accounts: PublicKey[] } | null = null
+    ) => {
+        const { jobPda, vaultPda: jobVaultPda } = await deriveJobPDAs(client.publicKey, jobRefSeed);
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer, refSeed);
+        await program.methods
+            .acceptBid(jobRefSeed, refSeed, callback)
+            .accountsPartial({
+                client: client.publicKey,
+                job: jobPda,
+                jobVault: jobVaultPda,
+                bid: await deriveBidPDA(jobPda, freelancer),
+                freelancer,
+                escrow: escrowPda,
+                vault: vaultPda,
+                clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                jobTokenAccount: null,
+                clientTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                associatedTokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+        return { escrowPda, vaultPda };
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        alice = Keypair.generate();
+        bob = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 5_000_000_000),
+            connection.requestAirdrop(alice.publicKey, 1_000_000_000),
+            connection.requestAirdrop(bob.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+    });
+
+    describe("bidding", () => {
+        const jobRefSeed = 1;
+        const refSeed = 131;
+        const alicePrice = new anchor.BN(LAMPORTS_PER_SOL * 0.6);
+        let jobPda: PublicKey;
+        let jobVaultPda: PublicKey;
+
+        before(async () => {
+            ({ jobPda, vaultPda: jobVaultPda } = await postJob(jobRefSeed));
+        });
+
+        it("funds the job budget up front", async () => {
+            const job = await program.account.job.fetch(jobPda);
+            assert.strictEqual(job.budget.toString(), budget.toString());
+            assert.isNull(job.tokenMint);
+            assert.strictEqual(await connection.getBalance(jobVaultPda), budget.toNumber());
+        });
+
+        it("fails to bid above the budget", async () => {
+            await expectError(
+                () => submitBid(jobPda, alice, budget.addn(1)),
+                "Bid must be within the job budget, cover the vault's rent minimum and take up to 366 days"
+            );
+        });
+
+        it("fails to bid with a timeline longer than 366 days", async () => {
+            await expectError(
+                () => submitBid(jobPda, alice, alicePrice, 367 * 24 * 60 * 60),
+                "Bid must be within the job budget, cover the vault's rent minimum and take up to 366 days"
+            );
+        });
+
+        it("fails to bid on your own job", async () => {
+            await expectError(() => submitBid(jobPda, client, alicePrice), "Can't send money to self");
+        });
+
+        it("records bids from several freelancers", async () => {
+            const bidPda = await submitBid(jobPda, alice, alicePrice);
+            await submitBid(jobPda, bob, new anchor.BN(LAMPORTS_PER_SOL * 0.8));
+
+            const bid = await program.account.bid.fetch(bidPda);
+            assert.isTrue(bid.freelancer.equals(alice.publicKey));
+            assert.strictEqual(bid.price.toString(), alicePrice.toString());
+
+            const job = await program.account.job.fetch(jobPda);
+            assert.strictEqual(job.bidCount, 2);
+        });
+
+        it("turns the winning bid into an accepted escrow and refunds the excess", async () => {
+            const clientBefore = await connection.getBalance(client.publicKey);
+            const bidPda = await deriveBidPDA(jobPda, alice.publicKey);
+
+            // the client can register a settlement callback as it accepts
+            const callbackProgram = Keypair.generate().publicKey;
+            const { escrowPda, vaultPda } = await acceptBid(jobRefSeed, refSeed, alice.publicKey, {
+                program: callbackProgram,
+                accounts: [client.publicKey],
+            });
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.callbackProgram!.equals(callbackProgram));
+            assert.strictEqual(escrow.callbackAccountCount, 1);
+            assert.isTrue(escrow.receiver.equals(alice.publicKey));
+            assert.strictEqual(escrow.amount.toString(), alicePrice.toString());
+            assert.isTrue(escrow.isAccepted);
+            assert.deepEqual(Array.from(escrow.termsHash), proposalHash(alice.publicKey.toBase58()));
+            assert.isNotNull(escrow.deadline);
+            assert.strictEqual(await connection.getBalance(vaultPda), alicePrice.toNumber());
+
+            // job and winning bid are closed, the job vault is empty
+            assert.isNull(await program.account.job.fetchNullable(jobPda));
+            assert.isNull(await program.account.bid.fetchNullable(bidPda));
+            assert.strictEqual(await connection.getBalance(jobVaultPda), 0);
+
+            // excess budget plus the job rent, minus the new escrow rent
+            const clientAfter = await connection.getBalance(client.publicKey);
+            assert.isAbove(clientAfter - clientBefore, budget.sub(alicePrice).toNumber() - 10_000_000);
+        });
+
+        it("lets a losing bidder withdraw their bid", async () => {
+            const bidPda = await deriveBidPDA(jobPda, bob.publicKey);
+            await program.methods
+                .withdrawBid()
+                .accountsPartial({ freelancer: bob.publicKey, bid: bidPda })
+                .signers([bob])
+                .rpc();
+
+            assert.isNull(await program.account.bid.fetchNullable(bidPda));
+        });
+    });
+
+    describe("cancel", () => {
+        const jobRefSeed = 2;
+
+        it("refunds the budget and closes the job", async () => {
+            const { jobPda, vaultPda } = await postJob(jobRefSeed);
+            const clientBefore = await connection.getBalance(client.publicKey);
+
+            await program.methods
+                .cancelJob(jobRefSeed)
+                .accountsPartial({
+                    client: client.publicKey,
+                    job: jobPda,
+                    vault: vaultPda,
+                    jobTokenAccount: null,
+                    clientTokenAccount: null,
+                    tokenMint: null,
+                    tokenProgram: null,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([client])
+                .rpc();
+
+            assert.isNull(await program.account.job.fetchNullable(jobPda));
+            assert.strictEqual(await connection.getBalance(vaultPda), 0);
+            const clientAfter = await connection.getBalance(client.publicKey);
+            assert.isAbove(clientAfter - clientBefore, budget.toNumber());
+        });
+    });
+});
@@ -0,0 +1,185 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it, before } from "mocha";
+
+import { program, connection, createFundedKeypair } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveReceiptPDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+describe("large multisig / add_multisig_members()", () => {
+    const refSeed = 60;
+    const amount = new anchor.BN(2_000_000);
+    const threshold = 4;
+
+    let client: Keypair;
+    let freelancer: Keypair;
+    // members only sign their votes, the provider pays the fees
+    const committee = Array.from({ length: 31 }, () => Keypair.generate());
+
+    let multisigPda: PublicKey;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+    let configPda: PublicKey;
+
+    // the client and the first three committee members make up the threshold of 4
+    const addMembers = (members: PublicKey[], approvers = committee.slice(0, threshold - 1)) =>
+        program.methods
+            .addMultisigMembers(members, null)
+            .accountsPartial({
+                client: client.publicKey,
+                multisig: multisigPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(approvers.map((m) => ({ pubkey: m.publicKey, isSigner: true, isWritable: false })))
+            .signers([client, ...approvers])
+            .rpc();
+
+    const accountSize = async () => (await connection.getAccountInfo(multisigPda))!.data.length;
+
+    const approve = (member: Keypair) =>
+        program.methods
+            .approveMultisigRelease(refSeed)
+            .accountsStrict({
+                member: member.publicKey,
+                multisig: multisigPda,
+                escrow: escrowPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([member])
+            .rpc();
+
+    before(async () => {
+        client = await createFundedKeypair();
+        freelancer = await createFundedKeypair();
+        configPda = await ensureReputationConfig();
+
+        [multisigPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("client_multisig"), client.publicKey.toBuffer()],
+            program.programId
+        );
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed));
+    });
+
+    it("sizes the account for the members it was created with", async () => {
+        const members = [client.publicKey, ...committee.slice(0, 19).map((m) => m.publicKey)];
+        await program.methods
+            .initializeMultisigClient(members, members.length, threshold, null, null)
+            .accountsPartial({
+                client: client.publicKey,
+                multisig: multisigPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+
+        const multisig = await program.account.clientMultisig.fetch(multisigPda);
+        assert.strictEqual(multisig.memberCount, 20);
+        assert.lengthOf(multisig.extraMembers, 15);
+        assert.isTrue(multisig.extraMembers[14].key.equals(committee[18].publicKey));
+
+        // 8 + 218 for the first five members, 34 bytes for each one after
+        assert.strictEqual(await accountSize(), 226 + 15 * 34);
+    });
+
+    it("fails to add members without the threshold of member signatures", async () => {
+        await expectError(
+            () => addMembers([Keypair.generate().publicKey], committee.slice(0, threshold - 2)),
+            "Not enough member signatures to change the multisig"
+        );
+
+        // signers that aren't members don't count
+        await expectError(
+            () => addMembers([Keypair.generate().publicKey], [...committee.slice(0, threshold - 2), Keypair.generate()]),
+            "Not enough member signatures to change the multisig"
+        );
+    });
+
+    it("fails to add an existing member", async () => {
+        await expectError(() => addMembers([committee[3].publicKey]), "Duplicate member in multisig");
+    });
+
+    it("grows the account up to 32 members", async () => {
+        await addMembers(committee.slice(19).map((m) => m.publicKey));
+
+        const multisig = await program.account.clientMultisig.fetch(multisigPda);
+        assert.strictEqual(multisig.memberCount, 32);
+        assert.lengthOf(multisig.extraMembers, 27);
+        assert.strictEqual(await accountSize(), 226 + 27 * 34);
+    });
+
+    it("fails to grow past 32 members", async () => {
+        await expectError(() => addMembers([Keypair.generate().publicKey]), "Invalid multisig config");
+    });
+
+    describe("with a pending escrow", () => {
+        before(async () => {
+            await fundEscrow(client, freelancer.publicKey, refSeed, amount, { multisig: multisigPda });
+
+            await acceptEscrow(client.publicKey, freelancer, refSeed);
+        });
+
+        it("fails to add members while an escrow is pending", async () => {
+            await expectError(() => addMembers([Keypair.generate().publicKey]), "Multisig is already busy");
+        });
+
+        it("counts votes of members past the first five", async () => {
+            for (const member of committee.slice(26, 26 + threshold)) {
+                await approve(member);
+            }
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.isReleased);
+
+            const multisig = await program.account.clientMultisig.fetch(multisigPda);
+            assert.deepEqual(
+                multisig.extraMembers.map((m: any) => m.vote),
+                Array.from({ length: 27 }, (_, i) => (i >= 22 && i < 26 ? 1 : 0))
+            );
+        });
+
+        it("pays out and resets every vote", async () => {
+            await program.methods
+                .claimPayment(refSeed, false)
+                .accountsPartial({
+                    client: client.publicKey,
+                    freelancer: freelancer.publicKey,
+                    escrow: escrowPda,
+                    vault: vaultPda,
+                    receiverStats: (await deriveUserStatsPDA(freelancer.publicKey)).statsPda,
+                    clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                    badge: null,
+                    counterparty: await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey),
+                    reputationConfig: configPda,
+                    receipt: await deriveReceiptPDA(
+                        client.publicKey,
+                        (await program.account.escrow.fetch(escrowPda)).escrowId
+                    ),
+                    multisig: multisigPda,
+                    payoutApproval: null,
+                    payoutMultisig: null,
+                    receiverSol: freelancer.publicKey,
+                    escrowTokenAccount: null,
+                    receiverTokenAccount: null,
+                    tokenMint: null,
+                    tokenProgram: null,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([freelancer])
+                .rpc();
+
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            const multisig = await program.account.clientMultisig.fetch(multisigPda);
+            assert.isTrue(multisig.pendingEscrow.equals(PublicKey.default));
+            assert.isTrue(multisig.extraMembers.every((m: any) => m.vote === 0));
+        });
+    });
+});
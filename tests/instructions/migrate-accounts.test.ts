@@ -0,0 +1,178 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+import * as fs from "fs";
+import * as path from "path";
+
+import { program, connection, createFundedKeypair } from "../utils/setup";
+import { deriveFreelancerBadgePDA, expectError } from "../utils/helpers";
+
+// Legacy accounts are preloaded by the validator from tests/fixtures (see Anchor.toml).
+// Their data is the pre-versioning layout: no version byte and none of the appended fields.
+const fixtureAddress = (name: string) => {
+    const file = path.join(__dirname, "..", "fixtures", `${name}.json`);
+    return new PublicKey(JSON.parse(fs.readFileSync(file, "utf8")).pubkey);
+};
+
+// keys baked into the fixture data
+const LEGACY_CLIENT = new PublicKey("9Xg6k8MRQB9bZuMydEitHNM1egqyXVUmqbaFktRn122");
+const LEGACY_FREELANCER = new PublicKey("6FPurf9yn4FDD286aEnnReDNBvUCEjbuF6BcMPS13ou2");
+const LEGACY_MEMBER_1 = new PublicKey("HbaZ5n5PByzgFCnDmejHPFMi94S6M3GYwZ1YS4Q7XSFz");
+const LEGACY_MEMBER_2 = new PublicKey("A7Q5d2B9B5xZybhEi8DzT3cAumk33DLYmsyZwDm5ZsiC");
+const LEGACY_PENDING_ESCROW = new PublicKey("Cpqz2E5NWWuRoDxa3PQVjqDdcknKNBjpyMhHgKey6wod");
+
+const LEGACY_ESCROW_ID_FLAG = new BN(1).shln(63);
+
+describe("migrate_* account migrations", () => {
+    const escrowAddress = fixtureAddress("legacy_escrow");
+    const userStatsAddress = fixtureAddress("legacy_user_stats");
+    const badgeAddress = fixtureAddress("legacy_freelancer_badge");
+    const multisigAddress = fixtureAddress("legacy_client_multisig");
+
+    const accounts = (account: PublicKey) => ({
+        payer: program.provider.publicKey,
+        account,
+        systemProgram: SystemProgram.programId,
+    });
+
+    const assertResized = async (address: PublicKey, size: number) => {
+        const info = await connection.getAccountInfo(address);
+        assert.strictEqual(info!.data.length, size);
+        const rent = await connection.getMinimumBalanceForRentExemption(size);
+        assert.isAtLeast(info!.lamports, rent);
+    };
+
+    it("upgrades a legacy freelancer badge and keeps its tier", async () => {
+        await program.methods.migrateFreelancerBadge().accountsStrict(accounts(badgeAddress)).rpc();
+
+        const badge = await program.account.freelancerBadge.fetch(badgeAddress);
+        assert.strictEqual(badge.version, 1);
+        assert.deepEqual(badge.tier, { elite: {} });
+        assert.deepEqual(badge.effectiveTier, { elite: {} });
+        assert.strictEqual(badge.completedEscrows, 30);
+        assert.strictEqual(badge.totalValueCompleted.toString(), "5000000000");
+        assert.strictEqual(badge.freelancer.toBase58(), LEGACY_FREELANCER.toBase58());
+        assert.strictEqual(badge.bump, 255);
+
+        // legacy counters were self-reported, so the verified ones start from zero
+        assert.strictEqual(badge.qualifiedEscrows, 0);
+        assert.strictEqual(badge.totalValueNormalized.toNumber(), 0);
+        assert.strictEqual(badge.distinctClients, 0);
+        assert.strictEqual(badge.failedEscrows, 0);
+        assert.isFalse(badge.isStale);
+
+        await assertResized(badgeAddress, program.account.freelancerBadge.size);
+    });
+
+    it("upgrades legacy user stats", async () => {
+        await program.methods.migrateUserStats().accountsStrict(accounts(userStatsAddress)).rpc();
+
+        const stats = await program.account.userStats.fetch(userStatsAddress);
+        assert.strictEqual(stats.version, 1);
+        assert.strictEqual(stats.user.toBase58(), LEGACY_FREELANCER.toBase58());
+        assert.strictEqual(stats.completedEscrows, 7);
+        assert.strictEqual(stats.bump, 254);
+        assert.strictEqual(stats.escrowsFunded, 0);
+        assert.strictEqual(stats.ratings.count, 0);
+
+        await assertResized(userStatsAddress, program.account.userStats.size);
+    });
+
+    it("upgrades a legacy escrow with a flagged escrow id", async () => {
+        await program.methods.migrateEscrow().accountsStrict(accounts(escrowAddress)).rpc();
+
+        const escrow = await program.account.escrow.fetch(escrowAddress);
+        assert.strictEqual(escrow.version, 11);
+        assert.strictEqual(escrow.sender.toBase58(), LEGACY_CLIENT.toBase58());
+        assert.strictEqual(escrow.receiver.toBase58(), LEGACY_FREELANCER.toBase58());
+        assert.strictEqual(escrow.amount.toNumber(), 1_000_000);
+        assert.isFalse(escrow.isReleased);
+        assert.strictEqual(escrow.bump, 253);
+        assert.strictEqual(escrow.vaultBump, 252);
+        assert.isNull(escrow.clientMultisig);
+        assert.isNull(escrow.deadline);
+        assert.strictEqual(escrow.releasedAmount.toNumber(), 0);
+        assert.strictEqual(escrow.claimedAmount.toNumber(), 0);
+        // funded before the acceptance handshake, so it counts as accepted
+        assert.isTrue(escrow.isAccepted);
+        assert.isNull(escrow.reviewEndsAt);
+        assert.isFalse(escrow.isDisputed);
+        // nothing records whether it holds SOL or tokens
+        assert.deepStrictEqual(escrow.asset, { unrecorded: {} });
+        assert.strictEqual(escrow.multisigReleased.toNumber(), 0);
+        assert.isNull(escrow.vesting);
+        assert.strictEqual(escrow.teamSize, 0);
+        assert.isFalse(escrow.isRejected);
+        assert.isNull(escrow.releasableAt);
+        assert.isNull(escrow.callbackProgram);
+        assert.strictEqual(escrow.callbackAccountCount, 0);
+
+        // ids of legacy escrows can't collide with ids taken from escrows_funded
+        assert.isTrue(escrow.escrowId.gte(LEGACY_ESCROW_ID_FLAG));
+
+        await assertResized(escrowAddress, program.account.escrow.size);
+    });
+
+    it("upgrades a legacy client multisig", async () => {
+        await program.methods.migrateClientMultisig().accountsStrict(accounts(multisigAddress)).rpc();
+
+        const multisig = await program.account.clientMultisig.fetch(multisigAddress);
+        assert.strictEqual(multisig.version, 4);
+        assert.strictEqual(multisig.members[0].toBase58(), LEGACY_CLIENT.toBase58());
+        assert.strictEqual(multisig.members[1].toBase58(), LEGACY_MEMBER_1.toBase58());
+        assert.strictEqual(multisig.members[2].toBase58(), LEGACY_MEMBER_2.toBase58());
+        assert.strictEqual(multisig.memberCount, 3);
+        assert.strictEqual(multisig.threshold, 2);
+        assert.deepEqual(Array.from(multisig.approvals), [1, 0, 0, 0, 0]);
+        assert.strictEqual(multisig.pendingEscrow.toBase58(), LEGACY_PENDING_ESCROW.toBase58());
+        assert.strictEqual(multisig.bump, 251);
+        assert.strictEqual(multisig.timelock.toNumber(), 0);
+        assert.deepEqual(Array.from(multisig.weights), [1, 1, 1, 1, 1]);
+        assert.lengthOf(multisig.extraMembers, 0);
+
+        // the IDL size doesn't cover vectors, 8 + 218 with an empty extra_members
+        await assertResized(multisigAddress, 226);
+    });
+
+    it("fails to migrate an account twice", async () => {
+        await expectError(
+            () => program.methods.migrateFreelancerBadge().accountsStrict(accounts(badgeAddress)).rpc(),
+            "Account is already on the latest version"
+        );
+    });
+
+    it("fails to migrate an account that was created on the latest version", async () => {
+        const freelancer = await createFundedKeypair(1_000_000_000);
+        const { badgePda: freshBadge } = await deriveFreelancerBadgePDA(freelancer.publicKey);
+
+        await program.methods
+            .initializeFreelancerBadge()
+            .accountsStrict({
+                freelancer: freelancer.publicKey,
+                badge: freshBadge,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+
+        await expectError(
+            () => program.methods.migrateFreelancerBadge().accountsStrict(accounts(freshBadge)).rpc(),
+            "Account is already on the latest version"
+        );
+    });
+
+    it("fails to migrate an account of a different type", async () => {
+        await expectError(
+            () => program.methods.migrateEscrow().accountsStrict(accounts(userStatsAddress)).rpc(),
+            "Account is not a migratable Bondr account"
+        );
+    });
+
+    it("fails to migrate an account not owned by the program", async () => {
+        await expectError(
+            () => program.methods.migrateUserStats().accountsStrict(accounts(Keypair.generate().publicKey)).rpc(),
+            "Account is not a migratable Bondr account"
+        );
+    });
+});
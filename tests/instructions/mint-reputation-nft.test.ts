@@ -5,21 +5,33 @@ import { describe, it } from "mocha";
 import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
 
 import { program, connection, sleep } from "../utils/setup";
-import { deriveFreelancerBadgePDA, deriveBadgeAuthorityPDA } from "../utils/helpers";
+import {
+    deriveFreelancerBadgePDA,
+    deriveBadgeAuthorityPDA,
+    ensureReputationConfig,
+    completeEscrows,
+} from "../utils/helpers";
 
 describe("mint_reputation_nft()", () => {
+    let client: Keypair;
     let freelancer: Keypair;
     let badgePda: PublicKey;
     let badgeAuthorityPda: PublicKey;
     let badgeAuthorityBump: number;
+    let configPda: PublicKey;
 
     // MPL Core program ID
     const MPL_PROGRAM_ID = new PublicKey(MPL_CORE_PROGRAM_ID);
 
     before(async () => {
+        client = Keypair.generate();
         freelancer = Keypair.generate();
 
-        await connection.requestAirdrop(freelancer.publicKey, 2_000_000_000);
+        // tiers are only reachable through real claims, this client funds all of them
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 2_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 2_000_000_000),
+        ]);
         await sleep(3000);
 
         const { badgePda: derivedBadgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey);
@@ -29,6 +41,8 @@ describe("mint_reputation_nft()", () => {
         badgeAuthorityPda = authorityPda;
         badgeAuthorityBump = authorityBump;
 
+        configPda = await ensureReputationConfig();
+
         await program.methods
             .initializeFreelancerBadge()
             .accountsStrict({
@@ -59,16 +73,7 @@ describe("mint_reputation_nft()", () => {
                 .signers([freelancer])
                 .rpc();
 
-            for (let i = 0; i < 3; i++) {
-                await program.methods
-                    .updateFreelancerBadge(new anchor.BN(1_000_000))
-                    .accountsStrict({
-                        freelancer: freelancer.publicKey,
-                        badge: badgePda,
-                    })
-                    .signers([freelancer])
-                    .rpc();
-            }
+            await completeEscrows(client, freelancer, 3);
 
             let badge = await program.account.freelancerBadge.fetch(badgePda);
 
@@ -80,6 +85,7 @@ describe("mint_reputation_nft()", () => {
                 .accountsPartial({
                     freelancer: freelancer.publicKey,
                     badge: badgePda,
+                    config: configPda,
                     asset: asset.publicKey,
                     collection: collection.publicKey,
                     mplCoreProgram: MPL_PROGRAM_ID,
@@ -109,16 +115,7 @@ describe("mint_reputation_nft()", () => {
                 .signers([freelancer2])
                 .rpc();
 
-            for (let i = 0; i < 10; i++) {
-                await program.methods
-                    .updateFreelancerBadge(new anchor.BN(1_000_000))
-                    .accountsStrict({
-                        freelancer: freelancer2.publicKey,
-                        badge: badge2Pda,
-                    })
-                    .signers([freelancer2])
-                    .rpc();
-            }
+            await completeEscrows(client, freelancer2, 10);
 
             let badge = await program.account.freelancerBadge.fetch(badge2Pda);
             assert.strictEqual(badge.completedEscrows, 10);
@@ -131,6 +128,7 @@ describe("mint_reputation_nft()", () => {
                 .accountsStrict({
                     freelancer: freelancer2.publicKey,
                     badge: badge2Pda,
+                    config: configPda,
                     asset: asset.publicKey,
                     collection: collection.publicKey,
                     mplCoreProgram: MPL_CORE_PROGRAM_ID,
@@ -160,16 +158,7 @@ describe("mint_reputation_nft()", () => {
                 .signers([freelancer3])
                 .rpc();
 
-            for (let i = 0; i < 25; i++) {
-                await program.methods
-                    .updateFreelancerBadge(new anchor.BN(1_000_000))
-                    .accountsStrict({
-                        freelancer: freelancer3.publicKey,
-                        badge: badge3Pda,
-                    })
-                    .signers([freelancer3])
-                    .rpc();
-            }
+            await completeEscrows(client, freelancer3, 25);
 
             let badge = await program.account.freelancerBadge.fetch(badge3Pda);
             assert.strictEqual(badge.completedEscrows, 25);
@@ -182,6 +171,7 @@ describe("mint_reputation_nft()", () => {
                 .accountsStrict({
                     freelancer: freelancer3.publicKey,
                     badge: badge3Pda,
+                    config: configPda,
                     asset: asset.publicKey,
                     collection: collection.publicKey,
                     mplCoreProgram: MPL_CORE_PROGRAM_ID,
@@ -198,16 +188,7 @@ describe("mint_reputation_nft()", () => {
             let badge = await program.account.freelancerBadge.fetch(badgePda);
             assert.deepStrictEqual(badge.tier, { unranked: {} });
 
-            for (let i = 0; i < 3; i++) {
-                await program.methods
-                    .updateFreelancerBadge(new anchor.BN(1_000_000))
-                    .accountsStrict({
-                        freelancer: freelancer.publicKey,
-                        badge: badgePda,
-                    })
-                    .signers([freelancer])
-                    .rpc();
-            }
+            await completeEscrows(client, freelancer, 3);
 
             const firstAsset = Keypair.generate();
             const firstCollection = Keypair.generate();
@@ -217,6 +198,7 @@ describe("mint_reputation_nft()", () => {
                 .accountsStrict({
                     freelancer: freelancer.publicKey,
                     badge: badgePda,
+                    config: configPda,
                     asset: firstAsset.publicKey,
                     collection: firstCollection.publicKey,
                     mplCoreProgram: MPL_PROGRAM_ID,
@@ -228,16 +210,7 @@ describe("mint_reputation_nft()", () => {
             badge = await program.account.freelancerBadge.fetch(badgePda);
             assert.deepStrictEqual(badge.tier, { verified: {} });
 
-            for (let i = 0; i < 7; i++) {
-                await program.methods
-                    .updateFreelancerBadge(new anchor.BN(1_000_000))
-                    .accountsStrict({
-                        freelancer: freelancer.publicKey,
-                        badge: badgePda,
-                    })
-                    .signers([freelancer])
-                    .rpc();
-            }
+            await completeEscrows(client, freelancer, 7);
 
             badge = await program.account.freelancerBadge.fetch(badgePda);
             assert.strictEqual(badge.completedEscrows, 10);
@@ -250,6 +223,7 @@ describe("mint_reputation_nft()", () => {
                 .accountsStrict({
                     freelancer: freelancer.publicKey,
                     badge: badgePda,
+                    config: configPda,
                     asset: secondAsset.publicKey,
                     collection: secondCollection.publicKey,
                     mplCoreProgram: MPL_PROGRAM_ID,
@@ -290,6 +264,7 @@ describe("mint_reputation_nft()", () => {
                     .accountsStrict({
                         freelancer: freelancerNew.publicKey,
                         badge: newBadgePda,
+                        config: configPda,
                         asset: asset.publicKey,
                         collection: collection.publicKey,
                         mplCoreProgram: MPL_CORE_PROGRAM_ID,
@@ -321,16 +296,7 @@ describe("mint_reputation_nft()", () => {
                 .signers([freelancerNew])
                 .rpc();
 
-            for (let i = 0; i < 2; i++) {
-                await program.methods
-                    .updateFreelancerBadge(new anchor.BN(1_000_000))
-                    .accountsStrict({
-                        freelancer: freelancerNew.publicKey,
-                        badge: newBadgePda,
-                    })
-                    .signers([freelancerNew])
-                    .rpc();
-            }
+            await completeEscrows(client, freelancerNew, 2);
 
             const asset = Keypair.generate();
             const collection = Keypair.generate();
@@ -341,6 +307,7 @@ describe("mint_reputation_nft()", () => {
                     .accountsStrict({
                         freelancer: freelancerNew.publicKey,
                         badge: newBadgePda,
+                        config: configPda,
                         asset: asset.publicKey,
                         collection: collection.publicKey,
                         mplCoreProgram: MPL_CORE_PROGRAM_ID,
@@ -372,16 +339,7 @@ describe("mint_reputation_nft()", () => {
                 .signers([freelancer])
                 .rpc();
 
-            for (let i = 0; i < 10; i++) {
-                await program.methods
-                    .updateFreelancerBadge(new anchor.BN(1_000_000))
-                    .accountsStrict({
-                        freelancer: freelancer.publicKey,
-                        badge: badgePda,
-                    })
-                    .signers([freelancer])
-                    .rpc();
-            }
+            await completeEscrows(client, freelancer, 10);
 
             let badge = await program.account.freelancerBadge.fetch(badgePda);
             console.log("Badge state before first mint:", {
@@ -401,6 +359,7 @@ describe("mint_reputation_nft()", () => {
                     .accountsStrict({
                         freelancer: freelancer.publicKey,
                         badge: badgePda,
+                        config: configPda,
                         asset: firstAsset.publicKey,
                         collection: firstCollection.publicKey,
                         mplCoreProgram: MPL_CORE_PROGRAM_ID,
@@ -433,6 +392,7 @@ describe("mint_reputation_nft()", () => {
                     .accountsStrict({
                         freelancer: freelancer.publicKey,
                         badge: badgePda,
+                        config: configPda,
                         asset: secondAsset.publicKey,
                         collection: secondCollection.publicKey,
                         mplCoreProgram: MPL_CORE_PROGRAM_ID,
@@ -492,6 +452,7 @@ describe("mint_reputation_nft()", () => {
                     .accountsStrict({
                         freelancer: wrongFreelancer.publicKey, // Wrong freelancer
                         badge: badgePda, // Badge belongs to different freelancer
+                        config: configPda,
                         asset: asset.publicKey,
                         collection: collection.publicKey,
                         mplCoreProgram: MPL_CORE_PROGRAM_ID,
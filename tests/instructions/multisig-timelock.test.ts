@@ -0,0 +1,165 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it, before } from "mocha";
+
+import { program, sleep, createFundedKeypair } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveReceiptPDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+describe("multisig timelock / veto_multisig_release()", () => {
+    const refSeed = 58;
+    const amount = new anchor.BN(2_000_000);
+    const timelock = 4; // seconds
+
+    let client: Keypair;
+    let freelancer: Keypair;
+    let member1: Keypair;
+    let member2: Keypair;
+
+    let multisigPda: PublicKey;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+    let configPda: PublicKey;
+
+    const approve = (member: Keypair) =>
+        program.methods
+            .approveMultisigRelease(refSeed)
+            .accountsStrict({
+                member: member.publicKey,
+                multisig: multisigPda,
+                escrow: escrowPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([member])
+            .rpc();
+
+    const veto = (member: Keypair) =>
+        program.methods
+            .vetoMultisigRelease(refSeed)
+            .accountsStrict({
+                member: member.publicKey,
+                multisig: multisigPda,
+                escrow: escrowPda,
+            })
+            .signers([member])
+            .rpc();
+
+    const claim = async () => {
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: (await deriveUserStatsPDA(freelancer.publicKey)).statsPda,
+                clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey),
+                reputationConfig: configPda,
+                receipt: await deriveReceiptPDA(client.publicKey, escrow.escrowId),
+                multisig: multisigPda,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+    };
+
+    before(async () => {
+        client = await createFundedKeypair();
+        freelancer = await createFundedKeypair();
+        member1 = await createFundedKeypair();
+        member2 = await createFundedKeypair();
+        configPda = await ensureReputationConfig();
+
+        [multisigPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("client_multisig"), client.publicKey.toBuffer()],
+            program.programId
+        );
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed));
+
+        // 2-of-3 multisig with a short cooling-off period
+        const members: PublicKey[] = [
+            client.publicKey,
+            member1.publicKey,
+            member2.publicKey,
+            PublicKey.default,
+            PublicKey.default,
+        ];
+        await program.methods
+            .initializeMultisigClient(members, 3, 2, new anchor.BN(timelock), null)
+            .accountsPartial({
+                client: client.publicKey,
+                multisig: multisigPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+
+        await fundEscrow(client, freelancer.publicKey, refSeed, amount, { multisig: multisigPda });
+
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+    });
+
+    it("stores the timelock on the multisig", async () => {
+        const multisig = await program.account.clientMultisig.fetch(multisigPda);
+        assert.strictEqual(multisig.timelock.toNumber(), timelock);
+    });
+
+    it("fails to veto before the threshold is met", async () => {
+        await expectError(() => veto(member2), "No timelocked release to veto");
+    });
+
+    it("starts the timelock when the threshold is met", async () => {
+        await approve(client);
+        await approve(member1);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.isReleased);
+        assert.isNotNull(escrow.releasableAt);
+    });
+
+    it("fails to claim during the timelock", async () => {
+        await expectError(claim, "Release is timelocked, it can be claimed once the delay has passed");
+    });
+
+    it("lets any member veto during the timelock", async () => {
+        await veto(member2);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isFalse(escrow.isReleased);
+        assert.strictEqual(escrow.releasedAmount.toNumber(), 0);
+        assert.isNull(escrow.releasableAt);
+
+        const multisig = await program.account.clientMultisig.fetch(multisigPda);
+        assert.deepEqual(Array.from(multisig.approvals), [0, 0, 0, 0, 0]);
+    });
+
+    it("pays out once a new approval round has waited out the timelock", async () => {
+        await approve(client);
+        await approve(member1);
+        await sleep((timelock + 2) * 1000);
+
+        await expectError(() => veto(member2), "No timelocked release to veto");
+        await claim();
+
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+    });
+});
@@ -0,0 +1,227 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveReceiptPDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+describe("partial releases", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let configPda: PublicKey;
+    let clientStatsPda: PublicKey;
+    let freelancerStatsPda: PublicKey;
+
+    const amount = new anchor.BN(LAMPORTS_PER_SOL);
+
+    const initEscrow = async (refSeed: number, deadline: anchor.BN | null = null) => {
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
+        await fundEscrow(client, freelancer.publicKey, refSeed, amount, { deadline });
+
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+        const { escrowId } = await program.account.escrow.fetch(escrowPda);
+        return { escrowPda, vaultPda, escrowId };
+    };
+
+    const release = async (refSeed: number, escrowPda: PublicKey, vaultPda: PublicKey, chunk: anchor.BN | null) => {
+        await program.methods
+            .releasePayment(refSeed, chunk)
+            .accountsStrict({
+                client: client.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+            })
+            .signers([client])
+            .rpc();
+    };
+
+    const claim = async (refSeed: number, escrowPda: PublicKey, vaultPda: PublicKey) => {
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: freelancerStatsPda,
+                clientStats: clientStatsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey),
+                reputationConfig: configPda,
+                multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+    };
+
+    const reclaim = async (refSeed: number, escrowPda: PublicKey, vaultPda: PublicKey) => {
+        await program.methods
+            .reclaimExpiredEscrow(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                badge: null,
+                reputationConfig: configPda,
+                multisig: null,
+                asset: null,
+                badgeAuthority: null,
+                mplCoreProgram: null,
+                escrowTokenAccount: null,
+                clientTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+    };
+
+    const secondsFromNow = (seconds: number) => new anchor.BN(Math.floor(Date.now() / 1000) + seconds);
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 3_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        configPda = await ensureReputationConfig();
+        ({ statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey));
+        ({ statsPda: freelancerStatsPda } = await deriveUserStatsPDA(freelancer.publicKey));
+    });
+
+    describe("released in chunks", () => {
+        const refSeed = 61;
+        const chunk = new anchor.BN(LAMPORTS_PER_SOL * 0.3);
+        let escrowPda: PublicKey;
+        let vaultPda: PublicKey;
+        let receiptPda: PublicKey;
+
+        before(async () => {
+            let escrowId: anchor.BN;
+            ({ escrowPda, vaultPda, escrowId } = await initEscrow(refSeed));
+            receiptPda = await deriveReceiptPDA(client.publicKey, escrowId);
+        });
+
+        it("releases part of the escrow", async () => {
+            await release(refSeed, escrowPda, vaultPda, chunk);
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.strictEqual(escrow.releasedAmount.toNumber(), chunk.toNumber());
+            assert.strictEqual(escrow.claimedAmount.toNumber(), 0);
+            assert.isFalse(escrow.isReleased);
+        });
+
+        it("claims the released chunk and keeps the rest locked", async () => {
+            const vaultBefore = await connection.getBalance(vaultPda);
+
+            await claim(refSeed, escrowPda, vaultPda);
+
+            const vaultAfter = await connection.getBalance(vaultPda);
+            assert.strictEqual(vaultBefore - vaultAfter, chunk.toNumber());
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.strictEqual(escrow.claimedAmount.toNumber(), chunk.toNumber());
+
+            const receipt = await program.account.escrowReceipt.fetch(receiptPda);
+            assert.strictEqual(receipt.amount.toNumber(), chunk.toNumber());
+
+            // the escrow only counts once it is settled
+            const stats = await program.account.userStats.fetch(freelancerStatsPda);
+            assert.strictEqual(stats.completedEscrows, 0);
+        });
+
+        it("fails to claim when nothing new was released", async () => {
+            await expectError(() => claim(refSeed, escrowPda, vaultPda), "Nothing released to claim");
+        });
+
+        it("fails to release more than what is still locked", async () => {
+            await expectError(
+                () => release(refSeed, escrowPda, vaultPda, amount),
+                "Release amount exceeds the unreleased escrow balance"
+            );
+        });
+
+        it("fails to release a SOL chunk below the vault rent minimum", async () => {
+            await expectError(
+                () => release(refSeed, escrowPda, vaultPda, new anchor.BN(1_000)),
+                "SOL escrow chunks and the locked remainder must each cover the vault rent-exempt minimum"
+            );
+        });
+
+        it("releases the rest and closes the escrow on the final claim", async () => {
+            await release(refSeed, escrowPda, vaultPda, null);
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.isReleased);
+            assert.strictEqual(escrow.releasedAmount.toNumber(), amount.toNumber());
+
+            await claim(refSeed, escrowPda, vaultPda);
+
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            assert.strictEqual(await connection.getBalance(vaultPda), 0);
+
+            const receipt = await program.account.escrowReceipt.fetch(receiptPda);
+            assert.strictEqual(receipt.amount.toNumber(), amount.toNumber());
+
+            const stats = await program.account.userStats.fetch(freelancerStatsPda);
+            assert.strictEqual(stats.completedEscrows, 1);
+        });
+    });
+
+    describe("reclaimed after a partial release", () => {
+        const refSeed = 62;
+        const chunk = new anchor.BN(LAMPORTS_PER_SOL * 0.4);
+        let escrowPda: PublicKey;
+        let vaultPda: PublicKey;
+
+        before(async () => {
+            ({ escrowPda, vaultPda } = await initEscrow(refSeed, secondsFromNow(3)));
+            await release(refSeed, escrowPda, vaultPda, chunk);
+            await sleep(5000);
+        });
+
+        it("refunds only the unreleased remainder", async () => {
+            await reclaim(refSeed, escrowPda, vaultPda);
+
+            assert.strictEqual(await connection.getBalance(vaultPda), chunk.toNumber());
+
+            // the escrow stays open until the freelancer claims what was released
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.strictEqual(escrow.amount.toNumber(), chunk.toNumber());
+            assert.isTrue(escrow.isReleased);
+        });
+
+        it("lets the freelancer claim the released part and closes the escrow", async () => {
+            const balanceBefore = await connection.getBalance(freelancer.publicKey);
+
+            await claim(refSeed, escrowPda, vaultPda);
+
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            const balanceAfter = await connection.getBalance(freelancer.publicKey);
+            assert.isAbove(balanceAfter, balanceBefore);
+        });
+    });
+});
@@ -0,0 +1,322 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it, before } from "mocha";
+
+import { program, connection, createFundedKeypair, sleep } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveReceiptPDA,
+    deriveRetainerPDAs,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+describe("payout destinations / approve_payout()", () => {
+    const refSeed = 61;
+    const amount = new anchor.BN(2_000_000);
+
+    let client: Keypair;
+    // the agency lead is the escrow receiver, the partners form its receiver group
+    let agency: Keypair;
+    let partner1: Keypair;
+    let partner2: Keypair;
+    let treasury: Keypair;
+    const newTreasury = Keypair.generate();
+
+    let agencyMultisigPda: PublicKey;
+    let destinationPda: PublicKey;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+    let configPda: PublicKey;
+
+    const deriveApprovalPDA = (target: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+            [Buffer.from("payout_approval"), destinationPda.toBuffer(), target.toBuffer()],
+            program.programId
+        )[0];
+
+    const approvePayout = (member: Keypair, target: PublicKey) =>
+        program.methods
+            .approvePayout(target)
+            .accountsStrict({
+                member: member.publicKey,
+                destination: destinationPda,
+                multisig: agencyMultisigPda,
+                approval: deriveApprovalPDA(target),
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([member])
+            .rpc();
+
+    const setDestination = (to: PublicKey, withMultisig: boolean, approval: PublicKey | null = null) =>
+        program.methods
+            .setPayoutDestination(to, withMultisig)
+            .accountsPartial({
+                owner: agency.publicKey,
+                destination: destinationPda,
+                multisig: agencyMultisigPda,
+                approval,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([agency])
+            .rpc();
+
+    const claim = async (receiverSol: PublicKey, withApproval = true) => {
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: agency.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: (await deriveUserStatsPDA(agency.publicKey)).statsPda,
+                clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(agency.publicKey, client.publicKey),
+                reputationConfig: configPda,
+                receipt: await deriveReceiptPDA(client.publicKey, escrow.escrowId),
+                multisig: null,
+                payoutDestination: destinationPda,
+                payoutApproval: withApproval ? deriveApprovalPDA(escrowPda) : null,
+                payoutMultisig: agencyMultisigPda,
+                receiverSol,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([agency])
+            .rpc();
+    };
+
+    before(async () => {
+        client = await createFundedKeypair();
+        agency = await createFundedKeypair();
+        partner1 = await createFundedKeypair();
+        partner2 = await createFundedKeypair();
+        treasury = Keypair.generate();
+        configPda = await ensureReputationConfig();
+
+        [agencyMultisigPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("client_multisig"), agency.publicKey.toBuffer()],
+            program.programId
+        );
+        [destinationPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("payout_destination"), agency.publicKey.toBuffer()],
+            program.programId
+        );
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, agency.publicKey, refSeed));
+
+        // 2-of-3 receiver group
+        const members: PublicKey[] = [
+            agency.publicKey,
+            partner1.publicKey,
+            partner2.publicKey,
+            PublicKey.default,
+            PublicKey.default,
+        ];
+        await program.methods
+            .initializeMultisigClient(members, 3, 2, null, null)
+            .accountsPartial({
+                client: agency.publicKey,
+                multisig: agencyMultisigPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([agency])
+            .rpc();
+
+        await fundEscrow(client, agency.publicKey, refSeed, amount);
+
+        await acceptEscrow(client.publicKey, agency, refSeed);
+        await program.methods
+            .releasePayment(refSeed, null)
+            .accountsStrict({ client: client.publicKey, escrow: escrowPda, vault: vaultPda })
+            .signers([client])
+            .rpc();
+    });
+
+    it("registers a treasury guarded by the receiver group", async () => {
+        await setDestination(treasury.publicKey, true);
+
+        const destination = await program.account.payoutDestination.fetch(destinationPda);
+        assert.isTrue(destination.owner.equals(agency.publicKey));
+        assert.isTrue(destination.treasury.equals(treasury.publicKey));
+        assert.isTrue(destination.receiverMultisig!.equals(agencyMultisigPda));
+    });
+
+    it("fails to claim before the group approved", async () => {
+        await expectError(() => claim(treasury.publicKey, false), "The receiver group has not approved this payout");
+
+        await approvePayout(partner1, escrowPda);
+        await expectError(() => claim(treasury.publicKey), "The receiver group has not approved this payout");
+    });
+
+    it("fails to vote twice", async () => {
+        await expectError(() => approvePayout(partner1, escrowPda), "This member has already approved.");
+    });
+
+    it("fails to claim to the receiver's own wallet", async () => {
+        await approvePayout(partner2, escrowPda);
+        await expectError(() => claim(agency.publicKey), "Payout must go to the receiver's registered treasury");
+    });
+
+    it("pays the treasury and uses up the approval", async () => {
+        const treasuryBefore = await connection.getBalance(treasury.publicKey);
+
+        await claim(treasury.publicKey);
+
+        const treasuryAfter = await connection.getBalance(treasury.publicKey);
+        assert.strictEqual(treasuryAfter - treasuryBefore, amount.toNumber());
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        assert.isNull(await program.account.payoutApproval.fetchNullable(deriveApprovalPDA(escrowPda)));
+    });
+
+    it("fails to change the destination without the group", async () => {
+        await expectError(
+            () => setDestination(agency.publicKey, false),
+            "The receiver group has not approved this payout"
+        );
+    });
+
+    it("fails to change to a treasury the group didn't approve", async () => {
+        await approvePayout(agency, newTreasury.publicKey);
+        await approvePayout(partner2, newTreasury.publicKey);
+
+        await expectError(
+            () => setDestination(agency.publicKey, true, deriveApprovalPDA(newTreasury.publicKey)),
+            "The receiver group has not approved this payout"
+        );
+    });
+
+    it("moves to the approved treasury and voids votes cast before", async () => {
+        // a vote on another change, short of the threshold
+        await approvePayout(partner1, agency.publicKey);
+
+        await setDestination(newTreasury.publicKey, true, deriveApprovalPDA(newTreasury.publicKey));
+
+        const destination = await program.account.payoutDestination.fetch(destinationPda);
+        assert.isTrue(destination.treasury.equals(newTreasury.publicKey));
+        assert.isTrue(destination.receiverMultisig!.equals(agencyMultisigPda));
+        assert.isNull(await program.account.payoutApproval.fetchNullable(deriveApprovalPDA(newTreasury.publicKey)));
+
+        // partner1's vote was cast against the old treasury, the round starts over
+        await approvePayout(partner1, agency.publicKey);
+        const approval = await program.account.payoutApproval.fetch(deriveApprovalPDA(agency.publicKey));
+        assert.isTrue(approval.treasury.equals(newTreasury.publicKey));
+        assert.strictEqual(approval.approved, 0b010);
+    });
+
+    it("changes the destination once the group approved it", async () => {
+        await approvePayout(partner2, agency.publicKey);
+
+        await setDestination(agency.publicKey, true, deriveApprovalPDA(agency.publicKey));
+
+        const destination = await program.account.payoutDestination.fetch(destinationPda);
+        assert.isTrue(destination.treasury.equals(agency.publicKey));
+        assert.isTrue(destination.receiverMultisig!.equals(agencyMultisigPda));
+    });
+
+    it("fails to drop the group with an approval of the treasury only", async () => {
+        await approvePayout(partner1, treasury.publicKey);
+        await approvePayout(partner2, treasury.publicKey);
+
+        await expectError(
+            () => setDestination(treasury.publicKey, false, deriveApprovalPDA(treasury.publicKey)),
+            "The receiver group has not approved this payout"
+        );
+    });
+
+    it("drops the group once it approved giving up the destination", async () => {
+        await approvePayout(partner1, destinationPda);
+        await approvePayout(partner2, destinationPda);
+
+        await setDestination(treasury.publicKey, false, deriveApprovalPDA(destinationPda));
+
+        const destination = await program.account.payoutDestination.fetch(destinationPda);
+        assert.isTrue(destination.treasury.equals(treasury.publicKey));
+        assert.isNull(destination.receiverMultisig);
+    });
+
+    describe("retainer claims", () => {
+        const retainerSeed = 62;
+        const periodAmount = new anchor.BN(1_000_000);
+        let retainerPda: PublicKey;
+        let retainerVaultPda: PublicKey;
+
+        const claimRetainer = (receiverSol: PublicKey) =>
+            program.methods
+                .claimRetainer(retainerSeed, false)
+                .accountsPartial({
+                    sender: client.publicKey,
+                    receiver: agency.publicKey,
+                    retainer: retainerPda,
+                    vault: retainerVaultPda,
+                    payoutDestination: destinationPda,
+                    payoutApproval: null,
+                    payoutMultisig: null,
+                    receiverSol,
+                    retainerTokenAccount: null,
+                    receiverTokenAccount: null,
+                    tokenMint: null,
+                    tokenProgram: null,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([agency])
+                .rpc();
+
+        before(async () => {
+            ({ retainerPda, vaultPda: retainerVaultPda } = await deriveRetainerPDAs(
+                client.publicKey,
+                agency.publicKey,
+                retainerSeed
+            ));
+            // a one second period, prefunded once
+            await program.methods
+                .initializeRetainer(
+                    retainerSeed,
+                    { periodAmount, periodLength: new anchor.BN(1), maxPeriods: 1, startAt: null },
+                    1,
+                    false
+                )
+                .accountsPartial({
+                    sender: client.publicKey,
+                    receiver: agency.publicKey,
+                    retainer: retainerPda,
+                    vault: retainerVaultPda,
+                    systemProgram: SystemProgram.programId,
+                    tokenProgram: null,
+                    senderTokenAccount: null,
+                    retainerTokenAccount: null,
+                    tokenMint: null,
+                    associatedTokenProgram: null,
+                })
+                .signers([client])
+                .rpc();
+            await sleep(2000);
+        });
+
+        it("fails to claim a retainer to the receiver's own wallet", async () => {
+            await expectError(
+                () => claimRetainer(agency.publicKey),
+                "Payout must go to the receiver's registered treasury"
+            );
+        });
+
+        it("pays retainer periods to the treasury", async () => {
+            const treasuryBefore = await connection.getBalance(treasury.publicKey);
+
+            await claimRetainer(treasury.publicKey);
+
+            const treasuryAfter = await connection.getBalance(treasury.publicKey);
+            assert.strictEqual(treasuryAfter - treasuryBefore, periodAmount.toNumber());
+            assert.isNull(await program.account.retainer.fetchNullable(retainerPda));
+        });
+    });
+});
@@ -0,0 +1,200 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    acceptEscrow,
+    completeEscrows,
+    deriveBadgeAuthorityPDA,
+    deriveEscrowPDAs,
+    deriveFreelancerBadgePDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+} from "../utils/helpers";
+
+describe("reclaim_expired_escrow()", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let badgePda: PublicKey;
+    let badgeAuthorityPda: PublicKey;
+    let configPda: PublicKey;
+    let asset: Keypair;
+
+    const amount = new anchor.BN(20_000_000);
+
+    const initEscrow = async (refSeed: number, deadline: anchor.BN | null, escrowAmount: anchor.BN = amount) => {
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
+
+        await program.methods
+            .initializeEscrow(escrowAmount, refSeed, false, false, deadline, null, null, null, null)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                senderStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                clientMultisig: null,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: null,
+                senderTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                associatedTokenProgram: null,
+            })
+            .signers([client])
+            .rpc();
+
+        return { escrowPda, vaultPda };
+    };
+
+    const reclaim = async (refSeed: number, escrowPda: PublicKey, vaultPda: PublicKey) => {
+        await program.methods
+            .reclaimExpiredEscrow(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                badge: badgePda,
+                reputationConfig: configPda,
+                multisig: null,
+                asset: asset.publicKey,
+                badgeAuthority: badgeAuthorityPda,
+                mplCoreProgram: MPL_CORE_PROGRAM_ID,
+                escrowTokenAccount: null,
+                clientTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+    };
+
+    const secondsFromNow = (seconds: number) => new anchor.BN(Math.floor(Date.now() / 1000) + seconds);
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 2_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        configPda = await ensureReputationConfig();
+        ({ badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey));
+        ({ authorityPda: badgeAuthorityPda } = await deriveBadgeAuthorityPDA());
+
+        await program.methods
+            .initializeFreelancerBadge()
+            .accountsStrict({
+                freelancer: freelancer.publicKey,
+                badge: badgePda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+
+        // Verified badge with an asset, so the failure has something to slash
+        await completeEscrows(client, freelancer, 3, amount);
+
+        asset = Keypair.generate();
+        await program.methods
+            .mintReputationNft()
+            .accountsPartial({
+                freelancer: freelancer.publicKey,
+                badge: badgePda,
+                config: configPda,
+                asset: asset.publicKey,
+                collection: Keypair.generate().publicKey,
+                mplCoreProgram: MPL_CORE_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer, asset])
+            .rpc();
+    });
+
+    it("fails to create an escrow with a past deadline", async () => {
+        try {
+            await initEscrow(40, secondsFromNow(-60));
+            assert.fail("Expected InvalidDeadline");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Deadline must be in the future");
+        }
+    });
+
+    it("fails before the deadline and without one", async () => {
+        const cases: [number, anchor.BN | null][] = [
+            [41, secondsFromNow(3600)],
+            [42, null],
+        ];
+
+        for (const [refSeed, deadline] of cases) {
+            const { escrowPda, vaultPda } = await initEscrow(refSeed, deadline);
+            try {
+                await reclaim(refSeed, escrowPda, vaultPda);
+                assert.fail("Expected EscrowNotExpired");
+            } catch (err: any) {
+                const msg = err.error?.errorMessage || err.message;
+                assert.strictEqual(msg, "Escrow has no deadline or it has not passed yet");
+            }
+        }
+    });
+
+    it("refunds without slashing an escrow the freelancer never accepted", async () => {
+        const refSeed = 44;
+        const { escrowPda, vaultPda } = await initEscrow(refSeed, secondsFromNow(2));
+        await sleep(4000);
+
+        await reclaim(refSeed, escrowPda, vaultPda);
+
+        assert.isNull(await connection.getAccountInfo(escrowPda));
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.strictEqual(badge.failedEscrows, 0);
+        assert.strictEqual(badge.failedValue.toNumber(), 0);
+    });
+
+    it("refunds without slashing a dust escrow", async () => {
+        const refSeed = 45;
+        // below the seeded min_escrow_value of 10_000_000
+        const { escrowPda, vaultPda } = await initEscrow(refSeed, secondsFromNow(3), new anchor.BN(5_000_000));
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+        await sleep(5000);
+
+        await reclaim(refSeed, escrowPda, vaultPda);
+
+        assert.isNull(await connection.getAccountInfo(escrowPda));
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.strictEqual(badge.failedEscrows, 0);
+    });
+
+    it("refunds the client and slashes the freelancer badge", async () => {
+        const refSeed = 43;
+        const { escrowPda, vaultPda } = await initEscrow(refSeed, secondsFromNow(3));
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+        await sleep(5000);
+
+        await reclaim(refSeed, escrowPda, vaultPda);
+
+        assert.isNull(await connection.getAccountInfo(escrowPda), "escrow should be closed to the client");
+        assert.strictEqual(await connection.getBalance(vaultPda), 0);
+
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.strictEqual(badge.failedEscrows, 1);
+        assert.strictEqual(badge.failedValue.toNumber(), amount.toNumber());
+        // default penalty cancels out 3 completed escrows -> below Verified
+        assert.deepStrictEqual(badge.tier, { verified: {} });
+        assert.deepStrictEqual(badge.effectiveTier, { unranked: {} });
+        assert.isTrue(badge.isStale);
+
+        // attributes plugin on the asset carries the penalty
+        const assetInfo = await connection.getAccountInfo(asset.publicKey);
+        assert.isTrue(assetInfo.data.includes(Buffer.from("failed_escrows")));
+        assert.isTrue(assetInfo.data.includes(Buffer.from("Unranked")));
+    });
+});
@@ -0,0 +1,144 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it, before } from "mocha";
+
+import { program, connection, createFundedKeypair } from "../utils/setup";
+import { acceptEscrow, deriveEscrowPDAs, deriveUserStatsPDA, expectError, fundEscrow } from "../utils/helpers";
+
+describe("reject_multisig_release()", () => {
+    const refSeed = 57;
+    const amount = new anchor.BN(2_000_000);
+
+    let client: Keypair;
+    let freelancer: Keypair;
+    let member1: Keypair;
+    let member2: Keypair;
+
+    let multisigPda: PublicKey;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+
+    const vote = (member: Keypair, approve: boolean) =>
+        (approve
+            ? program.methods.approveMultisigRelease(refSeed).accountsStrict({
+                  member: member.publicKey,
+                  multisig: multisigPda,
+                  escrow: escrowPda,
+                  systemProgram: SystemProgram.programId,
+              })
+            : program.methods.rejectMultisigRelease(refSeed).accountsStrict({
+                  member: member.publicKey,
+                  multisig: multisigPda,
+                  escrow: escrowPda,
+              })
+        )
+            .signers([member])
+            .rpc();
+
+    before(async () => {
+        client = await createFundedKeypair();
+        freelancer = await createFundedKeypair();
+        member1 = await createFundedKeypair();
+        member2 = await createFundedKeypair();
+
+        [multisigPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("client_multisig"), client.publicKey.toBuffer()],
+            program.programId
+        );
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed));
+
+        // 2-of-3 multisig: two rejections make the threshold unreachable
+        const members: PublicKey[] = [
+            client.publicKey,
+            member1.publicKey,
+            member2.publicKey,
+            PublicKey.default,
+            PublicKey.default,
+        ];
+        await program.methods
+            .initializeMultisigClient(members, 3, 2, null, null)
+            .accountsPartial({
+                client: client.publicKey,
+                multisig: multisigPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+
+        await fundEscrow(client, freelancer.publicKey, refSeed, amount, { multisig: multisigPda });
+
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+    });
+
+    it("records a rejection while the threshold is still reachable", async () => {
+        await vote(member1, false);
+
+        const multisig = await program.account.clientMultisig.fetch(multisigPda);
+        assert.strictEqual(multisig.approvals[1], 2);
+        assert.isTrue(multisig.pendingEscrow.equals(escrowPda));
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isFalse(escrow.isRejected);
+    });
+
+    it("fails to vote twice", async () => {
+        await expectError(() => vote(member1, false), "This member has already rejected.");
+        await expectError(() => vote(member1, true), "This member has already rejected.");
+    });
+
+    it("fails to reject after approving", async () => {
+        await vote(client, true);
+        await expectError(() => vote(client, false), "This member has already approved.");
+    });
+
+    it("rejects the escrow and frees the multisig once the threshold is unreachable", async () => {
+        await vote(member2, false);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.isRejected);
+        assert.isNull(escrow.clientMultisig);
+        assert.isFalse(escrow.isReleased);
+
+        const multisig = await program.account.clientMultisig.fetch(multisigPda);
+        assert.isTrue(multisig.pendingEscrow.equals(PublicKey.default));
+        assert.deepEqual(Array.from(multisig.approvals), [0, 0, 0, 0, 0]);
+    });
+
+    it("fails to release a rejected escrow", async () => {
+        await expectError(
+            () =>
+                program.methods
+                    .releasePayment(refSeed, null)
+                    .accountsStrict({ client: client.publicKey, escrow: escrowPda, vault: vaultPda })
+                    .signers([client])
+                    .rpc(),
+            "The multisig rejected this escrow, it can only be refunded"
+        );
+    });
+
+    it("refunds the rejected escrow without the multisig", async () => {
+        const clientBefore = await connection.getBalance(client.publicKey);
+
+        await program.methods
+            .cancelEscrow(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                multisig: null,
+                escrowTokenAccount: null,
+                clientTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        const clientAfter = await connection.getBalance(client.publicKey);
+        assert.isAbove(clientAfter - clientBefore, amount.toNumber());
+    });
+});
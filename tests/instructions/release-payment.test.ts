@@ -4,7 +4,7 @@ import { assert } from "chai";
 import { describe, it } from "mocha";
 
 import { program, connection, sleep } from "../utils/setup";
-import { deriveEscrowPDAs, deriveUserStatsPDA } from "../utils/helpers";
+import { acceptEscrow, deriveEscrowPDAs, deriveUserStatsPDA } from "../utils/helpers";
 
 describe("release_payment()", () => {
     let client: Keypair;
@@ -36,7 +36,7 @@ describe("release_payment()", () => {
         const { statsPda } = await deriveUserStatsPDA(client.publicKey);
 
         await program.methods
-            .initializeEscrow(amount, refSeed, false, false)
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
             .accountsPartial({
                 sender: client.publicKey,
                 receiver: freelancer.publicKey,
@@ -53,14 +53,17 @@ describe("release_payment()", () => {
             })
             .signers([client])
             .rpc();
+
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
     });
 
     it("releases payment successfully", async () => {
         await program.methods
-            .releasePayment(refSeed)
+            .releasePayment(refSeed, null)
             .accountsStrict({
                 client: client.publicKey,
                 escrow: escrowPda,
+                vault: vaultPda,
             })
             .signers([client])
             .rpc();
@@ -72,10 +75,11 @@ describe("release_payment()", () => {
     it("fails when called by an unauthorised signer", async () => {
         try {
             await program.methods
-                .releasePayment(refSeed)
+                .releasePayment(refSeed, null)
                 .accountsStrict({
                     client: freelancer.publicKey,
                     escrow: escrowPda,
+                    vault: vaultPda,
                 })
                 .signers([freelancer])
                 .rpc();
@@ -89,10 +93,11 @@ describe("release_payment()", () => {
     it("fails if payment is released twice", async () => {
         try {
             await program.methods
-                .releasePayment(refSeed)
+                .releasePayment(refSeed, null)
                 .accountsStrict({
                     client: client.publicKey,
                     escrow: escrowPda,
+                    vault: vaultPda,
                 })
                 .signers([client])
                 .rpc();
@@ -0,0 +1,172 @@
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    deriveProgramDataAddress,
+    deriveReputationConfigPDA,
+    ensureReputationConfig,
+} from "../utils/helpers";
+
+describe("reputation config", () => {
+    let configPda: PublicKey;
+    let outsider: Keypair;
+
+    before(async () => {
+        outsider = Keypair.generate();
+        await connection.requestAirdrop(outsider.publicKey, 1_000_000_000);
+        await sleep(3000);
+
+        configPda = await ensureReputationConfig();
+    });
+
+    it("is seeded with the default tiers", async () => {
+        const config = await program.account.reputationConfig.fetch(configPda);
+
+        assert.isTrue(config.admin.equals(program.provider.publicKey));
+        assert.strictEqual(config.tiers.length, 3);
+        assert.strictEqual(config.tiers[0].name, "Bondr Verified Badge");
+        assert.isAtLeast(config.tiers[0].minCompletedEscrows, 1);
+        assert.isAtLeast(config.tiers[1].minCompletedEscrows, config.tiers[0].minCompletedEscrows);
+        assert.isAtLeast(config.tiers[2].minCompletedEscrows, config.tiers[1].minCompletedEscrows);
+
+        // value floors keep dust escrows from farming a tier
+        assert.isAbove(config.minEscrowValue.toNumber(), 0);
+        assert.isAbove(config.tiers[0].minTotalValue.toNumber(), 0);
+        assert.isAbove(config.clientTiers[0].minTotalValue.toNumber(), 0);
+    });
+
+    it("fails to initialize when signer is not the upgrade authority", async () => {
+        const { configPda: pda } = await deriveReputationConfigPDA();
+
+        try {
+            await program.methods
+                .initializeReputationConfig()
+                .accountsPartial({
+                    admin: outsider.publicKey,
+                    config: pda,
+                    program: program.programId,
+                    programData: await deriveProgramDataAddress(),
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([outsider])
+                .rpc();
+            assert.fail("should have failed");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.match(msg, /(already in use|not the reputation config admin)/i);
+        }
+    });
+
+    it("admin updates a tier's thresholds and metadata", async () => {
+        const initial = await program.account.reputationConfig.fetch(configPda);
+        const elite = initial.tiers[2];
+
+        await program.methods
+            .updateReputationTier({ elite: {} }, {
+                ...elite,
+                uri: "https://example.com/elite-v2.json",
+            })
+            .accountsStrict({
+                admin: program.provider.publicKey,
+                config: configPda,
+            })
+            .rpc();
+
+        let config = await program.account.reputationConfig.fetch(configPda);
+        assert.strictEqual(config.tiers[2].uri, "https://example.com/elite-v2.json");
+        assert.strictEqual(config.tiers[2].minCompletedEscrows, elite.minCompletedEscrows);
+
+        // restore for the suites that mint afterwards
+        await program.methods
+            .updateReputationTier({ elite: {} }, elite)
+            .accountsStrict({
+                admin: program.provider.publicKey,
+                config: configPda,
+            })
+            .rpc();
+
+        config = await program.account.reputationConfig.fetch(configPda);
+        assert.strictEqual(config.tiers[2].uri, elite.uri);
+    });
+
+    it("admin updates client tiers independently of freelancer tiers", async () => {
+        const initial = await program.account.reputationConfig.fetch(configPda);
+        const elite = initial.clientTiers[2];
+
+        await program.methods
+            .updateClientReputationTier({ elite: {} }, {
+                ...elite,
+                minDistinctCounterparties: elite.minDistinctCounterparties + 2,
+            })
+            .accountsStrict({
+                admin: program.provider.publicKey,
+                config: configPda,
+            })
+            .rpc();
+
+        let config = await program.account.reputationConfig.fetch(configPda);
+        assert.strictEqual(config.clientTiers[2].minDistinctCounterparties, elite.minDistinctCounterparties + 2);
+        assert.strictEqual(config.tiers[2].minDistinctCounterparties, initial.tiers[2].minDistinctCounterparties);
+
+        await program.methods
+            .updateClientReputationTier({ elite: {} }, elite)
+            .accountsStrict({
+                admin: program.provider.publicKey,
+                config: configPda,
+            })
+            .rpc();
+
+        config = await program.account.reputationConfig.fetch(configPda);
+        assert.strictEqual(config.clientTiers[2].minDistinctCounterparties, elite.minDistinctCounterparties);
+    });
+
+    it("fails when a non-admin updates a tier", async () => {
+        const config = await program.account.reputationConfig.fetch(configPda);
+
+        try {
+            await program.methods
+                .updateReputationTier({ verified: {} }, { ...config.tiers[0], minCompletedEscrows: 1 })
+                .accountsStrict({
+                    admin: outsider.publicKey,
+                    config: configPda,
+                })
+                .signers([outsider])
+                .rpc();
+            assert.fail("should have failed");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Signer is not the reputation config admin");
+        }
+    });
+
+    it("rejects the unranked tier, empty thresholds and unordered tiers", async () => {
+        const config = await program.account.reputationConfig.fetch(configPda);
+
+        const invalidUpdates: [any, any][] = [
+            [{ unranked: {} }, config.tiers[0]],
+            [{ verified: {} }, { ...config.tiers[0], minCompletedEscrows: 0 }],
+            [{ verified: {} }, { ...config.tiers[0], name: "" }],
+            // a tier can't be easier to reach than the one below it
+            [{ professional: {} }, { ...config.tiers[1], minCompletedEscrows: config.tiers[0].minCompletedEscrows - 1 }],
+            [{ verified: {} }, { ...config.tiers[0], minTotalValue: config.tiers[1].minTotalValue.addn(1) }],
+        ];
+
+        for (const [tier, thresholds] of invalidUpdates) {
+            try {
+                await program.methods
+                    .updateReputationTier(tier, thresholds)
+                    .accountsStrict({
+                        admin: program.provider.publicKey,
+                        config: configPda,
+                    })
+                    .rpc();
+                assert.fail("should have failed");
+            } catch (err: any) {
+                const msg = err.error?.errorMessage || err.message;
+                assert.strictEqual(msg, "Invalid reputation tier config");
+            }
+        }
+    });
+});
@@ -0,0 +1,145 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    completeEscrows,
+    deriveFreelancerBadgePDA,
+    ensureReputationConfig,
+} from "../utils/helpers";
+
+describe("reputation decay", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let badgePda: PublicKey;
+    let configPda: PublicKey;
+    let defaultDecayPeriod: BN;
+
+    const setDecayPeriod = async (decayPeriod: BN, admin?: Keypair) => {
+        const builder = program.methods
+            .setDecayPeriod(decayPeriod)
+            .accountsStrict({
+                admin: admin ? admin.publicKey : program.provider.publicKey,
+                config: configPda,
+            });
+        await (admin ? builder.signers([admin]) : builder).rpc();
+    };
+
+    const refresh = async () => {
+        await program.methods
+            .refreshReputation()
+            .accountsPartial({
+                payer: program.provider.publicKey,
+                badge: badgePda,
+                config: configPda,
+                asset: null,
+                badgeAuthority: null,
+                mplCoreProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 2_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        configPda = await ensureReputationConfig();
+        defaultDecayPeriod = (await program.account.reputationConfig.fetch(configPda)).decayPeriod;
+        ({ badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey));
+
+        await program.methods
+            .initializeFreelancerBadge()
+            .accountsStrict({
+                freelancer: freelancer.publicKey,
+                badge: badgePda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+
+        await completeEscrows(client, freelancer, 3);
+
+        const asset = Keypair.generate();
+        await program.methods
+            .mintReputationNft()
+            .accountsPartial({
+                freelancer: freelancer.publicKey,
+                badge: badgePda,
+                config: configPda,
+                asset: asset.publicKey,
+                collection: Keypair.generate().publicKey,
+                mplCoreProgram: MPL_CORE_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer, asset])
+            .rpc();
+    });
+
+    after(async () => {
+        await setDecayPeriod(defaultDecayPeriod);
+    });
+
+    it("claims and mints keep the effective tier in sync", async () => {
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.deepStrictEqual(badge.tier, { verified: {} });
+        assert.deepStrictEqual(badge.effectiveTier, { verified: {} });
+        assert.isFalse(badge.isStale);
+        assert.isAbove(badge.lastCompletedAt.toNumber(), 0);
+    });
+
+    it("refresh keeps an active badge untouched", async () => {
+        await refresh();
+
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.deepStrictEqual(badge.effectiveTier, { verified: {} });
+        assert.isFalse(badge.isStale);
+    });
+
+    it("refresh lowers the effective tier after inactivity", async () => {
+        await setDecayPeriod(new BN(1));
+        await sleep(2500);
+
+        await refresh();
+
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.deepStrictEqual(badge.tier, { verified: {} }, "minted tier is never lowered");
+        assert.deepStrictEqual(badge.effectiveTier, { unranked: {} });
+        assert.isTrue(badge.isStale);
+    });
+
+    it("a new claim clears the stale flag", async () => {
+        await completeEscrows(client, freelancer, 1);
+
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.deepStrictEqual(badge.effectiveTier, { verified: {} });
+        assert.isFalse(badge.isStale);
+    });
+
+    it("fails for a negative decay period or a non-admin signer", async () => {
+        try {
+            await setDecayPeriod(new BN(-1));
+            assert.fail("Expected InvalidDecayPeriod");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Decay period can't be negative");
+        }
+
+        try {
+            await setDecayPeriod(new BN(0), client);
+            assert.fail("Expected UnauthorizedAdmin");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Signer is not the reputation config admin");
+        }
+    });
+});
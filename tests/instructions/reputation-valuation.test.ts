@@ -0,0 +1,138 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    completeEscrows,
+    deriveCounterpartyPDA,
+    deriveFreelancerBadgePDA,
+    ensureReputationConfig,
+} from "../utils/helpers";
+
+describe("value-weighted reputation", () => {
+    let configPda: PublicKey;
+    let outsider: Keypair;
+
+    const admin = () => ({
+        admin: program.provider.publicKey,
+        config: configPda,
+    });
+
+    const initBadge = async (freelancer: Keypair): Promise<PublicKey> => {
+        const { badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey);
+        await program.methods
+            .initializeFreelancerBadge()
+            .accountsStrict({
+                freelancer: freelancer.publicKey,
+                badge: badgePda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+        return badgePda;
+    };
+
+    before(async () => {
+        outsider = Keypair.generate();
+        await connection.requestAirdrop(outsider.publicKey, 1_000_000_000);
+        await sleep(3000);
+
+        configPda = await ensureReputationConfig();
+    });
+
+    it("admin adds, updates and removes a mint price", async () => {
+        const mint = Keypair.generate().publicKey;
+
+        await program.methods.setMintPrice(mint, new anchor.BN(5_000_000)).accountsStrict(admin()).rpc();
+        let config = await program.account.reputationConfig.fetch(configPda);
+        let entry = config.mintPrices.find((p) => p.mint.equals(mint));
+        assert.strictEqual(entry.price.toNumber(), 5_000_000);
+
+        await program.methods.setMintPrice(mint, new anchor.BN(7_000_000)).accountsStrict(admin()).rpc();
+        config = await program.account.reputationConfig.fetch(configPda);
+        entry = config.mintPrices.find((p) => p.mint.equals(mint));
+        assert.strictEqual(entry.price.toNumber(), 7_000_000);
+
+        await program.methods.setMintPrice(mint, new anchor.BN(0)).accountsStrict(admin()).rpc();
+        config = await program.account.reputationConfig.fetch(configPda);
+        assert.isUndefined(config.mintPrices.find((p) => p.mint.equals(mint)));
+    });
+
+    it("fails when a non-admin sets a price", async () => {
+        try {
+            await program.methods
+                .setMintPrice(Keypair.generate().publicKey, new anchor.BN(1))
+                .accountsStrict({ admin: outsider.publicKey, config: configPda })
+                .signers([outsider])
+                .rpc();
+            assert.fail("should have failed");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Signer is not the reputation config admin");
+        }
+    });
+
+    it("records claims on the badge and counts distinct clients", async () => {
+        const clientA = Keypair.generate();
+        const clientB = Keypair.generate();
+        const freelancer = Keypair.generate();
+        await Promise.all([
+            connection.requestAirdrop(clientA.publicKey, 1_000_000_000),
+            connection.requestAirdrop(clientB.publicKey, 1_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        const badgePda = await initBadge(freelancer);
+
+        await completeEscrows(clientA, freelancer, 2, new anchor.BN(20_000_000));
+        await completeEscrows(clientB, freelancer, 1, new anchor.BN(20_000_000));
+
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.strictEqual(badge.completedEscrows, 3);
+        assert.strictEqual(badge.qualifiedEscrows, 3);
+        assert.strictEqual(badge.totalValueNormalized.toNumber(), 60_000_000);
+        assert.strictEqual(badge.distinctClients, 2);
+
+        const record = await program.account.counterpartyRecord.fetch(
+            await deriveCounterpartyPDA(freelancer.publicKey, clientA.publicKey)
+        );
+        assert.strictEqual(record.completedEscrows, 2);
+    });
+
+    it("admin changes the minimum escrow value", async () => {
+        const { minEscrowValue } = await program.account.reputationConfig.fetch(configPda);
+
+        await program.methods.setMinEscrowValue(new anchor.BN(5_000_000)).accountsStrict(admin()).rpc();
+        let config = await program.account.reputationConfig.fetch(configPda);
+        assert.strictEqual(config.minEscrowValue.toNumber(), 5_000_000);
+
+        await program.methods.setMinEscrowValue(minEscrowValue).accountsStrict(admin()).rpc();
+        config = await program.account.reputationConfig.fetch(configPda);
+        assert.strictEqual(config.minEscrowValue.toNumber(), minEscrowValue.toNumber());
+    });
+
+    it("dust escrows below the minimum value never count towards a tier", async () => {
+        const client = Keypair.generate();
+        const freelancer = Keypair.generate();
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 1_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        const badgePda = await initBadge(freelancer);
+
+        // the seeded minimum already filters dust
+        const config = await program.account.reputationConfig.fetch(configPda);
+        assert.isAbove(config.minEscrowValue.toNumber(), 1);
+        await completeEscrows(client, freelancer, 3, new anchor.BN(1));
+
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.strictEqual(badge.completedEscrows, 3);
+        assert.strictEqual(badge.qualifiedEscrows, 0);
+        assert.strictEqual(badge.totalValueNormalized.toNumber(), 0);
+    });
+});
@@ -0,0 +1,351 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    TOKEN_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    getAccount,
+    getAssociatedTokenAddressSync,
+    getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import { createFundedMint, deriveRetainerPDAs, expectError } from "../utils/helpers";
+
+describe("retainers", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+
+    const periodAmount = new anchor.BN(LAMPORTS_PER_SOL / 10);
+    const periodLength = 3; // seconds
+
+    const terms = (maxPeriods: number, length: number) => ({
+        periodAmount,
+        periodLength: new anchor.BN(length),
+        maxPeriods,
+        startAt: null,
+    });
+
+    const initRetainer = async (
+        refSeed: number,
+        maxPeriods: number,
+        fundedPeriods: number,
+        length: number = periodLength,
+        mint: PublicKey | null = null
+    ) => {
+        const { retainerPda, vaultPda } = await deriveRetainerPDAs(client.publicKey, freelancer.publicKey, refSeed);
+        await program.methods
+            .initializeRetainer(refSeed, terms(maxPeriods, length), fundedPeriods, mint !== null)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: freelancer.publicKey,
+                retainer: retainerPda,
+                vault: vaultPda,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: mint && TOKEN_PROGRAM_ID,
+                senderTokenAccount: mint && getAssociatedTokenAddressSync(mint, client.publicKey),
+                retainerTokenAccount: mint && getAssociatedTokenAddressSync(mint, retainerPda, true),
+                tokenMint: mint,
+                associatedTokenProgram: mint && ASSOCIATED_TOKEN_PROGRAM_ID,
+            })
+            .signers([client])
+            .rpc();
+        return { retainerPda, vaultPda };
+    };
+
+    const crank = async (retainerPda: PublicKey) => {
+        // anyone can crank, the fee payer is the provider wallet here
+        await program.methods.crankRetainer().accountsStrict({ retainer: retainerPda }).rpc();
+    };
+
+    const claim = async (
+        refSeed: number,
+        retainerPda: PublicKey,
+        vaultPda: PublicKey,
+        mint: PublicKey | null = null,
+        retainerTokenAccount: PublicKey | null = mint && getAssociatedTokenAddressSync(mint, retainerPda, true)
+    ) => {
+        const receiverAta = mint
+            ? (await getOrCreateAssociatedTokenAccount(connection, freelancer, mint, freelancer.publicKey)).address
+            : null;
+        await program.methods
+            .claimRetainer(refSeed, mint !== null)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: freelancer.publicKey,
+                retainer: retainerPda,
+                vault: vaultPda,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                retainerTokenAccount,
+                receiverTokenAccount: receiverAta,
+                tokenMint: mint,
+                tokenProgram: mint && TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+    };
+
+    const fund = async (
+        refSeed: number,
+        retainerPda: PublicKey,
+        vaultPda: PublicKey,
+        periods: number,
+        mint: PublicKey | null = null
+    ) => {
+        await program.methods
+            .fundRetainer(refSeed, periods, mint !== null)
+            .accountsPartial({
+                sender: client.publicKey,
+                retainer: retainerPda,
+                vault: vaultPda,
+                senderTokenAccount: mint && getAssociatedTokenAddressSync(mint, client.publicKey),
+                retainerTokenAccount: mint && getAssociatedTokenAddressSync(mint, retainerPda, true),
+                tokenMint: mint,
+                tokenProgram: mint && TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+    };
+
+    const cancel = async (
+        refSeed: number,
+        retainerPda: PublicKey,
+        vaultPda: PublicKey,
+        mint: PublicKey | null = null
+    ) => {
+        await program.methods
+            .cancelRetainer(refSeed, mint !== null)
+            .accountsPartial({
+                sender: client.publicKey,
+                retainer: retainerPda,
+                vault: vaultPda,
+                retainerTokenAccount: mint && getAssociatedTokenAddressSync(mint, retainerPda, true),
+                senderTokenAccount: mint && getAssociatedTokenAddressSync(mint, client.publicKey),
+                tokenMint: mint,
+                tokenProgram: mint && TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+    };
+
+    const setPaused = async (refSeed: number, retainerPda: PublicKey, isPaused: boolean) => {
+        await program.methods
+            .setRetainerPaused(refSeed, isPaused)
+            .accountsStrict({
+                sender: client.publicKey,
+                retainer: retainerPda,
+            })
+            .signers([client])
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 4_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+    });
+
+    it("fails to prefund more periods than the retainer has", async () => {
+        await expectError(() => initRetainer(120, 2, 3), "Funding exceeds the remaining retainer periods");
+    });
+
+    it("fails with a period longer than 366 days", async () => {
+        await expectError(
+            () => initRetainer(120, 2, 1, 367 * 24 * 60 * 60),
+            "Retainer needs a positive period amount, a period of up to 366 days and at least one period"
+        );
+    });
+
+    describe("paid period by period", () => {
+        const refSeed = 121;
+        let retainerPda: PublicKey;
+        let vaultPda: PublicKey;
+
+        before(async () => {
+            ({ retainerPda, vaultPda } = await initRetainer(refSeed, 3, 2));
+        });
+
+        it("prefunds the vault", async () => {
+            const retainer = await program.account.retainer.fetch(retainerPda);
+            assert.strictEqual(retainer.balance.toNumber(), periodAmount.toNumber() * 2);
+            assert.strictEqual(await connection.getBalance(vaultPda), periodAmount.toNumber() * 2);
+        });
+
+        it("fails to crank before the first period is due", async () => {
+            await expectError(() => crank(retainerPda), "No funded retainer period is due");
+        });
+
+        it("releases a due period through the crank", async () => {
+            await sleep((periodLength + 1) * 1000);
+            await crank(retainerPda);
+
+            const retainer = await program.account.retainer.fetch(retainerPda);
+            assert.isAtLeast(retainer.periodsReleased, 1);
+            assert.strictEqual(
+                retainer.releasedAmount.toNumber(),
+                retainer.periodsReleased * periodAmount.toNumber()
+            );
+        });
+
+        it("claims the released periods", async () => {
+            const vaultBefore = await connection.getBalance(vaultPda);
+            await claim(refSeed, retainerPda, vaultPda);
+
+            const retainer = await program.account.retainer.fetch(retainerPda);
+            assert.strictEqual(retainer.claimedAmount.toNumber(), retainer.releasedAmount.toNumber());
+            assert.strictEqual(
+                vaultBefore - (await connection.getBalance(vaultPda)),
+                retainer.claimedAmount.toNumber()
+            );
+        });
+
+        it("funds the last period and closes after the final claim", async () => {
+            await fund(refSeed, retainerPda, vaultPda, 1);
+
+            await sleep(periodLength * 3 * 1000);
+            await claim(refSeed, retainerPda, vaultPda);
+
+            assert.isNull(await program.account.retainer.fetchNullable(retainerPda));
+            assert.strictEqual(await connection.getBalance(vaultPda), 0);
+        });
+    });
+
+    describe("catching up on many due periods", () => {
+        const refSeed = 123;
+
+        it("releases every due period at once, capped by the funded ones", async () => {
+            // one second periods and a long retainer, only three of them prefunded
+            const { retainerPda } = await initRetainer(refSeed, 1000, 3, 1);
+            const before = await program.account.retainer.fetch(retainerPda);
+
+            await sleep(6000);
+            await crank(retainerPda);
+
+            const retainer = await program.account.retainer.fetch(retainerPda);
+            assert.strictEqual(retainer.periodsReleased, 3);
+            assert.strictEqual(retainer.balance.toNumber(), 0);
+            assert.strictEqual(retainer.releasedAmount.toNumber(), periodAmount.toNumber() * 3);
+            assert.strictEqual(retainer.nextPeriodAt.toNumber(), before.nextPeriodAt.toNumber() + 3);
+        });
+    });
+
+    describe("paused and cancelled", () => {
+        const refSeed = 122;
+        let retainerPda: PublicKey;
+        let vaultPda: PublicKey;
+
+        before(async () => {
+            ({ retainerPda, vaultPda } = await initRetainer(refSeed, 5, 5));
+        });
+
+        it("releases nothing while paused", async () => {
+            await setPaused(refSeed, retainerPda, true);
+            await expectError(() => setPaused(refSeed, retainerPda, true), "Retainer is already in that state");
+
+            await sleep((periodLength + 1) * 1000);
+            await expectError(() => crank(retainerPda), "No funded retainer period is due");
+        });
+
+        it("skips the paused periods on resume", async () => {
+            await setPaused(refSeed, retainerPda, false);
+
+            const retainer = await program.account.retainer.fetch(retainerPda);
+            assert.strictEqual(retainer.periodsReleased, 0);
+            assert.isAbove(retainer.nextPeriodAt.toNumber(), Math.floor(Date.now() / 1000) - 1);
+        });
+
+        it("refunds the unreleased balance on cancel", async () => {
+            const clientBefore = await connection.getBalance(client.publicKey);
+
+            await cancel(refSeed, retainerPda, vaultPda);
+
+            // a period that fell due since the resume stays claimable, otherwise the retainer is closed
+            const retainer = await program.account.retainer.fetchNullable(retainerPda);
+            const released = retainer ? retainer.releasedAmount.toNumber() : 0;
+            assert.strictEqual(await connection.getBalance(vaultPda), released);
+            if (retainer) {
+                assert.isTrue(retainer.isCancelled);
+                assert.strictEqual(retainer.balance.toNumber(), 0);
+            }
+
+            const clientAfter = await connection.getBalance(client.publicKey);
+            assert.isAbove(clientAfter - clientBefore, periodAmount.toNumber() * 3);
+        });
+    });
+
+    describe("SPL", () => {
+        const refSeed = 124;
+        let mint: PublicKey;
+        let retainerPda: PublicKey;
+        let vaultPda: PublicKey;
+
+        const tokenBalance = async (owner: PublicKey) =>
+            Number((await getAccount(connection, getAssociatedTokenAddressSync(mint, owner, true))).amount);
+
+        before(async () => {
+            mint = await createFundedMint(client, periodAmount.muln(3));
+            ({ retainerPda, vaultPda } = await initRetainer(refSeed, 3, 1, periodLength, mint));
+        });
+
+        it("records the mint and prefunds the retainer's token account", async () => {
+            const retainer = await program.account.retainer.fetch(retainerPda);
+            assert.isTrue((retainer.asset as any).token.mint.equals(mint));
+            assert.strictEqual(await tokenBalance(retainerPda), periodAmount.toNumber());
+        });
+
+        it("fails to fund a token retainer with SOL", async () => {
+            await expectError(
+                () => fund(refSeed, retainerPda, vaultPda, 1),
+                "Transfer doesn't match the escrow's asset"
+            );
+        });
+
+        it("funds another period in tokens", async () => {
+            await fund(refSeed, retainerPda, vaultPda, 1, mint);
+            assert.strictEqual(await tokenBalance(retainerPda), periodAmount.toNumber() * 2);
+        });
+
+        it("fails to claim from a token account the retainer doesn't own", async () => {
+            await sleep((periodLength + 1) * 1000);
+            const clientAta = getAssociatedTokenAddressSync(mint, client.publicKey);
+            await expectError(
+                () => claim(refSeed, retainerPda, vaultPda, mint, clientAta),
+                "A token owner constraint was violated"
+            );
+        });
+
+        it("claims the released periods in tokens", async () => {
+            await claim(refSeed, retainerPda, vaultPda, mint);
+
+            const retainer = await program.account.retainer.fetch(retainerPda);
+            assert.isAtLeast(retainer.periodsReleased, 1);
+            assert.strictEqual(await tokenBalance(freelancer.publicKey), retainer.claimedAmount.toNumber());
+        });
+
+        it("refunds the unreleased tokens on cancel", async () => {
+            const clientBefore = await tokenBalance(client.publicKey);
+            const { balance } = await program.account.retainer.fetch(retainerPda);
+
+            await cancel(refSeed, retainerPda, vaultPda, mint);
+
+            // a period released since the claim can leave less to refund, never more
+            const clientAfter = await tokenBalance(client.publicKey);
+            assert.isAtMost(clientAfter - clientBefore, balance.toNumber());
+
+            // whatever wasn't refunded or claimed is still released to the freelancer
+            const held = (await tokenBalance(freelancer.publicKey)) + (await tokenBalance(retainerPda));
+            assert.strictEqual(clientAfter + held, periodAmount.toNumber() * 3);
+        });
+    });
+});
@@ -0,0 +1,238 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it, before } from "mocha";
+
+import { program, createFundedKeypair, sleep } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+describe("revoke_multisig_approval()", () => {
+    const refSeed = 56;
+    const amount = new anchor.BN(2_000_000);
+
+    let client: Keypair;
+    let freelancer: Keypair;
+    let member1: Keypair;
+    let member2: Keypair;
+    let nonMember: Keypair;
+
+    let multisigPda: PublicKey;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+
+    const approve = (member: Keypair) =>
+        program.methods
+            .approveMultisigRelease(refSeed)
+            .accountsStrict({
+                member: member.publicKey,
+                multisig: multisigPda,
+                escrow: escrowPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([member])
+            .rpc();
+
+    const revoke = (member: Keypair) =>
+        program.methods
+            .revokeMultisigApproval(refSeed)
+            .accountsStrict({
+                member: member.publicKey,
+                multisig: multisigPda,
+                escrow: escrowPda,
+            })
+            .signers([member])
+            .rpc();
+
+    before(async () => {
+        client = await createFundedKeypair();
+        freelancer = await createFundedKeypair();
+        member1 = await createFundedKeypair();
+        member2 = await createFundedKeypair();
+        nonMember = await createFundedKeypair();
+
+        [multisigPda] = await PublicKey.findProgramAddress(
+            [Buffer.from("client_multisig"), client.publicKey.toBuffer()],
+            program.programId
+        );
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed));
+
+        // 2-of-3 multisig
+        const members: PublicKey[] = [
+            client.publicKey,
+            member1.publicKey,
+            member2.publicKey,
+            PublicKey.default,
+            PublicKey.default,
+        ];
+        await program.methods
+            .initializeMultisigClient(members, 3, 2, null, null)
+            .accountsPartial({
+                client: client.publicKey,
+                multisig: multisigPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+
+        await fundEscrow(client, freelancer.publicKey, refSeed, amount, { multisig: multisigPda });
+
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+    });
+
+    it("fails to revoke without an approval", async () => {
+        await expectError(() => revoke(member1), "This member has not approved.");
+    });
+
+    it("fails to revoke as a non-member", async () => {
+        await expectError(() => revoke(nonMember), "Caller is not a member of the multisig group.");
+    });
+
+    it("clears an approval below the threshold", async () => {
+        await approve(member1);
+        await revoke(member1);
+
+        const multisig = await program.account.clientMultisig.fetch(multisigPda);
+        assert.strictEqual(multisig.approvals[1], 0);
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isFalse(escrow.isReleased);
+    });
+
+    it("un-releases the escrow when the count drops below the threshold", async () => {
+        await approve(member1);
+        await approve(member2);
+
+        let escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.isReleased);
+
+        await revoke(member2);
+
+        escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isFalse(escrow.isReleased);
+        assert.strictEqual(escrow.releasedAmount.toNumber(), 0);
+        assert.strictEqual(escrow.multisigReleased.toNumber(), 0);
+
+        const multisig = await program.account.clientMultisig.fetch(multisigPda);
+        assert.strictEqual(multisig.approvals[1], 1, "member1 keeps their approval");
+        assert.strictEqual(multisig.approvals[2], 0);
+    });
+
+    it("releases again once the threshold is met again", async () => {
+        await approve(member2);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.isReleased);
+        assert.strictEqual(escrow.releasedAmount.toString(), amount.toString());
+    });
+
+    describe("after a stream vested", () => {
+        const seed = 57;
+        const streamAmount = new anchor.BN(100_000_000);
+
+        let sender: Keypair;
+        let receiver: Keypair;
+        let signer1: Keypair;
+        let signer2: Keypair;
+        let streamMultisig: PublicKey;
+        let streamEscrow: PublicKey;
+        let streamVault: PublicKey;
+
+        const vote = (method: "approveMultisigRelease" | "revokeMultisigApproval", member: Keypair) =>
+            program.methods[method](seed)
+                .accountsPartial({ member: member.publicKey, multisig: streamMultisig, escrow: streamEscrow })
+                .signers([member])
+                .rpc();
+
+        before(async () => {
+            sender = await createFundedKeypair();
+            receiver = await createFundedKeypair();
+            signer1 = await createFundedKeypair();
+            signer2 = await createFundedKeypair();
+
+            [streamMultisig] = await PublicKey.findProgramAddress(
+                [Buffer.from("client_multisig"), sender.publicKey.toBuffer()],
+                program.programId
+            );
+            ({ escrowPda: streamEscrow, vaultPda: streamVault } = await deriveEscrowPDAs(
+                sender.publicKey,
+                receiver.publicKey,
+                seed
+            ));
+
+            await program.methods
+                .initializeMultisigClient(
+                    [sender.publicKey, signer1.publicKey, signer2.publicKey, PublicKey.default, PublicKey.default],
+                    3,
+                    2,
+                    null,
+                    null
+                )
+                .accountsPartial({
+                    client: sender.publicKey,
+                    multisig: streamMultisig,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([sender])
+                .rpc();
+
+            const now = Math.floor(Date.now() / 1000);
+            const vesting = { start: new anchor.BN(now), cliff: null, end: new anchor.BN(now + 20) };
+            await fundEscrow(sender, receiver.publicKey, seed, streamAmount, { multisig: streamMultisig, vesting });
+            await acceptEscrow(sender.publicKey, receiver, seed);
+        });
+
+        it("takes back only the multisig's release", async () => {
+            // the receiver claims what vested so far
+            await sleep(5000);
+            await program.methods
+                .claimPayment(seed, false)
+                .accountsPartial({
+                    client: sender.publicKey,
+                    freelancer: receiver.publicKey,
+                    escrow: streamEscrow,
+                    vault: streamVault,
+                    receiverStats: (await deriveUserStatsPDA(receiver.publicKey)).statsPda,
+                    clientStats: (await deriveUserStatsPDA(sender.publicKey)).statsPda,
+                    badge: null,
+                    counterparty: await deriveCounterpartyPDA(receiver.publicKey, sender.publicKey),
+                    reputationConfig: await ensureReputationConfig(),
+                    multisig: streamMultisig,
+                    payoutApproval: null,
+                    payoutMultisig: null,
+                    receiverSol: receiver.publicKey,
+                    escrowTokenAccount: null,
+                    receiverTokenAccount: null,
+                    tokenMint: null,
+                    tokenProgram: null,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([receiver])
+                .rpc();
+
+            let escrow = await program.account.escrow.fetch(streamEscrow);
+            const vested = escrow.releasedAmount.toNumber();
+            assert.isAbove(vested, 0);
+
+            // the threshold releases the rest, the revocation takes back just that part
+            await vote("approveMultisigRelease", signer1);
+            await vote("approveMultisigRelease", signer2);
+            escrow = await program.account.escrow.fetch(streamEscrow);
+            assert.isTrue(escrow.isReleased);
+            assert.strictEqual(escrow.multisigReleased.toNumber(), streamAmount.toNumber() - vested);
+
+            await vote("revokeMultisigApproval", signer2);
+            escrow = await program.account.escrow.fetch(streamEscrow);
+            assert.isFalse(escrow.isReleased);
+            assert.strictEqual(escrow.releasedAmount.toNumber(), vested);
+            assert.strictEqual(escrow.claimedAmount.toNumber(), vested);
+            assert.strictEqual(escrow.multisigReleased.toNumber(), 0);
+        });
+    });
+});
@@ -0,0 +1,252 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { AccountMeta, Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it, before } from "mocha";
+
+import { BondrCpiExample } from "../../target/types/bondr_cpi_example";
+import { program, createFundedKeypair } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveReceiptPDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+// bondr_cpi_example's settlement log stands in for an integrator's state
+describe("settlement callbacks", () => {
+    const example = anchor.workspace.BondrCpiExample as Program<BondrCpiExample>;
+    const claimSeed = 64;
+    const cancelSeed = 65;
+    const declineSeed = 66;
+    const invalidSeed = 67;
+    const skipSeed = 68;
+    const strangerSeed = 69;
+    const amount = new anchor.BN(2_000_000);
+
+    let client: Keypair;
+    let freelancer: Keypair;
+    let logPda: PublicKey;
+    let configPda: PublicKey;
+
+    // the callback program followed by the registered accounts
+    const callbackAccounts = (): AccountMeta[] => [
+        { pubkey: example.programId, isSigner: false, isWritable: false },
+        { pubkey: logPda, isSigner: false, isWritable: true },
+    ];
+
+    // the callback is registered by the instruction creating the escrow
+    const fundWithCallback = (refSeed: number, accounts = [logPda]) =>
+        fundEscrow(client, freelancer.publicKey, refSeed, amount, {
+            callback: { program: example.programId, accounts },
+        });
+
+    const release = async (refSeed: number) => {
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
+        await program.methods
+            .releasePayment(refSeed, null)
+            .accountsStrict({ client: client.publicKey, escrow: escrowPda, vault: vaultPda })
+            .signers([client])
+            .rpc();
+    };
+
+    const claim = async (remainingAccounts: AccountMeta[], refSeed = claimSeed) => {
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: (await deriveUserStatsPDA(freelancer.publicKey)).statsPda,
+                clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey),
+                reputationConfig: configPda,
+                receipt: await deriveReceiptPDA(client.publicKey, escrow.escrowId),
+                multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([freelancer])
+            .rpc();
+    };
+
+    const cancel = async (refSeed: number, sender = client, remainingAccounts = callbackAccounts()) => {
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(sender.publicKey, freelancer.publicKey, refSeed);
+        await program.methods
+            .cancelEscrow(refSeed, false)
+            .accountsPartial({
+                client: sender.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                clientStats: (await deriveUserStatsPDA(sender.publicKey)).statsPda,
+                multisig: null,
+                escrowTokenAccount: null,
+                clientTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([sender])
+            .rpc();
+    };
+
+    const assertLogged = async (settled: number, escrowId: number, outcome: number) => {
+        const log = await example.account.settlementLog.fetch(logPda);
+        assert.strictEqual(log.settled, settled);
+        assert.strictEqual(log.lastEscrowId.toNumber(), escrowId);
+        assert.strictEqual(log.lastOutcome, outcome);
+        assert.strictEqual(log.lastAmount.toNumber(), amount.toNumber());
+    };
+
+    before(async () => {
+        client = await createFundedKeypair();
+        freelancer = await createFundedKeypair();
+        configPda = await ensureReputationConfig();
+
+        [logPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("settlement_log"), client.publicKey.toBuffer()],
+            example.programId
+        );
+        await example.methods
+            .initSettlementLog()
+            .accountsStrict({
+                authority: client.publicKey,
+                log: logPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+    });
+
+    describe("claim", () => {
+        before(async () => {
+            await fundWithCallback(claimSeed);
+        });
+
+        it("registers the callback at creation", async () => {
+            const { escrowPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, claimSeed);
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.callbackProgram!.equals(example.programId));
+            assert.strictEqual(escrow.callbackAccountCount, 1);
+            assert.isTrue(escrow.callbackAccounts[0].equals(logPda));
+        });
+
+        it("fails with more than four callback accounts", async () => {
+            const accounts = Array.from({ length: 5 }, () => Keypair.generate().publicKey);
+            await expectError(() => fundWithCallback(invalidSeed, accounts), "Invalid settlement callback");
+        });
+
+        it("fails to settle with other callback accounts", async () => {
+            await acceptEscrow(client.publicKey, freelancer, claimSeed);
+            await release(claimSeed);
+
+            await expectError(
+                () => claim([callbackAccounts()[0]]),
+                "Settlement callback accounts don't match the escrow's"
+            );
+            await expectError(
+                () => claim([callbackAccounts()[0], { pubkey: client.publicKey, isSigner: false, isWritable: true }]),
+                "Settlement callback accounts don't match the escrow's"
+            );
+        });
+
+        it("notifies the callback when the claim settles the escrow", async () => {
+            await claim(callbackAccounts());
+            await assertLogged(1, 0, 0);
+        });
+    });
+
+    describe("refunds", () => {
+        it("notifies the callback on cancel", async () => {
+            await fundWithCallback(cancelSeed);
+
+            // only the receiver's claim can leave the callback out
+            await expectError(
+                () => cancel(cancelSeed, client, []),
+                "Settlement callback accounts don't match the escrow's"
+            );
+            await cancel(cancelSeed);
+
+            const { escrowPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, cancelSeed);
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            await assertLogged(2, 1, 1);
+        });
+
+        it("notifies the callback on decline", async () => {
+            await fundWithCallback(declineSeed);
+
+            const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, declineSeed);
+            await program.methods
+                .declineEscrow(declineSeed, false)
+                .accountsPartial({
+                    sender: client.publicKey,
+                    receiver: freelancer.publicKey,
+                    escrow: escrowPda,
+                    vault: vaultPda,
+                    multisig: null,
+                    escrowTokenAccount: null,
+                    senderTokenAccount: null,
+                    tokenMint: null,
+                    tokenProgram: null,
+                    systemProgram: SystemProgram.programId,
+                })
+                .remainingAccounts(callbackAccounts())
+                .signers([freelancer])
+                .rpc();
+
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            await assertLogged(3, 2, 2);
+        });
+    });
+
+    // a failing or hostile callback can't keep the receiver from its money
+    it("lets the receiver's claim skip the callback", async () => {
+        await fundWithCallback(skipSeed);
+        await acceptEscrow(client.publicKey, freelancer, skipSeed);
+        await release(skipSeed);
+
+        await claim([], skipSeed);
+
+        const { escrowPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, skipSeed);
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        await assertLogged(3, 2, 2);
+    });
+
+    it("rejects callbacks that weren't signed by a Bondr escrow", async () => {
+        await expectError(
+            () =>
+                example.methods
+                    .onBondrSettlement(new anchor.BN(0), 0, amount)
+                    .accountsStrict({ escrow: client.publicKey, log: logPda })
+                    .signers([client])
+                    .rpc(),
+            "The given account is owned by a different program than expected"
+        );
+    });
+
+    it("rejects escrows of other senders registering the log", async () => {
+        const stranger = await createFundedKeypair();
+        await fundEscrow(stranger, freelancer.publicKey, strangerSeed, amount, {
+            callback: { program: example.programId, accounts: [logPda] },
+        });
+
+        await expectError(() => cancel(strangerSeed, stranger), "A require_keys_eq expression was violated");
+        await assertLogged(3, 2, 2);
+    });
+});
@@ -0,0 +1,210 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+import { createHash } from "crypto";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+describe("streaming escrows", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let configPda: PublicKey;
+    let clientStatsPda: PublicKey;
+    let freelancerStatsPda: PublicKey;
+
+    const amount = new anchor.BN(LAMPORTS_PER_SOL);
+
+    type Vesting = { start: anchor.BN; cliff: anchor.BN | null; end: anchor.BN };
+
+    const schedule = (startOffset: number, endOffset: number, cliffOffset?: number): Vesting => {
+        const now = Math.floor(Date.now() / 1000);
+        return {
+            start: new anchor.BN(now + startOffset),
+            cliff: cliffOffset === undefined ? null : new anchor.BN(now + cliffOffset),
+            end: new anchor.BN(now + endOffset),
+        };
+    };
+
+    const initEscrow = async (refSeed: number, vesting: Vesting, deadline: anchor.BN | null = null) => {
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
+        await fundEscrow(client, freelancer.publicKey, refSeed, amount, { deadline, vesting });
+        return { escrowPda, vaultPda };
+    };
+
+    const claim = async (refSeed: number, escrowPda: PublicKey, vaultPda: PublicKey) => {
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: freelancerStatsPda,
+                clientStats: clientStatsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey),
+                reputationConfig: configPda,
+                multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+    };
+
+    const reclaim = async (refSeed: number, escrowPda: PublicKey, vaultPda: PublicKey) => {
+        await program.methods
+            .reclaimExpiredEscrow(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                badge: null,
+                reputationConfig: configPda,
+                multisig: null,
+                asset: null,
+                badgeAuthority: null,
+                mplCoreProgram: null,
+                escrowTokenAccount: null,
+                clientTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([client])
+            .rpc();
+    };
+
+    const secondsFromNow = (seconds: number) => new anchor.BN(Math.floor(Date.now() / 1000) + seconds);
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 4_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        configPda = await ensureReputationConfig();
+        ({ statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey));
+        ({ statsPda: freelancerStatsPda } = await deriveUserStatsPDA(freelancer.publicKey));
+    });
+
+    it("fails with a schedule that ends before it starts", async () => {
+        await expectError(
+            () => initEscrow(111, schedule(60, 30)),
+            "Vesting must start before it ends, with the cliff in between"
+        );
+    });
+
+    it("fails with a cliff after the end", async () => {
+        await expectError(
+            () => initEscrow(111, schedule(0, 30, 60)),
+            "Vesting must start before it ends, with the cliff in between"
+        );
+    });
+
+    it("has nothing to claim before the cliff", async () => {
+        const refSeed = 112;
+        const { escrowPda, vaultPda } = await initEscrow(refSeed, schedule(-10, 3600, 1800));
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+
+        await expectError(() => claim(refSeed, escrowPda, vaultPda), "Payment not released yet");
+    });
+
+    it("fails to submit work on a stream", async () => {
+        const { escrowPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, 112);
+        const deliverable = Array.from(createHash("sha256").update("ipfs://stream-report").digest());
+
+        await expectError(
+            () =>
+                program.methods
+                    .submitWork(112, deliverable)
+                    .accountsStrict({ receiver: freelancer.publicKey, escrow: escrowPda })
+                    .signers([freelancer])
+                    .rpc(),
+            "Streaming escrows release on their vesting schedule, not on submitted work"
+        );
+    });
+
+    describe("vesting over time", () => {
+        const refSeed = 113;
+        let escrowPda: PublicKey;
+        let vaultPda: PublicKey;
+
+        before(async () => {
+            // a quarter vested at creation, the rest over the next 30 seconds, expired after 5
+            ({ escrowPda, vaultPda } = await initEscrow(refSeed, schedule(-10, 30), secondsFromNow(5)));
+            await acceptEscrow(client.publicKey, freelancer, refSeed);
+        });
+
+        it("claims the vested part and keeps the rest locked", async () => {
+            await claim(refSeed, escrowPda, vaultPda);
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isAbove(escrow.claimedAmount.toNumber(), 0);
+            assert.isBelow(escrow.claimedAmount.toNumber(), amount.toNumber());
+            assert.strictEqual(escrow.claimedAmount.toNumber(), escrow.releasedAmount.toNumber());
+            assert.isFalse(escrow.isReleased);
+        });
+
+        it("refunds only the unvested remainder once the deadline passed", async () => {
+            await sleep(6000);
+            const clientBefore = await connection.getBalance(client.publicKey);
+
+            await reclaim(refSeed, escrowPda, vaultPda);
+
+            // what vested until the reclaim stays with the freelancer
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.isReleased);
+            assert.isAbove(escrow.amount.toNumber(), escrow.claimedAmount.toNumber());
+            assert.isBelow(escrow.amount.toNumber(), amount.toNumber());
+            assert.strictEqual(
+                await connection.getBalance(vaultPda),
+                escrow.amount.sub(escrow.claimedAmount).toNumber()
+            );
+
+            const clientAfter = await connection.getBalance(client.publicKey);
+            assert.isAbove(clientAfter, clientBefore);
+        });
+
+        it("lets the freelancer claim what vested and closes the escrow", async () => {
+            await claim(refSeed, escrowPda, vaultPda);
+
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            assert.strictEqual(await connection.getBalance(vaultPda), 0);
+        });
+    });
+
+    it("releases everything once the schedule has ended", async () => {
+        const refSeed = 114;
+        const { escrowPda, vaultPda } = await initEscrow(refSeed, schedule(-20, 2));
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+        await sleep(3000);
+
+        const balanceBefore = await connection.getBalance(freelancer.publicKey);
+        await claim(refSeed, escrowPda, vaultPda);
+
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        const balanceAfter = await connection.getBalance(freelancer.publicKey);
+        assert.isAbove(balanceAfter - balanceBefore, amount.toNumber() * 0.99);
+    });
+});
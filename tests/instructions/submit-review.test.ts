@@ -0,0 +1,219 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    acceptEscrow,
+    completeEscrows,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveFreelancerBadgePDA,
+    deriveReceiptPDA,
+    deriveReviewPDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+} from "../utils/helpers";
+
+describe("submit_review()", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let outsider: Keypair;
+    let receiptPda: PublicKey;
+    let badgePda: PublicKey;
+    let clientStatsPda: PublicKey;
+    let freelancerStatsPda: PublicKey;
+
+    const contentHash = Array.from(Buffer.alloc(32, 7));
+
+    // the receiver's badge is always passed, it's only rated when the client reviews the freelancer
+    const submitReview = async (reviewer: Keypair, rating: number, revieweeStats: PublicKey) => {
+        await program.methods
+            .submitReview(rating, contentHash)
+            .accountsPartial({
+                reviewer: reviewer.publicKey,
+                receipt: receiptPda,
+                review: await deriveReviewPDA(receiptPda, reviewer.publicKey),
+                revieweeStats,
+                revieweeBadge: badgePda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([reviewer])
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+        outsider = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 2_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+            connection.requestAirdrop(outsider.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        ({ badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey));
+        ({ statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey));
+        ({ statsPda: freelancerStatsPda } = await deriveUserStatsPDA(freelancer.publicKey));
+
+        await program.methods
+            .initializeFreelancerBadge()
+            .accountsStrict({
+                freelancer: freelancer.publicKey,
+                badge: badgePda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+
+        // first escrow of a fresh client -> escrow_id 0
+        await completeEscrows(client, freelancer, 1);
+        receiptPda = await deriveReceiptPDA(client.publicKey, 0);
+    });
+
+    it("claim leaves a receipt for the settled escrow", async () => {
+        const receipt = await program.account.escrowReceipt.fetch(receiptPda);
+        assert.isTrue(receipt.sender.equals(client.publicKey));
+        assert.isTrue(receipt.receiver.equals(freelancer.publicKey));
+        assert.strictEqual(receipt.escrowId.toNumber(), 0);
+        assert.strictEqual(receipt.amount.toNumber(), 20_000_000);
+        assert.isAbove(receipt.settledAt.toNumber(), 0);
+    });
+
+    it("client rates the freelancer", async () => {
+        await submitReview(client, 5, freelancerStatsPda);
+
+        const review = await program.account.review.fetch(await deriveReviewPDA(receiptPda, client.publicKey));
+        assert.isTrue(review.reviewee.equals(freelancer.publicKey));
+        assert.strictEqual(review.rating, 5);
+        assert.deepStrictEqual(review.contentHash, contentHash);
+
+        const stats = await program.account.userStats.fetch(freelancerStatsPda);
+        assert.strictEqual(stats.ratings.count, 1);
+        assert.strictEqual(stats.ratings.average, 500);
+
+        const badge = await program.account.freelancerBadge.fetch(badgePda);
+        assert.strictEqual(badge.ratings.count, 1);
+        assert.strictEqual(badge.ratings.average, 500);
+    });
+
+    it("freelancer rates the client", async () => {
+        await submitReview(freelancer, 4, clientStatsPda);
+
+        const stats = await program.account.userStats.fetch(clientStatsPda);
+        assert.strictEqual(stats.ratings.count, 1);
+        assert.strictEqual(stats.ratings.sum.toNumber(), 4);
+        assert.strictEqual(stats.ratings.average, 400);
+    });
+
+    it("fails to review the same escrow twice", async () => {
+        try {
+            await submitReview(client, 1, freelancerStatsPda);
+            assert.fail("Expected second review to fail");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.match(msg, /already in use/i);
+        }
+
+        const stats = await program.account.userStats.fetch(freelancerStatsPda);
+        assert.strictEqual(stats.ratings.count, 1);
+    });
+
+    it("fails when the reviewer is not a party of the escrow", async () => {
+        try {
+            await submitReview(outsider, 1, freelancerStatsPda);
+            assert.fail("Expected NotEscrowParty");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Signer is not a party of this escrow");
+        }
+    });
+
+    it("rejects ratings outside 1-5", async () => {
+        // a second settled escrow so the reviewer has not used up the review yet
+        await completeEscrows(client, freelancer, 1);
+        receiptPda = await deriveReceiptPDA(client.publicKey, 1);
+
+        for (const rating of [0, 6]) {
+            try {
+                await submitReview(client, rating, freelancerStatsPda);
+                assert.fail("Expected InvalidRating");
+            } catch (err: any) {
+                const msg = err.error?.errorMessage || err.message;
+                assert.strictEqual(msg, "Rating must be between 1 and 5");
+            }
+        }
+    });
+
+    it("fails to review before the escrow is settled", async () => {
+        const refSeed = 10;
+        const amount = new anchor.BN(20_000_000);
+        const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
+
+        await program.methods
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                senderStats: clientStatsPda,
+                clientMultisig: null,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: null,
+                senderTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                associatedTokenProgram: null,
+            })
+            .signers([client])
+            .rpc();
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+
+        // half is released and claimed, the receipt exists but the escrow is still open
+        await program.methods
+            .releasePayment(refSeed, amount.divn(2))
+            .accountsStrict({ client: client.publicKey, escrow: escrowPda, vault: vaultPda })
+            .signers([client])
+            .rpc();
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: freelancerStatsPda,
+                clientStats: clientStatsPda,
+                badge: badgePda,
+                counterparty: await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey),
+                reputationConfig: await ensureReputationConfig(),
+                multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+
+        receiptPda = await deriveReceiptPDA(client.publicKey, 2);
+        const receipt = await program.account.escrowReceipt.fetch(receiptPda);
+        assert.strictEqual(receipt.settledAt.toNumber(), 0);
+
+        try {
+            await submitReview(client, 5, freelancerStatsPda);
+            assert.fail("Expected EscrowNotSettled");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Escrow is not settled yet");
+        }
+    });
+});
@@ -0,0 +1,278 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+import { createHash } from "crypto";
+import * as fs from "fs";
+import * as path from "path";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+// The review window is 7 days, so the auto-release itself can't be reached on a local validator.
+// These tests cover the submission, the dispute and the claim before the window ends; the
+// lapsed window is covered by an escrow fixture (see Anchor.toml).
+describe("submit_work() / dispute_work()", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+    let configPda: PublicKey;
+
+    const refSeed = 101;
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 2);
+    const deliverable = Array.from(createHash("sha256").update("ipfs://landing-page-v1").digest());
+    const revision = Array.from(createHash("sha256").update("ipfs://landing-page-v2").digest());
+
+    const submitWork = async (hash: number[]) => {
+        await program.methods
+            .submitWork(refSeed, hash)
+            .accountsStrict({
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+            })
+            .signers([freelancer])
+            .rpc();
+    };
+
+    const disputeWork = async () => {
+        await program.methods
+            .disputeWork(refSeed)
+            .accountsStrict({
+                sender: client.publicKey,
+                escrow: escrowPda,
+            })
+            .signers([client])
+            .rpc();
+    };
+
+    const claim = async () => {
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: (await deriveUserStatsPDA(freelancer.publicKey)).statsPda,
+                clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey),
+                reputationConfig: configPda,
+                multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 2_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        configPda = await ensureReputationConfig();
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed));
+
+        await fundEscrow(client, freelancer.publicKey, refSeed, amount);
+    });
+
+    it("fails to submit work before accepting the escrow", async () => {
+        await expectError(() => submitWork(deliverable), "Escrow has not been accepted by the receiver");
+    });
+
+    it("fails to dispute when nothing was submitted", async () => {
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+        await expectError(() => disputeWork(), "No work submitted for review");
+    });
+
+    it("records the deliverable and opens the review window", async () => {
+        const before = Math.floor(Date.now() / 1000);
+        await submitWork(deliverable);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.deepEqual(Array.from(escrow.deliverableHash), deliverable);
+        assert.isAtLeast(escrow.reviewEndsAt!.toNumber(), before + 7 * 24 * 60 * 60 - 60);
+        assert.isFalse(escrow.isDisputed);
+    });
+
+    it("fails to claim while the client is still reviewing", async () => {
+        await expectError(() => claim(), "Payment not released yet");
+    });
+
+    it("lets the client dispute the submission once", async () => {
+        await disputeWork();
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.isDisputed);
+
+        await expectError(() => disputeWork(), "Submission already disputed");
+    });
+
+    it("clears the dispute on a resubmission", async () => {
+        await submitWork(revision);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.deepEqual(Array.from(escrow.deliverableHash), revision);
+        assert.isFalse(escrow.isDisputed);
+    });
+
+    it("fails to submit work for a fully released escrow", async () => {
+        // the revision gets disputed too, the client pays it out anyway
+        await disputeWork();
+        await program.methods
+            .releasePayment(refSeed, null)
+            .accountsStrict({
+                client: client.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+            })
+            .signers([client])
+            .rpc();
+
+        await expectError(() => submitWork(revision), "Payment already released");
+    });
+
+    it("records a lost dispute on the client once the disputed work is paid out", async () => {
+        await claim();
+
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        const stats = await program.account.userStats.fetch((await deriveUserStatsPDA(client.publicKey)).statsPda);
+        assert.strictEqual(stats.disputesLost, 1);
+        assert.strictEqual(stats.escrowsPaidOut, 1);
+    });
+});
+
+describe("refunds of submitted work", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+
+    const refSeed = 102;
+    const amount = new anchor.BN(20_000_000);
+
+    const refundAccounts = (sender: PublicKey, escrow: PublicKey, vault: PublicKey) => ({
+        client: sender,
+        escrow,
+        vault,
+        multisig: null,
+        escrowTokenAccount: null,
+        clientTokenAccount: null,
+        tokenMint: null,
+        tokenProgram: null,
+        systemProgram: SystemProgram.programId,
+    });
+
+    const reclaim = (sender: Keypair, escrow: PublicKey, vault: PublicKey, seed: number) =>
+        program.methods
+            .reclaimExpiredEscrow(seed, false)
+            .accountsPartial({
+                ...refundAccounts(sender.publicKey, escrow, vault),
+                badge: null,
+                asset: null,
+                badgeAuthority: null,
+                mplCoreProgram: null,
+            })
+            .signers([sender])
+            .rpc();
+
+    const cancel = async (sender: Keypair, escrow: PublicKey, vault: PublicKey, seed: number) =>
+        program.methods
+            .cancelEscrow(seed, false)
+            .accountsPartial({
+                ...refundAccounts(sender.publicKey, escrow, vault),
+                clientStats: (await deriveUserStatsPDA(sender.publicKey)).statsPda,
+            })
+            .signers([sender])
+            .rpc();
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 2_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed));
+
+        const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3);
+        await fundEscrow(client, freelancer.publicKey, refSeed, amount, { deadline });
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+
+        await program.methods
+            .submitWork(refSeed, Array.from(createHash("sha256").update("ipfs://report").digest()))
+            .accountsStrict({ receiver: freelancer.publicKey, escrow: escrowPda })
+            .signers([freelancer])
+            .rpc();
+        await sleep(5000);
+    });
+
+    it("fails to cancel once work was submitted", async () => {
+        await expectError(() => cancel(client, escrowPda, vaultPda, refSeed), "Escrow already accepted");
+    });
+
+    it("fails to reclaim submitted work past the deadline without a dispute", async () => {
+        await expectError(
+            () => reclaim(client, escrowPda, vaultPda, refSeed),
+            "Submitted work can only be refunded while disputed"
+        );
+    });
+
+    it("reclaims disputed work past the deadline", async () => {
+        await program.methods
+            .disputeWork(refSeed)
+            .accountsStrict({ sender: client.publicKey, escrow: escrowPda })
+            .signers([client])
+            .rpc();
+
+        const clientBefore = await connection.getBalance(client.publicKey);
+        await reclaim(client, escrowPda, vaultPda, refSeed);
+
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        assert.isAbove(await connection.getBalance(client.publicKey), clientBefore);
+    });
+
+    it("treats work whose review window lapsed as released", async () => {
+        // fixture: accepted, work submitted, review window and deadline long past
+        const sender = Keypair.fromSecretKey(
+            Uint8Array.from(
+                JSON.parse(fs.readFileSync(path.join(__dirname, "..", "fixtures", "lapsed_review_sender.json"), "utf8"))
+            )
+        );
+        const lapsedEscrow = new PublicKey("DYnvyYeKYbMQbmKgsSTNTe5sYnWqQQh1csExW29xf3co");
+        const lapsedVault = new PublicKey("6oe54WKAeFBWYvfg6kJiDcXZkpFUmB9xsYb3f43Vm89x");
+        await connection.requestAirdrop(sender.publicKey, 1_000_000_000);
+        await sleep(2000);
+
+        const escrow = await program.account.escrow.fetch(lapsedEscrow);
+        assert.isTrue(escrow.sender.equals(sender.publicKey));
+        assert.strictEqual(escrow.reviewEndsAt!.toNumber(), 1);
+
+        await expectError(() => reclaim(sender, lapsedEscrow, lapsedVault, 0), "Payment already released");
+        assert.strictEqual(await connection.getBalance(lapsedVault), amount.toNumber());
+    });
+});
@@ -0,0 +1,265 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    TOKEN_PROGRAM_ID,
+    getAccount,
+    getAssociatedTokenAddressSync,
+    getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import {
+    acceptEscrow,
+    createFundedMint,
+    deriveCounterpartyPDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+describe("team escrows", () => {
+    let client: Keypair;
+    let lead: Keypair;
+    let designer: Keypair;
+    let outsider: Keypair;
+    let configPda: PublicKey;
+    let clientStatsPda: PublicKey;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+
+    const refSeed = 131;
+    const amount = new anchor.BN(LAMPORTS_PER_SOL);
+
+    const initEscrow = (seed: number, team: { recipient: PublicKey; bps: number }[], mint?: PublicKey) =>
+        fundEscrow(client, lead.publicKey, seed, amount, { team, mint });
+
+    // every recipient claims from the lead's escrow, into their own ATA on SPL escrows
+    const claim = async (recipient: Keypair, seed: number = refSeed, mint: PublicKey | null = null) => {
+        const receiverAta = mint
+            ? (await getOrCreateAssociatedTokenAccount(connection, recipient, mint, recipient.publicKey)).address
+            : null;
+        await program.methods
+            .claimPayment(seed, mint !== null)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: recipient.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: (await deriveUserStatsPDA(recipient.publicKey)).statsPda,
+                clientStats: clientStatsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(recipient.publicKey, client.publicKey),
+                reputationConfig: configPda,
+                multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: recipient.publicKey,
+                escrowTokenAccount: mint && getAssociatedTokenAddressSync(mint, escrowPda, true),
+                receiverTokenAccount: receiverAta,
+                tokenMint: mint,
+                tokenProgram: mint && TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([recipient])
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        lead = Keypair.generate();
+        designer = Keypair.generate();
+        outsider = Keypair.generate();
+
+        await Promise.all(
+            [client, lead, designer, outsider].map((k) => connection.requestAirdrop(k.publicKey, 2_000_000_000))
+        );
+        await sleep(3000);
+
+        configPda = await ensureReputationConfig();
+        ({ statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey));
+    });
+
+    it("fails with splits that don't add up to 10000 bps", async () => {
+        await expectError(
+            () => initEscrow(130, [
+                { recipient: lead.publicKey, bps: 7_000 },
+                { recipient: designer.publicKey, bps: 2_000 },
+            ]),
+            "Team needs 2-5 distinct recipients led by the receiver, with splits adding up to 10000 bps"
+        );
+    });
+
+    it("fails when the receiver doesn't lead the team", async () => {
+        await expectError(
+            () => initEscrow(130, [
+                { recipient: designer.publicKey, bps: 3_000 },
+                { recipient: lead.publicKey, bps: 7_000 },
+            ]),
+            "Team needs 2-5 distinct recipients led by the receiver, with splits adding up to 10000 bps"
+        );
+    });
+
+    describe("70/30 split", () => {
+        before(async () => {
+            ({ escrowPda, vaultPda } = await initEscrow(refSeed, [
+                { recipient: lead.publicKey, bps: 7_000 },
+                { recipient: designer.publicKey, bps: 3_000 },
+            ]));
+            await acceptEscrow(client.publicKey, lead, refSeed);
+
+            await program.methods
+                .releasePayment(refSeed, null)
+                .accountsStrict({
+                    client: client.publicKey,
+                    escrow: escrowPda,
+                    vault: vaultPda,
+                })
+                .signers([client])
+                .rpc();
+        });
+
+        it("stores the splits and a rent reserve in the vault", async () => {
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.strictEqual(escrow.teamSize, 2);
+            assert.deepEqual(escrow.teamBps.slice(0, 2), [7_000, 3_000]);
+
+            const reserve = await connection.getMinimumBalanceForRentExemption(0);
+            assert.strictEqual(await connection.getBalance(vaultPda), amount.toNumber() + reserve);
+        });
+
+        it("fails when someone outside the team claims", async () => {
+            await expectError(() => claim(outsider), "Signer is not a party of this escrow");
+        });
+
+        it("pays a recipient only their share", async () => {
+            const before = await connection.getBalance(designer.publicKey);
+            await claim(designer);
+            const after = await connection.getBalance(designer.publicKey);
+
+            // the recipient pays the stats/counterparty/receipt rent, so only bound the payout
+            assert.isAbove(after - before, amount.toNumber() * 0.29);
+            assert.isBelow(after - before, amount.toNumber() * 0.3 + 1);
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.strictEqual(escrow.teamClaimed[1].toNumber(), amount.toNumber() * 0.3);
+
+            const stats = await program.account.userStats.fetch((await deriveUserStatsPDA(designer.publicKey)).statsPda);
+            assert.strictEqual(stats.completedEscrows, 1);
+
+            await expectError(() => claim(designer), "Nothing released to claim");
+        });
+
+        it("closes the escrow after the last claim and returns the reserve", async () => {
+            await claim(lead);
+
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            assert.strictEqual(await connection.getBalance(vaultPda), 0);
+
+            const stats = await program.account.userStats.fetch((await deriveUserStatsPDA(lead.publicKey)).statsPda);
+            assert.strictEqual(stats.completedEscrows, 1);
+
+            // counted once for the client, paid out to two freelancers
+            const clientStats = await program.account.userStats.fetch(clientStatsPda);
+            assert.strictEqual(clientStats.escrowsPaidOut, 1);
+            assert.strictEqual(clientStats.distinctFreelancers, 2);
+        });
+    });
+
+    describe("partial releases", () => {
+        const seed = 132;
+        const share = (released: number, bps: number) => Math.floor((released * bps) / 10_000);
+
+        const release = async (chunk: anchor.BN | null) => {
+            await program.methods
+                .releasePayment(seed, chunk)
+                .accountsStrict({ client: client.publicKey, escrow: escrowPda, vault: vaultPda })
+                .signers([client])
+                .rpc();
+        };
+
+        before(async () => {
+            ({ escrowPda, vaultPda } = await initEscrow(seed, [
+                { recipient: lead.publicKey, bps: 6_667 },
+                { recipient: designer.publicKey, bps: 3_333 },
+            ]));
+            await acceptEscrow(client.publicKey, lead, seed);
+        });
+
+        it("rounds every share down while the escrow is partially released", async () => {
+            const released = Math.floor(amount.toNumber() / 3);
+            await release(new anchor.BN(released));
+
+            await claim(lead, seed);
+            await claim(designer, seed);
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.strictEqual(escrow.teamClaimed[0].toNumber(), share(released, 6_667));
+            assert.strictEqual(escrow.teamClaimed[1].toNumber(), share(released, 3_333));
+            // the rounding leftover waits for the full release
+            assert.isBelow(escrow.claimedAmount.toNumber(), released);
+        });
+
+        it("gives the lead the leftovers once everything is released", async () => {
+            await release(null);
+
+            await claim(designer, seed);
+            await claim(lead, seed);
+
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+            assert.strictEqual(await connection.getBalance(vaultPda), 0);
+        });
+    });
+
+    describe("SPL splits", () => {
+        const seed = 133;
+        let mint: PublicKey;
+
+        const tokenBalance = async (owner: PublicKey) =>
+            Number((await getAccount(connection, getAssociatedTokenAddressSync(mint, owner, true))).amount);
+
+        before(async () => {
+            mint = await createFundedMint(client, amount.muln(2));
+            ({ escrowPda, vaultPda } = await initEscrow(
+                seed,
+                [
+                    { recipient: lead.publicKey, bps: 7_000 },
+                    { recipient: designer.publicKey, bps: 3_000 },
+                ],
+                mint
+            ));
+            await acceptEscrow(client.publicKey, lead, seed);
+
+            await program.methods
+                .releasePayment(seed, null)
+                .accountsStrict({ client: client.publicKey, escrow: escrowPda, vault: vaultPda })
+                .signers([client])
+                .rpc();
+        });
+
+        it("pays every recipient their share in tokens", async () => {
+            await claim(designer, seed, mint);
+            assert.strictEqual(await tokenBalance(designer.publicKey), amount.toNumber() * 0.3);
+            await expectError(() => claim(designer, seed, mint), "Nothing released to claim");
+
+            await claim(lead, seed, mint);
+            assert.strictEqual(await tokenBalance(lead.publicKey), amount.toNumber() * 0.7);
+            assert.strictEqual(await tokenBalance(escrowPda), 0);
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        });
+
+        it("fails to claim a token escrow as SOL", async () => {
+            ({ escrowPda, vaultPda } = await initEscrow(
+                seed + 1,
+                [
+                    { recipient: lead.publicKey, bps: 5_000 },
+                    { recipient: designer.publicKey, bps: 5_000 },
+                ],
+                mint
+            ));
+            await expectError(() => claim(designer, seed + 1), "Transfer doesn't match the escrow's asset");
+        });
+    });
+});
@@ -0,0 +1,152 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { assert } from "chai";
+import { describe, it } from "mocha";
+
+import { program, connection, sleep } from "../utils/setup";
+import { acceptEscrow, deriveEscrowPDAs, deriveUserStatsPDA } from "../utils/helpers";
+
+describe("top_up_escrow()", () => {
+    let client: Keypair;
+    let freelancer: Keypair;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+
+    const refSeed = 71;
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 2);
+    const topUp = new anchor.BN(LAMPORTS_PER_SOL / 4);
+
+    const topUpEscrow = async (signer: Keypair, value: anchor.BN) => {
+        await program.methods
+            .topUpEscrow(refSeed, value, false)
+            .accountsPartial({
+                sender: signer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                senderTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+    };
+
+    before(async () => {
+        client = Keypair.generate();
+        freelancer = Keypair.generate();
+
+        await Promise.all([
+            connection.requestAirdrop(client.publicKey, 2_000_000_000),
+            connection.requestAirdrop(freelancer.publicKey, 1_000_000_000),
+        ]);
+        await sleep(3000);
+
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed));
+
+        await program.methods
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
+            .accountsPartial({
+                sender: client.publicKey,
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                senderStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                clientMultisig: null,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: null,
+                senderTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                associatedTokenProgram: null,
+            })
+            .signers([client])
+            .rpc();
+
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+    });
+
+    it("adds SOL to the vault and grows the escrow amount", async () => {
+        const vaultBefore = await connection.getBalance(vaultPda);
+
+        await topUpEscrow(client, topUp);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.strictEqual(escrow.amount.toNumber(), amount.add(topUp).toNumber());
+        assert.strictEqual(await connection.getBalance(vaultPda), vaultBefore + topUp.toNumber());
+    });
+
+    it("fails with a zero top-up", async () => {
+        try {
+            await topUpEscrow(client, new anchor.BN(0));
+            assert.fail("Expected InvalidAmountZero");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Amount can't be 0");
+        }
+    });
+
+    it("fails when someone else tops up", async () => {
+        try {
+            await topUpEscrow(freelancer, topUp);
+            assert.fail("Expected seeds constraint failure");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.match(msg, /(seeds|constraint)/i);
+        }
+    });
+
+    it("fails to top up a SOL escrow with tokens", async () => {
+        const mint = await createMint(connection, client, client.publicKey, null, 6);
+        const senderAta = await getOrCreateAssociatedTokenAccount(connection, client, mint, client.publicKey);
+        const escrowAta = await getOrCreateAssociatedTokenAccount(connection, client, mint, escrowPda, true);
+        await mintTo(connection, client, mint, senderAta.address, client, 1_000_000);
+
+        try {
+            await program.methods
+                .topUpEscrow(refSeed, new anchor.BN(1_000_000), true)
+                .accountsPartial({
+                    sender: client.publicKey,
+                    escrow: escrowPda,
+                    vault: vaultPda,
+                    senderTokenAccount: senderAta.address,
+                    escrowTokenAccount: escrowAta.address,
+                    tokenMint: mint,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([client])
+                .rpc();
+            assert.fail("Expected EscrowAssetMismatch");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Transfer doesn't match the escrow's asset");
+        }
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.deepStrictEqual(escrow.asset, { sol: {} });
+        assert.strictEqual(escrow.amount.toNumber(), amount.add(topUp).toNumber());
+    });
+
+    it("fails once the escrow is fully released", async () => {
+        await program.methods
+            .releasePayment(refSeed, null)
+            .accountsStrict({
+                client: client.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+            })
+            .signers([client])
+            .rpc();
+
+        try {
+            await topUpEscrow(client, topUp);
+            assert.fail("Expected AlreadyReleased");
+        } catch (err: any) {
+            const msg = err.error?.errorMessage || err.message;
+            assert.strictEqual(msg, "Payment already released");
+        }
+    });
+});
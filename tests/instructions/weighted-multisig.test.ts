@@ -0,0 +1,169 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it, before } from "mocha";
+
+import { program, createFundedKeypair } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveReceiptPDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+    fundEscrow,
+} from "../utils/helpers";
+
+describe("weighted multisig", () => {
+    const refSeed = 59;
+    const amount = new anchor.BN(2_000_000);
+
+    // the founder's vote alone meets the threshold, the two partners together don't
+    const weights = [3, 1, 1, 0, 0];
+    const threshold = 3;
+
+    let client: Keypair;
+    let freelancer: Keypair;
+    let partner1: Keypair;
+    let partner2: Keypair;
+
+    let multisigPda: PublicKey;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+    let configPda: PublicKey;
+
+    const deriveMultisigPDA = (owner: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from("client_multisig"), owner.toBuffer()], program.programId)[0];
+
+    const initMultisig = (owner: Keypair, members: PublicKey[], memberThreshold: number, memberWeights: number[]) =>
+        program.methods
+            .initializeMultisigClient(members, 3, memberThreshold, null, memberWeights)
+            .accountsPartial({
+                client: owner.publicKey,
+                multisig: deriveMultisigPDA(owner.publicKey),
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([owner])
+            .rpc();
+
+    const membersOf = (owner: Keypair) => [
+        owner.publicKey,
+        Keypair.generate().publicKey,
+        Keypair.generate().publicKey,
+        PublicKey.default,
+        PublicKey.default,
+    ];
+
+    const approve = (member: Keypair) =>
+        program.methods
+            .approveMultisigRelease(refSeed)
+            .accountsStrict({
+                member: member.publicKey,
+                multisig: multisigPda,
+                escrow: escrowPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([member])
+            .rpc();
+
+    const claim = async () => {
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: (await deriveUserStatsPDA(freelancer.publicKey)).statsPda,
+                clientStats: (await deriveUserStatsPDA(client.publicKey)).statsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey),
+                reputationConfig: configPda,
+                receipt: await deriveReceiptPDA(client.publicKey, escrow.escrowId),
+                multisig: multisigPda,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+    };
+
+    before(async () => {
+        client = await createFundedKeypair();
+        freelancer = await createFundedKeypair();
+        partner1 = await createFundedKeypair();
+        partner2 = await createFundedKeypair();
+        configPda = await ensureReputationConfig();
+
+        multisigPda = deriveMultisigPDA(client.publicKey);
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed));
+    });
+
+    describe("initialize", () => {
+        it("fails when the threshold exceeds the total weight", async () => {
+            const owner = await createFundedKeypair();
+            await expectError(
+                () => initMultisig(owner, membersOf(owner), 6, weights),
+                "Multisig threshold exceeds the members' total weight"
+            );
+        });
+
+        it("fails when an active member has no weight", async () => {
+            const owner = await createFundedKeypair();
+            await expectError(
+                () => initMultisig(owner, membersOf(owner), 2, [2, 0, 1, 0, 0]),
+                "Invalid multisig config"
+            );
+        });
+
+        it("stores the member weights", async () => {
+            const members = [
+                client.publicKey,
+                partner1.publicKey,
+                partner2.publicKey,
+                PublicKey.default,
+                PublicKey.default,
+            ];
+            await initMultisig(client, members, threshold, weights);
+
+            const multisig = await program.account.clientMultisig.fetch(multisigPda);
+            assert.deepEqual(Array.from(multisig.weights), weights);
+            assert.strictEqual(multisig.threshold, threshold);
+        });
+    });
+
+    describe("approve / claim", () => {
+        before(async () => {
+            await fundEscrow(client, freelancer.publicKey, refSeed, amount, { multisig: multisigPda });
+
+            await acceptEscrow(client.publicKey, freelancer, refSeed);
+        });
+
+        it("doesn't release when the approved weight is below the threshold", async () => {
+            await approve(partner1);
+            await approve(partner2);
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isFalse(escrow.isReleased);
+            await expectError(claim, "Multisig threshold is not met, can't withdraw amount");
+        });
+
+        it("releases once the founder's weight is added", async () => {
+            await approve(client);
+
+            const escrow = await program.account.escrow.fetch(escrowPda);
+            assert.isTrue(escrow.isReleased);
+
+            await claim();
+            assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        });
+    });
+});
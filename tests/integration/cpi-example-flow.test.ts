@@ -0,0 +1,161 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ComputeBudgetProgram, Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it, before } from "mocha";
+
+import { BondrCpiExample } from "../../target/types/bondr_cpi_example";
+import { program, connection, createFundedKeypair, airdropSol } from "../utils/setup";
+import {
+    NO_TERMS,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveReceiptPDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+    expectError,
+} from "../utils/helpers";
+
+// Both parties are PDAs of another program: agents of bondr_cpi_example fund, accept,
+// release and claim the escrow through CPI.
+describe("CPI Example Flow Integration", () => {
+    const example = anchor.workspace.BondrCpiExample as Program<BondrCpiExample>;
+    const refSeed = 63;
+    const amount = new anchor.BN(50_000_000);
+
+    let clientAuthority: Keypair;
+    let workerAuthority: Keypair;
+
+    let clientAgent: PublicKey;
+    let workerAgent: PublicKey;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+    let configPda: PublicKey;
+
+    const deriveAgentPDA = (authority: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from("agent"), authority.toBuffer()], example.programId)[0];
+
+    const release = (authority: Keypair) =>
+        example.methods
+            .releaseEscrow(refSeed)
+            .accountsStrict({
+                authority: authority.publicKey,
+                agent: deriveAgentPDA(authority.publicKey),
+                escrow: escrowPda,
+                vault: vaultPda,
+                bondrProgram: program.programId,
+            })
+            .signers([authority])
+            .rpc();
+
+    before(async () => {
+        clientAuthority = await createFundedKeypair();
+        workerAuthority = await createFundedKeypair();
+        configPda = await ensureReputationConfig();
+
+        // agents pay for everything they create in Bondr
+        clientAgent = deriveAgentPDA(clientAuthority.publicKey);
+        workerAgent = deriveAgentPDA(workerAuthority.publicKey);
+        await airdropSol(clientAgent);
+        await airdropSol(workerAgent);
+
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(clientAgent, workerAgent, refSeed));
+    });
+
+    it("funds an escrow with a PDA sender and returns its id", async () => {
+        const fund = example.methods.fundEscrow(amount, refSeed).accountsStrict({
+            authority: clientAuthority.publicKey,
+            agent: clientAgent,
+            receiver: workerAgent,
+            escrow: escrowPda,
+            vault: vaultPda,
+            senderStats: (await deriveUserStatsPDA(clientAgent)).statsPda,
+            bondrProgram: program.programId,
+            systemProgram: SystemProgram.programId,
+        });
+
+        // the escrow id comes back as Bondr's return data
+        const simulation = await fund.signers([clientAuthority]).simulate();
+        assert.include(simulation.raw, "Program log: Funded Bondr escrow 0");
+
+        await fund.signers([clientAuthority]).rpc();
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.sender.equals(clientAgent));
+        assert.isTrue(escrow.receiver.equals(workerAgent));
+        assert.strictEqual(escrow.escrowId.toNumber(), 0);
+        assert.strictEqual(escrow.amount.toNumber(), amount.toNumber());
+    });
+
+    it("accepts with a PDA receiver", async () => {
+        await example.methods
+            .acceptEscrow(refSeed, NO_TERMS)
+            .accountsStrict({
+                authority: workerAuthority.publicKey,
+                agent: workerAgent,
+                escrow: escrowPda,
+                bondrProgram: program.programId,
+            })
+            .signers([workerAuthority])
+            .rpc();
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.isAccepted);
+    });
+
+    it("fails to release through another authority's agent", async () => {
+        const stranger = await createFundedKeypair();
+        await airdropSol(deriveAgentPDA(stranger.publicKey));
+
+        await expectError(() => release(stranger), "Unauthorised sender");
+    });
+
+    it("releases with a PDA releaser", async () => {
+        await release(clientAuthority);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.isReleased);
+    });
+
+    it("claims into the receiving agent", async () => {
+        const agentBefore = await connection.getBalance(workerAgent);
+
+        await example.methods
+            .claimEscrow(refSeed)
+            .accountsStrict({
+                authority: workerAuthority.publicKey,
+                agent: workerAgent,
+                client: clientAgent,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: (await deriveUserStatsPDA(workerAgent)).statsPda,
+                clientStats: (await deriveUserStatsPDA(clientAgent)).statsPda,
+                counterparty: await deriveCounterpartyPDA(workerAgent, clientAgent),
+                reputationConfig: configPda,
+                receipt: await deriveReceiptPDA(clientAgent, 0),
+                payoutDestination: PublicKey.findProgramAddressSync(
+                    [Buffer.from("payout_destination"), workerAgent.toBuffer()],
+                    program.programId
+                )[0],
+                bondrProgram: program.programId,
+                systemProgram: SystemProgram.programId,
+            })
+            .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+            .signers([workerAuthority])
+            .rpc();
+
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+
+        // the payout minus the rent of the agent's stats, counterparty record and receipt
+        const agentAfter = await connection.getBalance(workerAgent);
+        assert.isAbove(agentAfter, agentBefore);
+        assert.isBelow(agentAfter, agentBefore + amount.toNumber());
+
+        const receipt = await program.account.escrowReceipt.fetch(await deriveReceiptPDA(clientAgent, 0));
+        assert.isTrue(receipt.receiver.equals(workerAgent));
+        assert.strictEqual(receipt.amount.toNumber(), amount.toNumber());
+
+        const workerStats = await program.account.userStats.fetch((await deriveUserStatsPDA(workerAgent)).statsPda);
+        assert.strictEqual(workerStats.completedEscrows, 1);
+    });
+});
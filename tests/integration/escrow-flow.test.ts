@@ -4,10 +4,23 @@ import { assert } from "chai";
 import { describe, it } from "mocha";
 
 import { program, createFundedKeypair } from "../utils/setup";
-import { deriveEscrowPDAs, deriveUserStatsPDA, deriveFreelancerBadgePDA } from "../utils/helpers";
+import {
+    acceptEscrow,
+    deriveEscrowPDAs,
+    deriveUserStatsPDA,
+    deriveFreelancerBadgePDA,
+    deriveCounterpartyPDA,
+    ensureReputationConfig,
+} from "../utils/helpers";
 import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
 
 describe("Complete Escrow Flow Integration", () => {
+    let configPda: PublicKey;
+
+    before(async () => {
+        configPda = await ensureReputationConfig();
+    });
+
     // MPL Core program ID
     const MPL_PROGRAM_ID = new PublicKey(MPL_CORE_PROGRAM_ID);
 
@@ -21,6 +34,7 @@ describe("Complete Escrow Flow Integration", () => {
         const { statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey);
         const { statsPda: freelancerStatsPda } = await deriveUserStatsPDA(freelancer.publicKey);
         const { badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey);
+        const counterpartyPda = await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey);
 
         // 1. Initialize freelancer badge
         await program.methods
@@ -39,7 +53,7 @@ describe("Complete Escrow Flow Integration", () => {
 
         // 2. Initialize escrow
         await program.methods
-            .initializeEscrow(amount, refSeed, false, false)
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
             .accountsPartial({
                 sender: client.publicKey,
                 receiver: freelancer.publicKey,
@@ -57,6 +71,8 @@ describe("Complete Escrow Flow Integration", () => {
             .signers([client])
             .rpc();
 
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+
         let escrowAccount = await program.account.escrow.fetch(escrowPda);
         assert.isFalse(escrowAccount.isReleased);
 
@@ -65,10 +81,11 @@ describe("Complete Escrow Flow Integration", () => {
 
         // 3. Release payment
         await program.methods
-            .releasePayment(refSeed)
+            .releasePayment(refSeed, null)
             .accountsStrict({
                 client: client.publicKey,
                 escrow: escrowPda,
+                vault: vaultPda,
             })
             .signers([client])
             .rpc();
@@ -85,7 +102,13 @@ describe("Complete Escrow Flow Integration", () => {
                 escrow: escrowPda,
                 vault: vaultPda,
                 receiverStats: freelancerStatsPda,
+                clientStats: clientStatsPda,
+                badge: badgePda,
+                counterparty: counterpartyPda,
+                reputationConfig: configPda,
                 multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
                 receiverSol: freelancer.publicKey,
                 escrowTokenAccount: null,
                 receiverTokenAccount: null,
@@ -100,16 +123,6 @@ describe("Complete Escrow Flow Integration", () => {
         const freelancerStats = await program.account.userStats.fetch(freelancerStatsPda);
         assert.strictEqual(freelancerStats.completedEscrows, 1);
 
-        // 5. Update freelancer badge
-        await program.methods
-            .updateFreelancerBadge(amount)
-            .accountsStrict({
-                freelancer: freelancer.publicKey,
-                badge: badgePda,
-            })
-            .signers([freelancer])
-            .rpc();
-
         badge = await program.account.freelancerBadge.fetch(badgePda);
         assert.strictEqual(badge.completedEscrows, 1);
         assert.strictEqual(badge.totalValueCompleted.toNumber(), amount.toNumber());
@@ -125,6 +138,7 @@ describe("Complete Escrow Flow Integration", () => {
                 .accountsStrict({
                     freelancer: freelancer.publicKey,
                     badge: badgePda,
+                    config: configPda,
                     asset: asset.publicKey,
                     collection: collection.publicKey,
                     mplCoreProgram: MPL_PROGRAM_ID,
@@ -149,10 +163,11 @@ describe("Complete Escrow Flow Integration", () => {
     it("should handle multiple escrows and tier progression", async () => {
         const client = await createFundedKeypair();
         const freelancer = await createFundedKeypair(1_000_000_000);
-        const amount = new anchor.BN(2_000_000);
+        const amount = new anchor.BN(20_000_000);
 
         // Initialize badge
         const { badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey);
+        const counterpartyPda = await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey);
         await program.methods
             .initializeFreelancerBadge()
             .accountsStrict({
@@ -170,9 +185,9 @@ describe("Complete Escrow Flow Integration", () => {
             const { statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey);
             const { statsPda: freelancerStatsPda } = await deriveUserStatsPDA(freelancer.publicKey);
 
-            // Initialize → Release → Claim → Update Badge
+            // Initialize → Release → Claim (claim records the badge)
             await program.methods
-                .initializeEscrow(amount, refSeed, false, false)
+                .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
                 .accountsPartial({
                     sender: client.publicKey,
                     receiver: freelancer.publicKey,
@@ -190,11 +205,14 @@ describe("Complete Escrow Flow Integration", () => {
                 .signers([client])
                 .rpc();
 
+            await acceptEscrow(client.publicKey, freelancer, refSeed);
+
             await program.methods
-                .releasePayment(refSeed)
+                .releasePayment(refSeed, null)
                 .accountsStrict({
                     client: client.publicKey,
                     escrow: escrowPda,
+                    vault: vaultPda,
                 })
                 .signers([client])
                 .rpc();
@@ -207,7 +225,13 @@ describe("Complete Escrow Flow Integration", () => {
                     escrow: escrowPda,
                     vault: vaultPda,
                     receiverStats: freelancerStatsPda,
+                    clientStats: clientStatsPda,
+                    badge: badgePda,
+                    counterparty: counterpartyPda,
+                    reputationConfig: configPda,
                     multisig: null,
+                    payoutApproval: null,
+                    payoutMultisig: null,
                     receiverSol: freelancer.publicKey,
                     escrowTokenAccount: null,
                     receiverTokenAccount: null,
@@ -217,25 +241,16 @@ describe("Complete Escrow Flow Integration", () => {
                 })
                 .signers([freelancer])
                 .rpc();
-
-            await program.methods
-                .updateFreelancerBadge(amount)
-                .accountsStrict({
-                    freelancer: freelancer.publicKey,
-                    badge: badgePda,
-                })
-                .signers([freelancer])
-                .rpc();
         }
 
         // Fetch the badge and verify completed escrows
         const badge = await program.account.freelancerBadge.fetch(badgePda);
 
-        // The completed_escrows counter should only increment after releasePayment + claimPayment + updateFreelancerBadge for each escrow
+        // The completed_escrows counter should only increment after releasePayment + claimPayment for each escrow
         assert.strictEqual(
             badge.completedEscrows,
             5,
-            "completedEscrows should reflect actual completed escrows (release+claim+update), not just escrow creations"
+            "completedEscrows should reflect actual completed escrows (release+claim), not just escrow creations"
         );
 
         assert.deepStrictEqual(
@@ -261,6 +276,7 @@ describe("Complete Escrow Flow Integration", () => {
             .accountsStrict({
                 freelancer: freelancer.publicKey,
                 badge: badgePda,
+                config: configPda,
                 asset: asset.publicKey,
                 collection: collection.publicKey,
                 mplCoreProgram: MPL_PROGRAM_ID,
@@ -281,10 +297,11 @@ describe("Complete Escrow Flow Integration", () => {
     it("should reach Professional tier after 10 completed escrows", async () => {
         const client = await createFundedKeypair();
         const freelancer = await createFundedKeypair(1_000_000_000);
-        const amount = new anchor.BN(2_000_000);
+        const amount = new anchor.BN(20_000_000);
 
         // Initialize badge
         const { badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey);
+        const counterpartyPda = await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey);
         await program.methods
             .initializeFreelancerBadge()
             .accountsStrict({
@@ -302,9 +319,9 @@ describe("Complete Escrow Flow Integration", () => {
             const { statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey);
             const { statsPda: freelancerStatsPda } = await deriveUserStatsPDA(freelancer.publicKey);
 
-            // Initialize → Release → Claim → Update Badge
+            // Initialize → Release → Claim (claim records the badge)
             await program.methods
-                .initializeEscrow(amount, refSeed, false, false)
+                .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
                 .accountsPartial({
                     sender: client.publicKey,
                     receiver: freelancer.publicKey,
@@ -322,11 +339,14 @@ describe("Complete Escrow Flow Integration", () => {
                 .signers([client])
                 .rpc();
 
+            await acceptEscrow(client.publicKey, freelancer, refSeed);
+
             await program.methods
-                .releasePayment(refSeed)
+                .releasePayment(refSeed, null)
                 .accountsStrict({
                     client: client.publicKey,
                     escrow: escrowPda,
+                    vault: vaultPda,
                 })
                 .signers([client])
                 .rpc();
@@ -339,7 +359,13 @@ describe("Complete Escrow Flow Integration", () => {
                     escrow: escrowPda,
                     vault: vaultPda,
                     receiverStats: freelancerStatsPda,
+                    clientStats: clientStatsPda,
+                    badge: badgePda,
+                    counterparty: counterpartyPda,
+                    reputationConfig: configPda,
                     multisig: null,
+                    payoutApproval: null,
+                    payoutMultisig: null,
                     receiverSol: freelancer.publicKey,
                     escrowTokenAccount: null,
                     receiverTokenAccount: null,
@@ -349,15 +375,6 @@ describe("Complete Escrow Flow Integration", () => {
                 })
                 .signers([freelancer])
                 .rpc();
-
-            await program.methods
-                .updateFreelancerBadge(amount)
-                .accountsStrict({
-                    freelancer: freelancer.publicKey,
-                    badge: badgePda,
-                })
-                .signers([freelancer])
-                .rpc();
         }
 
         // Fetch the badge and verify completed escrows
@@ -392,6 +409,7 @@ describe("Complete Escrow Flow Integration", () => {
             .accountsStrict({
                 freelancer: freelancer.publicKey,
                 badge: badgePda,
+                config: configPda,
                 asset: asset.publicKey,
                 collection: collection.publicKey,
                 mplCoreProgram: MPL_PROGRAM_ID,
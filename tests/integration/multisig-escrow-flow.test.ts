@@ -5,9 +5,22 @@ import { assert } from "chai";
 import { describe, it } from "mocha";
 
 import { program, createFundedKeypair, connection, sleep } from "../utils/setup";
-import { deriveEscrowPDAs, deriveUserStatsPDA, deriveFreelancerBadgePDA } from "../utils/helpers";
+import {
+    acceptEscrow,
+    deriveEscrowPDAs,
+    deriveUserStatsPDA,
+    deriveFreelancerBadgePDA,
+    deriveCounterpartyPDA,
+    ensureReputationConfig,
+} from "../utils/helpers";
 
 describe("Multisig Full Flow Integration", () => {
+    let configPda: PublicKey;
+
+    before(async () => {
+        configPda = await ensureReputationConfig();
+    });
+
     it("should run full multisig lifecycle: init ms → init escrow → approvals → claim → resets + stats", async () => {
         // Actors
         const client = await createFundedKeypair();
@@ -28,6 +41,8 @@ describe("Multisig Full Flow Integration", () => {
         const { escrowPda, vaultPda } = await deriveEscrowPDAs(client.publicKey, freelancer.publicKey, refSeed);
 
         const { badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey);
+        const counterpartyPda = await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey);
+
         await program.methods
             .initializeFreelancerBadge()
             .accountsStrict({
@@ -55,7 +70,7 @@ describe("Multisig Full Flow Integration", () => {
         const threshold = 2; // 2 of 4
 
         await program.methods
-            .initializeMultisigClient(members, memberCount, threshold)
+            .initializeMultisigClient(members, memberCount, threshold, null, null)
             .accountsStrict({
                 client: client.publicKey,
                 multisig: multisigPda,
@@ -73,7 +88,7 @@ describe("Multisig Full Flow Integration", () => {
 
         // 2) Initialize escrow (multisig enabled)
         await program.methods
-            .initializeEscrow(amount, refSeed, false, true)
+            .initializeEscrow(amount, refSeed, false, true, null, null, null, null, null)
             .accountsPartial({
                 sender: client.publicKey,
                 receiver: freelancer.publicKey,
@@ -91,6 +106,8 @@ describe("Multisig Full Flow Integration", () => {
             .signers([client])
             .rpc();
 
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+
         let escrow = await program.account.escrow.fetch(escrowPda);
         assert.ok(escrow.sender.equals(client.publicKey));
         assert.ok(escrow.receiver.equals(freelancer.publicKey));
@@ -138,6 +155,7 @@ describe("Multisig Full Flow Integration", () => {
 
         // 5) Freelancer claims payment
         const { statsPda: receiverStatsPda } = await deriveUserStatsPDA(freelancer.publicKey);
+        const { statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey);
 
         await program.methods
             .claimPayment(refSeed, false)
@@ -147,7 +165,13 @@ describe("Multisig Full Flow Integration", () => {
                 escrow: escrowPda,
                 vault: vaultPda,
                 receiverStats: receiverStatsPda,
+                clientStats: clientStatsPda,
+                badge: badgePda,
+                counterparty: counterpartyPda,
+                reputationConfig: configPda,
                 multisig: multisigPda,
+                payoutApproval: null,
+                payoutMultisig: null,
                 receiverSol: freelancer.publicKey,
                 escrowTokenAccount: null,
                 receiverTokenAccount: null,
@@ -158,26 +182,16 @@ describe("Multisig Full Flow Integration", () => {
             .signers([freelancer])
             .rpc();
 
-        // 6) Stats updated
+        // 6) Stats and badge updated by the claim
         const freelancerStats = await program.account.userStats.fetch(receiverStatsPda);
         assert.strictEqual(freelancerStats.completedEscrows, 1);
 
-        // 7) Update freelancer badge after completion
-        await program.methods
-            .updateFreelancerBadge(amount)
-            .accountsStrict({
-                freelancer: freelancer.publicKey,
-                badge: badgePda,
-            })
-            .signers([freelancer])
-            .rpc();
-
         let badge = await program.account.freelancerBadge.fetch(badgePda);
         assert.strictEqual(badge.completedEscrows, 1);
         assert.strictEqual(badge.totalValueCompleted.toNumber(), amount.toNumber());
         assert.deepStrictEqual(badge.tier, { unranked: {} });
 
-        // 8) Attempt to mint reputation NFT (should fail after only 1 escrow)
+        // 7) Attempt to mint reputation NFT (should fail after only 1 escrow)
         const MPL_PROGRAM_ID = new PublicKey(MPL_CORE_PROGRAM_ID);
         const asset = Keypair.generate();
         const collection = Keypair.generate();
@@ -188,6 +202,7 @@ describe("Multisig Full Flow Integration", () => {
                 .accountsStrict({
                     freelancer: freelancer.publicKey,
                     badge: badgePda,
+                    config: configPda,
                     asset: asset.publicKey,
                     collection: collection.publicKey,
                     mplCoreProgram: MPL_PROGRAM_ID,
@@ -209,7 +224,7 @@ describe("Multisig Full Flow Integration", () => {
         badge = await program.account.freelancerBadge.fetch(badgePda);
         assert.deepStrictEqual(badge.tier, { unranked: {} });
 
-        // 9) Post assertions
+        // 8) Post assertions
         const vaultInfoAfter = await connection.getAccountInfo(vaultPda);
         const vaultLamportsAfter = vaultInfoAfter?.lamports ?? 0;
         assert.strictEqual(vaultLamportsAfter, 0, "vault should be drained to 0 after claim");
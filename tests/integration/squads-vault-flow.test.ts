@@ -0,0 +1,129 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import { describe, it, before } from "mocha";
+
+import { program, connection, createFundedKeypair, airdropSol } from "../utils/setup";
+import {
+    acceptEscrow,
+    deriveCounterpartyPDA,
+    deriveEscrowPDAs,
+    deriveReceiptPDA,
+    deriveUserStatsPDA,
+    ensureReputationConfig,
+} from "../utils/helpers";
+import { createSquadsMultisig, executeVaultTransaction } from "../utils/squads";
+
+// The client is a Squads v4 vault: Bondr instructions run as CPIs signed by the vault PDA,
+// without the Bondr ClientMultisig being involved.
+describe("Squads Vault Flow Integration", () => {
+    const refSeed = 62;
+    const amount = new anchor.BN(5_000_000);
+
+    let member1: Keypair;
+    let member2: Keypair;
+    let freelancer: Keypair;
+
+    let squadsMultisig: PublicKey;
+    let squadsVault: PublicKey;
+    let escrowPda: PublicKey;
+    let vaultPda: PublicKey;
+    let configPda: PublicKey;
+
+    before(async () => {
+        member1 = await createFundedKeypair();
+        member2 = await createFundedKeypair();
+        freelancer = await createFundedKeypair(1_000_000_000);
+        configPda = await ensureReputationConfig();
+
+        // 2-of-2 Squads multisig, its vault funds the escrow
+        ({ multisig: squadsMultisig, vault: squadsVault } = await createSquadsMultisig([member1, member2], 2));
+        await airdropSol(squadsVault);
+
+        ({ escrowPda, vaultPda } = await deriveEscrowPDAs(squadsVault, freelancer.publicKey, refSeed));
+    });
+
+    it("creates an escrow from a Squads vault transaction", async () => {
+        const vaultBefore = await connection.getBalance(squadsVault);
+
+        const ix = await program.methods
+            .initializeEscrow(amount, refSeed, false, false, null, null, null, null, null)
+            .accountsPartial({
+                sender: squadsVault,
+                receiver: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                senderStats: (await deriveUserStatsPDA(squadsVault)).statsPda,
+                clientMultisig: null,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: null,
+                senderTokenAccount: null,
+                escrowTokenAccount: null,
+                tokenMint: null,
+                associatedTokenProgram: null,
+            })
+            .instruction();
+        await executeVaultTransaction(squadsMultisig, [member1, member2], [ix]);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.sender.equals(squadsVault));
+        assert.isNull(escrow.clientMultisig);
+        assert.strictEqual(escrow.amount.toNumber(), amount.toNumber());
+
+        // the vault paid the deposit and the rent, the members only paid Squads fees
+        const vaultAfter = await connection.getBalance(squadsVault);
+        assert.isAtLeast(vaultBefore - vaultAfter, amount.toNumber());
+    });
+
+    it("releases from a Squads vault transaction", async () => {
+        await acceptEscrow(squadsVault, freelancer, refSeed);
+
+        const ix = await program.methods
+            .releasePayment(refSeed, null)
+            .accountsStrict({ client: squadsVault, escrow: escrowPda, vault: vaultPda })
+            .instruction();
+        await executeVaultTransaction(squadsMultisig, [member1, member2], [ix]);
+
+        const escrow = await program.account.escrow.fetch(escrowPda);
+        assert.isTrue(escrow.isReleased);
+    });
+
+    it("lets the freelancer claim and returns the rent to the Squads vault", async () => {
+        const freelancerBefore = await connection.getBalance(freelancer.publicKey);
+        const squadsVaultBefore = await connection.getBalance(squadsVault);
+        const escrow = await program.account.escrow.fetch(escrowPda);
+
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: squadsVault,
+                freelancer: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: (await deriveUserStatsPDA(freelancer.publicKey)).statsPda,
+                clientStats: (await deriveUserStatsPDA(squadsVault)).statsPda,
+                badge: null,
+                counterparty: await deriveCounterpartyPDA(freelancer.publicKey, squadsVault),
+                reputationConfig: configPda,
+                receipt: await deriveReceiptPDA(squadsVault, escrow.escrowId),
+                multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+
+        assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+        assert.isAbove(await connection.getBalance(freelancer.publicKey), freelancerBefore);
+        assert.isAbove(await connection.getBalance(squadsVault), squadsVaultBefore);
+
+        const clientStats = await program.account.userStats.fetch((await deriveUserStatsPDA(squadsVault)).statsPda);
+        assert.strictEqual(clientStats.completedEscrows, 1);
+    });
+});
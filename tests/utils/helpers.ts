@@ -1,5 +1,19 @@
-import { PublicKey } from "@solana/web3.js";
-import { program } from "./setup";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    TOKEN_PROGRAM_ID,
+    createMint,
+    getAssociatedTokenAddressSync,
+    getOrCreateAssociatedTokenAccount,
+    mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { program, connection } from "./setup";
+
+export const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+    "BPFLoaderUpgradeab1e11111111111111111111111"
+);
 
 export interface EscrowPDAs {
     escrowPda: PublicKey;
@@ -47,6 +61,55 @@ export const deriveEscrowPDAs = async (
     return { escrowPda, escrowBump, vaultPda, vaultBump };
 };
 
+// Retainer vaults share the escrow vault prefix but are keyed by the retainer itself
+export const deriveRetainerPDAs = async (
+    sender: PublicKey,
+    receiver: PublicKey,
+    refSeed: number
+): Promise<{ retainerPda: PublicKey; vaultPda: PublicKey }> => {
+    const [retainerPda] = await PublicKey.findProgramAddress(
+        [
+            Buffer.from("retainer"),
+            sender.toBuffer(),
+            receiver.toBuffer(),
+            Buffer.from([refSeed]),
+        ],
+        program.programId
+    );
+
+    const [vaultPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("vault"), retainerPda.toBuffer()],
+        program.programId
+    );
+
+    return { retainerPda, vaultPda };
+};
+
+export const deriveJobPDAs = async (
+    client: PublicKey,
+    refSeed: number
+): Promise<{ jobPda: PublicKey; vaultPda: PublicKey }> => {
+    const [jobPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("job"), client.toBuffer(), Buffer.from([refSeed])],
+        program.programId
+    );
+
+    const [vaultPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("vault"), jobPda.toBuffer()],
+        program.programId
+    );
+
+    return { jobPda, vaultPda };
+};
+
+export const deriveBidPDA = async (job: PublicKey, freelancer: PublicKey): Promise<PublicKey> => {
+    const [bidPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("bid"), job.toBuffer(), freelancer.toBuffer()],
+        program.programId
+    );
+    return bidPda;
+};
+
 export const deriveUserStatsPDA = async (user: PublicKey): Promise<UserStatsPDA> => {
     const [statsPda, statsBump] = await PublicKey.findProgramAddress(
         [Buffer.from("user_stats"), user.toBuffer()],
@@ -63,6 +126,19 @@ export const deriveFreelancerBadgePDA = async (freelancer: PublicKey): Promise<F
     return { badgePda, badgeBump };
 };
 
+export interface ClientBadgePDA {
+    clientBadgePda: PublicKey;
+    clientBadgeBump: number;
+}
+
+export const deriveClientBadgePDA = async (client: PublicKey): Promise<ClientBadgePDA> => {
+    const [clientBadgePda, clientBadgeBump] = await PublicKey.findProgramAddress(
+        [Buffer.from("client_badge"), client.toBuffer()],
+        program.programId
+    );
+    return { clientBadgePda, clientBadgeBump };
+};
+
 export interface BadgeAuthorityPDA {
     authorityPda: PublicKey;
     authorityBump: number;
@@ -74,4 +150,231 @@ export const deriveBadgeAuthorityPDA = async (): Promise<BadgeAuthorityPDA> => {
         program.programId
     );
     return { authorityPda, authorityBump };
-}; 
\ No newline at end of file
+}; 
+export interface ReputationConfigPDA {
+    configPda: PublicKey;
+    configBump: number;
+}
+
+export const deriveReputationConfigPDA = async (): Promise<ReputationConfigPDA> => {
+    const [configPda, configBump] = await PublicKey.findProgramAddress(
+        [Buffer.from("reputation_config")],
+        program.programId
+    );
+    return { configPda, configBump };
+};
+
+// ProgramData account of the upgradeable loader, holds the upgrade authority
+export const deriveProgramDataAddress = async (): Promise<PublicKey> => {
+    const [programData] = await PublicKey.findProgramAddress(
+        [program.programId.toBuffer()],
+        BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    );
+    return programData;
+};
+
+// Reputation config is a singleton, every suite that mints or claims shares it.
+// The provider wallet is the upgrade authority on localnet, so it becomes the admin.
+export const ensureReputationConfig = async (): Promise<PublicKey> => {
+    const { configPda } = await deriveReputationConfigPDA();
+    const existing = await program.provider.connection.getAccountInfo(configPda);
+    if (existing) {
+        return configPda;
+    }
+
+    await program.methods
+        .initializeReputationConfig()
+        .accountsPartial({
+            admin: program.provider.publicKey,
+            config: configPda,
+            program: program.programId,
+            programData: await deriveProgramDataAddress(),
+            systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    return configPda;
+};
+
+export const deriveCounterpartyPDA = async (
+    freelancer: PublicKey,
+    client: PublicKey
+): Promise<PublicKey> => {
+    const [counterpartyPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("counterparty"), freelancer.toBuffer(), client.toBuffer()],
+        program.programId
+    );
+    return counterpartyPda;
+};
+
+// Receipts are keyed by the sender's escrow counter, escrow seeds are reused after claim
+export const deriveReceiptPDA = async (
+    sender: PublicKey,
+    escrowId: number | anchor.BN
+): Promise<PublicKey> => {
+    const [receiptPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("receipt"), sender.toBuffer(), new anchor.BN(escrowId).toArrayLike(Buffer, "le", 8)],
+        program.programId
+    );
+    return receiptPda;
+};
+
+export const deriveReviewPDA = async (receipt: PublicKey, reviewer: PublicKey): Promise<PublicKey> => {
+    const [reviewPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("review"), receipt.toBuffer(), reviewer.toBuffer()],
+        program.programId
+    );
+    return reviewPda;
+};
+
+// terms hash of escrows funded without off-chain terms
+export const NO_TERMS: number[] = Array(32).fill(0);
+
+// Asserts that `fn` fails with exactly the given program error message.
+export const expectError = async (fn: () => Promise<unknown>, expected: string): Promise<void> => {
+    try {
+        await fn();
+        assert.fail(`Expected "${expected}"`);
+    } catch (err: any) {
+        const msg = err.error?.errorMessage || err.message;
+        assert.strictEqual(msg, expected);
+    }
+};
+
+// Creates a 6-decimal mint owned by `authority` and mints `amount` into the authority's ATA.
+export const createFundedMint = async (authority: Keypair, amount: number | anchor.BN): Promise<PublicKey> => {
+    const mint = await createMint(connection, authority, authority.publicKey, null, 6);
+    const ata = await getOrCreateAssociatedTokenAccount(connection, authority, mint, authority.publicKey);
+    await mintTo(connection, authority, mint, ata.address, authority, new anchor.BN(amount).toNumber());
+    return mint;
+};
+
+export interface FundEscrowOptions {
+    multisig?: PublicKey;
+    deadline?: anchor.BN;
+    termsHash?: number[];
+    vesting?: object;
+    team?: { recipient: PublicKey; bps: number }[];
+    callback?: { program: PublicKey; accounts: PublicKey[] };
+    // funds an SPL escrow from the sender's ATA of this mint instead of SOL
+    mint?: PublicKey;
+}
+
+// Funds an escrow through initialize_escrow and returns its PDAs.
+export const fundEscrow = async (
+    sender: Keypair,
+    receiver: PublicKey,
+    refSeed: number,
+    amount: anchor.BN,
+    opts: FundEscrowOptions = {}
+): Promise<EscrowPDAs> => {
+    const pdas = await deriveEscrowPDAs(sender.publicKey, receiver, refSeed);
+    const { statsPda } = await deriveUserStatsPDA(sender.publicKey);
+    const mint = opts.mint ?? null;
+
+    await program.methods
+        .initializeEscrow(
+            amount,
+            refSeed,
+            mint !== null,
+            opts.multisig !== undefined,
+            opts.deadline ?? null,
+            opts.termsHash ?? null,
+            (opts.vesting as any) ?? null,
+            opts.team ?? null,
+            opts.callback ?? null
+        )
+        .accountsPartial({
+            sender: sender.publicKey,
+            receiver,
+            escrow: pdas.escrowPda,
+            vault: pdas.vaultPda,
+            senderStats: statsPda,
+            clientMultisig: opts.multisig ?? null,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: mint && TOKEN_PROGRAM_ID,
+            senderTokenAccount: mint && getAssociatedTokenAddressSync(mint, sender.publicKey),
+            escrowTokenAccount: mint && getAssociatedTokenAddressSync(mint, pdas.escrowPda, true),
+            tokenMint: mint,
+            associatedTokenProgram: mint && ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([sender])
+        .rpc();
+
+    return pdas;
+};
+
+// Receiver side of the acceptance handshake, an escrow can only be released once accepted.
+export const acceptEscrow = async (
+    sender: PublicKey,
+    receiver: Keypair,
+    refSeed: number,
+    termsHash: number[] = NO_TERMS
+): Promise<void> => {
+    const { escrowPda } = await deriveEscrowPDAs(sender, receiver.publicKey, refSeed);
+    await program.methods
+        .acceptEscrow(refSeed, termsHash)
+        .accountsStrict({
+            receiver: receiver.publicKey,
+            escrow: escrowPda,
+        })
+        .signers([receiver])
+        .rpc();
+};
+
+// Runs initialize → accept → release → claim `count` times, recording every claim on the freelancer badge.
+// Escrow PDAs are closed on claim, so the same reference seeds can be reused across calls.
+// The default amount clears the seeded min_escrow_value and, per tier escrow count, the tier values.
+export const completeEscrows = async (
+    client: Keypair,
+    freelancer: Keypair,
+    count: number,
+    amount: anchor.BN = new anchor.BN(20_000_000)
+): Promise<void> => {
+    const configPda = await ensureReputationConfig();
+    const { statsPda: clientStatsPda } = await deriveUserStatsPDA(client.publicKey);
+    const { statsPda: freelancerStatsPda } = await deriveUserStatsPDA(freelancer.publicKey);
+    const { badgePda } = await deriveFreelancerBadgePDA(freelancer.publicKey);
+    const counterpartyPda = await deriveCounterpartyPDA(freelancer.publicKey, client.publicKey);
+
+    for (let refSeed = 0; refSeed < count; refSeed++) {
+        const { escrowPda, vaultPda } = await fundEscrow(client, freelancer.publicKey, refSeed, amount);
+
+        await acceptEscrow(client.publicKey, freelancer, refSeed);
+
+        await program.methods
+            .releasePayment(refSeed, null)
+            .accountsStrict({
+                client: client.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+            })
+            .signers([client])
+            .rpc();
+
+        await program.methods
+            .claimPayment(refSeed, false)
+            .accountsPartial({
+                client: client.publicKey,
+                freelancer: freelancer.publicKey,
+                escrow: escrowPda,
+                vault: vaultPda,
+                receiverStats: freelancerStatsPda,
+                clientStats: clientStatsPda,
+                badge: badgePda,
+                counterparty: counterpartyPda,
+                reputationConfig: configPda,
+                multisig: null,
+                payoutApproval: null,
+                payoutMultisig: null,
+                receiverSol: freelancer.publicKey,
+                escrowTokenAccount: null,
+                receiverTokenAccount: null,
+                tokenMint: null,
+                tokenProgram: null,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([freelancer])
+            .rpc();
+    }
+};
@@ -0,0 +1,242 @@
+import { createHash } from "crypto";
+import {
+    ComputeBudgetProgram,
+    Keypair,
+    PublicKey,
+    SystemProgram,
+    Transaction,
+    TransactionInstruction,
+} from "@solana/web3.js";
+import { provider, connection } from "./setup";
+
+// Minimal Squads v4 client, just enough to run Bondr instructions from a vault transaction.
+// The program and its config are cloned from devnet in Anchor.toml.
+export const SQUADS_PROGRAM_ID = new PublicKey("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+
+const SEED_PREFIX = Buffer.from("multisig");
+
+// Initiate | Vote | Execute
+const ALL_PERMISSIONS = 7;
+
+// offsets past the 8-byte account discriminator
+const PROGRAM_CONFIG_TREASURY_OFFSET = 8 + 32 + 8;
+const MULTISIG_TRANSACTION_INDEX_OFFSET = 8 + 32 + 32 + 2 + 4;
+
+const discriminator = (name: string) => createHash("sha256").update(`global:${name}`).digest().subarray(0, 8);
+
+const u8 = (n: number) => Buffer.from([n]);
+const u16 = (n: number) => {
+    const buf = Buffer.alloc(2);
+    buf.writeUInt16LE(n);
+    return buf;
+};
+const u32 = (n: number) => {
+    const buf = Buffer.alloc(4);
+    buf.writeUInt32LE(n);
+    return buf;
+};
+const u64 = (n: number) => {
+    const buf = Buffer.alloc(8);
+    buf.writeUInt32LE(n % 2 ** 32);
+    buf.writeUInt32LE(Math.floor(n / 2 ** 32), 4);
+    return buf;
+};
+const NONE = u8(0);
+
+export const deriveSquadsProgramConfigPDA = () =>
+    PublicKey.findProgramAddressSync([SEED_PREFIX, Buffer.from("program_config")], SQUADS_PROGRAM_ID)[0];
+
+export const deriveSquadsMultisigPDA = (createKey: PublicKey) =>
+    PublicKey.findProgramAddressSync([SEED_PREFIX, Buffer.from("multisig"), createKey.toBuffer()], SQUADS_PROGRAM_ID)[0];
+
+export const deriveSquadsVaultPDA = (multisig: PublicKey, vaultIndex = 0) =>
+    PublicKey.findProgramAddressSync(
+        [SEED_PREFIX, multisig.toBuffer(), Buffer.from("vault"), u8(vaultIndex)],
+        SQUADS_PROGRAM_ID
+    )[0];
+
+const deriveTransactionPDA = (multisig: PublicKey, index: number) =>
+    PublicKey.findProgramAddressSync(
+        [SEED_PREFIX, multisig.toBuffer(), Buffer.from("transaction"), u64(index)],
+        SQUADS_PROGRAM_ID
+    )[0];
+
+const deriveProposalPDA = (multisig: PublicKey, index: number) =>
+    PublicKey.findProgramAddressSync(
+        [SEED_PREFIX, multisig.toBuffer(), Buffer.from("transaction"), u64(index), Buffer.from("proposal")],
+        SQUADS_PROGRAM_ID
+    )[0];
+
+// Creates a multisig where every member holds all permissions, returns it with its default vault.
+export const createSquadsMultisig = async (members: Keypair[], threshold: number) => {
+    const createKey = Keypair.generate();
+    const creator = members[0];
+    const multisig = deriveSquadsMultisigPDA(createKey.publicKey);
+
+    const programConfig = deriveSquadsProgramConfigPDA();
+    const configInfo = await connection.getAccountInfo(programConfig);
+    const treasury = new PublicKey(
+        configInfo!.data.subarray(PROGRAM_CONFIG_TREASURY_OFFSET, PROGRAM_CONFIG_TREASURY_OFFSET + 32)
+    );
+
+    const data = Buffer.concat([
+        discriminator("multisig_create_v2"),
+        NONE, // config_authority, autonomous multisig
+        u16(threshold),
+        u32(members.length),
+        ...members.map((m) => Buffer.concat([m.publicKey.toBuffer(), u8(ALL_PERMISSIONS)])),
+        u32(0), // time_lock
+        NONE, // rent_collector
+        NONE, // memo
+    ]);
+
+    const ix = new TransactionInstruction({
+        programId: SQUADS_PROGRAM_ID,
+        keys: [
+            { pubkey: programConfig, isSigner: false, isWritable: false },
+            { pubkey: treasury, isSigner: false, isWritable: true },
+            { pubkey: multisig, isSigner: false, isWritable: true },
+            { pubkey: createKey.publicKey, isSigner: true, isWritable: false },
+            { pubkey: creator.publicKey, isSigner: true, isWritable: true },
+            { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ],
+        data,
+    });
+    await provider.sendAndConfirm!(new Transaction().add(ix), [createKey, creator]);
+
+    return { multisig, vault: deriveSquadsVaultPDA(multisig) };
+};
+
+// Serializes the instructions as a Squads TransactionMessage with the vault as the only signer.
+// Keys are ordered writable signers, readonly signers, writable and readonly non-signers.
+const compileVaultMessage = (vault: PublicKey, instructions: TransactionInstruction[]) => {
+    const metas = new Map<string, { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }>();
+    const add = (pubkey: PublicKey, isSigner: boolean, isWritable: boolean) => {
+        const meta = metas.get(pubkey.toBase58());
+        if (meta) {
+            meta.isSigner ||= isSigner;
+            meta.isWritable ||= isWritable;
+        } else {
+            metas.set(pubkey.toBase58(), { pubkey, isSigner, isWritable });
+        }
+    };
+
+    add(vault, true, true);
+    for (const ix of instructions) {
+        ix.keys.forEach((k) => add(k.pubkey, k.isSigner, k.isWritable));
+        add(ix.programId, false, false);
+    }
+
+    const all = [...metas.values()];
+    const keys = [
+        ...all.filter((m) => m.isSigner && m.isWritable),
+        ...all.filter((m) => m.isSigner && !m.isWritable),
+        ...all.filter((m) => !m.isSigner && m.isWritable),
+        ...all.filter((m) => !m.isSigner && !m.isWritable),
+    ];
+    const indexOf = (pubkey: PublicKey) => keys.findIndex((k) => k.pubkey.equals(pubkey));
+
+    const compiled = instructions.map((ix) =>
+        Buffer.concat([
+            u8(indexOf(ix.programId)),
+            u8(ix.keys.length),
+            Buffer.from(ix.keys.map((k) => indexOf(k.pubkey))),
+            u16(ix.data.length),
+            ix.data,
+        ])
+    );
+
+    const message = Buffer.concat([
+        u8(keys.filter((k) => k.isSigner).length),
+        u8(keys.filter((k) => k.isSigner && k.isWritable).length),
+        u8(keys.filter((k) => !k.isSigner && k.isWritable).length),
+        u8(keys.length),
+        ...keys.map((k) => k.pubkey.toBuffer()),
+        u8(compiled.length),
+        ...compiled,
+        u8(0), // no address lookup tables
+    ]);
+
+    return { message, keys };
+};
+
+// Runs the instructions as a vault transaction: create → propose → approve by `approvers` → execute.
+export const executeVaultTransaction = async (
+    multisig: PublicKey,
+    approvers: Keypair[],
+    instructions: TransactionInstruction[]
+): Promise<void> => {
+    const member = approvers[0];
+    const vault = deriveSquadsVaultPDA(multisig);
+
+    const multisigInfo = await connection.getAccountInfo(multisig);
+    // transaction indexes stay far below 2^32 in tests
+    const index = multisigInfo!.data.readUInt32LE(MULTISIG_TRANSACTION_INDEX_OFFSET) + 1;
+    const transaction = deriveTransactionPDA(multisig, index);
+    const proposal = deriveProposalPDA(multisig, index);
+
+    const { message, keys } = compileVaultMessage(vault, instructions);
+
+    // 1. Store the vault transaction and open its proposal
+    const createIx = new TransactionInstruction({
+        programId: SQUADS_PROGRAM_ID,
+        keys: [
+            { pubkey: multisig, isSigner: false, isWritable: true },
+            { pubkey: transaction, isSigner: false, isWritable: true },
+            { pubkey: member.publicKey, isSigner: true, isWritable: false },
+            { pubkey: member.publicKey, isSigner: true, isWritable: true },
+            { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ],
+        data: Buffer.concat([
+            discriminator("vault_transaction_create"),
+            u8(0), // vault_index
+            u8(0), // ephemeral_signers
+            u32(message.length),
+            message,
+            NONE, // memo
+        ]),
+    });
+    const proposeIx = new TransactionInstruction({
+        programId: SQUADS_PROGRAM_ID,
+        keys: [
+            { pubkey: multisig, isSigner: false, isWritable: false },
+            { pubkey: proposal, isSigner: false, isWritable: true },
+            { pubkey: member.publicKey, isSigner: true, isWritable: false },
+            { pubkey: member.publicKey, isSigner: true, isWritable: true },
+            { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ],
+        data: Buffer.concat([discriminator("proposal_create"), u64(index), u8(0)]),
+    });
+    await provider.sendAndConfirm!(new Transaction().add(createIx, proposeIx), [member]);
+
+    // 2. Collect the approvals
+    for (const approver of approvers) {
+        const approveIx = new TransactionInstruction({
+            programId: SQUADS_PROGRAM_ID,
+            keys: [
+                { pubkey: multisig, isSigner: false, isWritable: false },
+                { pubkey: approver.publicKey, isSigner: true, isWritable: true },
+                { pubkey: proposal, isSigner: false, isWritable: true },
+            ],
+            data: Buffer.concat([discriminator("proposal_approve"), NONE]),
+        });
+        await provider.sendAndConfirm!(new Transaction().add(approveIx), [approver]);
+    }
+
+    // 3. Execute, Squads signs for the vault and CPIs into each instruction
+    const executeIx = new TransactionInstruction({
+        programId: SQUADS_PROGRAM_ID,
+        keys: [
+            { pubkey: multisig, isSigner: false, isWritable: false },
+            { pubkey: proposal, isSigner: false, isWritable: true },
+            { pubkey: transaction, isSigner: false, isWritable: false },
+            { pubkey: member.publicKey, isSigner: true, isWritable: false },
+            ...keys.map((k) => ({ pubkey: k.pubkey, isSigner: false, isWritable: k.isWritable })),
+        ],
+        data: discriminator("vault_transaction_execute"),
+    });
+    await provider.sendAndConfirm!(
+        new Transaction().add(ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }), executeIx),
+        [member]
+    );
+};